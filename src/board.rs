@@ -0,0 +1,1052 @@
+use std::default;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::candidate_set::CandidateSet;
+use crate::coord::Coord;
+use crate::error::Error;
+use crate::moves::Move;
+use crate::region::{BlockMut, BlockRef, ColumnMut, ColumnRef, RegionMut, RegionRef, RowMut, RowRef};
+use crate::sized::is_separator;
+use crate::strategies::house_name;
+use crate::symbols::SymbolSet;
+use crate::topology::{BLOCK_OF, COLUMN_OF, COUNT_PEERS, HOUSES_OF, PEERS, ROW_OF};
+
+pub type Value = u8;
+
+/// `Cell` is a wrapper for `u8`. Its only purpose is
+/// to provide convenient string representations for the
+/// content of a cell.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct Cell(Value);
+
+impl Eq for Cell {}
+
+impl Cell {
+    /// A cell holding `value` (0 means unassigned)
+    pub fn new(value: Value) -> Self {
+        Self(value)
+    }
+
+    /// The raw value stored in this cell (0 means unassigned)
+    pub fn value(&self) -> Value {
+        self.0
+    }
+
+    /// The symbol of the value in the set, `SymbolSet::BLANK` for unassigned cells
+    pub fn symbol(&self, symbols: &SymbolSet) -> char {
+        // NOTE: "I" as in "invalid"
+        symbols.symbol_of(self.0).unwrap_or('I')
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if 1 <= self.0 && self.0 <= 9 {
+            write!(f, "{:^3}", self.0)
+        } else {
+            // NOTE: "I" as in "invalid"
+            write!(f, " I ")
+        }
+    }
+}
+
+/// A cell whose value differs between two boards, as listed by `Board::diff`
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Hash)]
+pub struct CellChange {
+    pub cell: Coord,
+    /// Value on the board `diff` was called on (0 means unassigned)
+    pub before: Value,
+    /// Value on the other board (0 means unassigned)
+    pub after: Value,
+}
+
+impl fmt::Display for CellChange {
+    /// E.g. `r4c5: 0 -> 7`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {} -> {}", self.cell, self.before, self.after)
+    }
+}
+
+/// `Slice` is a set of 9 cells. Sudoku often operates on 9 cell elements.
+/// A `Slice` is the result if you access a column, row, or block by some index.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct Slice([Cell; 9]);
+
+impl Slice {
+    pub fn set(&mut self, index: usize, cell: Cell) {
+        self.0[index] = cell;
+    }
+
+    /// The 9 cells of this slice in order
+    pub fn cells(&self) -> &[Cell; 9] {
+        &self.0
+    }
+
+    /// Iterate over the cells of this slice in order
+    pub fn iter(&self) -> std::slice::Iter<'_, Cell> {
+        self.0.iter()
+    }
+
+    /// Does this slice contain the provided `Value`?
+    pub fn has(&self, value: Value) -> bool {
+        self.iter().any(|cell| cell.0 == value)
+    }
+
+    /// Are the admissible Sudoku values inside the cells unique?
+    pub fn has_unique_sudoku_values(&self) -> bool {
+        let mut seen = CandidateSet::empty();
+        for cell in self.iter() {
+            // NOTE: consider only admissible values
+            if 1 <= cell.0 && cell.0 <= 9 && !seen.insert(cell.0) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Which Sudoku values are used in this `Slice`?
+    pub fn sudoku_values(&self) -> CandidateSet {
+        self.iter().map(|cell| cell.0).collect()
+    }
+
+    /// Which Sudoku values are unused in this `Slice`?
+    pub fn unused_sudoku_values(&self) -> CandidateSet {
+        !self.sudoku_values()
+    }
+}
+
+impl Index<usize> for Slice {
+    type Output = Cell;
+
+    fn index(&self, index: usize) -> &Cell {
+        &self.0[index]
+    }
+}
+
+impl IntoIterator for Slice {
+    type Item = Cell;
+    type IntoIter = std::array::IntoIter<Cell, 9>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Slice {
+    type Item = &'a Cell;
+    type IntoIter = std::slice::Iter<'a, Cell>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl default::Default for Slice {
+    fn default() -> Self {
+        Self([Cell(0); 9])
+    }
+}
+
+impl fmt::Display for Slice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.iter().map(|cell| format!("{}", cell)).collect::<Vec<String>>().join(""))
+    }
+}
+
+/// The sudoku board containing 81 `Cell`s.
+/// Each cell is identified by some index or its row & column tuple.
+/// The `Cell` store the value (0 means unassigned, 1..=9 are Sudoku values)
+/// 
+/// Changes from outside of the crate are checked: `try_set`, `with_move` and the
+/// regions of `row_mut` and friends refuse values outside of 0..=9 and overwriting
+/// givens, `try_set_consistent` and `apply_moves` also refuse values repeating one
+/// of the same row, column or block. Indexing mutably stores any value, but it
+/// panics for givens like for cells outside of the board.
+/// The placements within the crate are unchecked, so use them with care!
+#[derive(Clone,Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(into = "BoardData", try_from = "BoardData"))]
+pub struct Board {
+    cells: [Cell; Self::COUNT_ROWS * Self::COUNT_COLUMNS],
+    /// Occupancy masks of every row, column and block (bit `v` is set if value `v` occurs).
+    /// They are kept up to date with `cells` by every placement and removal.
+    row_masks: [u16; Self::COUNT_ROWS],
+    column_masks: [u16; Self::COUNT_COLUMNS],
+    block_masks: [u16; Self::COUNT_BLOCKS],
+    /// Bit `i` is set if cell `i` holds an original clue of the puzzle
+    givens: u128,
+    /// Pencil marks of the player per cell, independent of the computed candidates.
+    /// Empty until the first note is added, so copying boards without notes stays cheap.
+    notes: Vec<CandidateSet>,
+    /// Set once cells were changed through `IndexMut` so the masks may be outdated.
+    /// They are rebuilt before the next placement.
+    masks_stale: bool,
+}
+
+impl Board {
+    pub const COUNT_VALUES: usize = 9 * 9;
+    pub const COUNT_BLOCKS: usize = 9;
+    pub const COUNT_ROWS: usize = 9;
+    pub const COUNT_COLUMNS: usize = 9;
+
+    /// Update the board's entries using the values provided.
+    /// All values are provided in one long linear array
+    /// from top-left to top-right until the last row and finally bottom-right.
+    pub(crate) fn from_flattened_values(values: &[Value; Self::COUNT_VALUES]) -> Self {
+        let mut new_cells = [Cell(0); Self::COUNT_VALUES];
+        for i in 0..Self::COUNT_VALUES {
+            new_cells[i] = Cell(values[i]);
+        }
+        Self::from_cells(new_cells)
+    }
+
+    /// Update the board's entries with the values provided per row in one array.
+    /// Specifically, there are as many arrays as there are rows on the board.
+    /// And there are as many entries per row as there are columns.
+    pub(crate) fn from_values_per_row(values: &[[Value; Self::COUNT_COLUMNS]; Self::COUNT_ROWS]) -> Self {
+        let mut new_cells = [Cell(0); Self::COUNT_VALUES];
+        for row_id in 0..Self::COUNT_ROWS {
+            for column_id in 0..Self::COUNT_COLUMNS {
+                new_cells[row_id * Self::COUNT_COLUMNS + column_id] = Cell(values[row_id][column_id]);
+            }
+        }
+        Self::from_cells(new_cells)
+    }
+
+    /// Return a board holding the values, e.g. a solution of this board.
+    /// The givens of this board stay givens, all other values become entries.
+    pub(crate) fn with_values(&self, values: &[Value; Self::COUNT_VALUES]) -> Self {
+        let mut board = Self::from_flattened_values(values);
+        board.givens = self.givens;
+        board
+    }
+
+    /// The values of all cells in reading order (0 means unassigned)
+    pub(crate) fn values(&self) -> [Value; Self::COUNT_VALUES] {
+        self.cells.map(|cell| cell.0)
+    }
+
+    /// Create the board and compute its occupancy masks.
+    /// All assigned cells become givens.
+    fn from_cells(cells: [Cell; Self::COUNT_VALUES]) -> Self {
+        let mut board = Self { cells, masks_stale: true, ..Self::default() };
+        board.refresh_masks();
+        board.mark_givens();
+        board
+    }
+
+    /// Rebuild the occupancy masks from scratch if cells were changed behind their back
+    fn refresh_masks(&mut self) {
+        if !self.masks_stale {
+            return;
+        }
+        self.row_masks = [0; Self::COUNT_ROWS];
+        self.column_masks = [0; Self::COUNT_COLUMNS];
+        self.block_masks = [0; Self::COUNT_BLOCKS];
+        for cell_id in 0..Self::COUNT_VALUES {
+            self.occupy(cell_id);
+        }
+        self.masks_stale = false;
+    }
+
+    /// Cell ID given a zero-based row and column. Panics if either lies outside of the board.
+    fn cell_id_of(row: usize, col: usize) -> usize {
+        assert!(row < Self::COUNT_ROWS && col < Self::COUNT_COLUMNS, "cell (row {row}, column {col}) is outside of the board");
+        row * Self::COUNT_COLUMNS + col
+    }
+
+    /// Row, column and block identifier of a cell
+    fn houses_of(cell_id: usize) -> (usize, usize, usize) {
+        (ROW_OF[cell_id], COLUMN_OF[cell_id], Self::block_id_of(cell_id))
+    }
+
+    /// Identifier of the block containing the cell, from 0 (top-left) to 8 (bottom-right)
+    pub fn block_id_of(cell_id: usize) -> usize {
+        BLOCK_OF[cell_id]
+    }
+
+    /// The IDs of the 20 cells sharing the row, the column or the block with the cell
+    /// in increasing order. The cell itself is not a peer.
+    pub fn peers(cell_id: usize) -> &'static [usize; COUNT_PEERS] {
+        &PEERS[cell_id]
+    }
+
+    /// Set the occupancy bits of the cell's value (if admissible)
+    fn occupy(&mut self, cell_id: usize) {
+        let value = self.cells[cell_id].0;
+        if !(1..=9).contains(&value) {
+            return;
+        }
+        let (row_id, column_id, block_id) = Self::houses_of(cell_id);
+        self.row_masks[row_id] |= 1 << value;
+        self.column_masks[column_id] |= 1 << value;
+        self.block_masks[block_id] |= 1 << value;
+    }
+
+    /// Clear the occupancy bits of the cell's value unless another cell of the house still holds it
+    fn vacate(&mut self, cell_id: usize) {
+        let value = self.cells[cell_id].0;
+        if !(1..=9).contains(&value) {
+            return;
+        }
+        let (row_id, column_id, block_id) = Self::houses_of(cell_id);
+        // NOTE: an invalid board may hold the value twice in one house
+        if !self.row_ref(row_id).has_besides(value, column_id) {
+            self.row_masks[row_id] &= !(1 << value);
+        }
+        if !self.column_ref(column_id).has_besides(value, row_id) {
+            self.column_masks[column_id] &= !(1 << value);
+        }
+        if !self.block_ref(block_id).has_besides(value, (row_id % 3) * 3 + column_id % 3) {
+            self.block_masks[block_id] &= !(1 << value);
+        }
+    }
+
+    /// Store `value` in the cell and update the occupancy masks
+    fn set_cell(&mut self, cell_id: usize, value: Value) {
+        self.refresh_masks();
+        self.vacate(cell_id);
+        self.cells[cell_id] = Cell(value);
+        self.occupy(cell_id);
+    }
+
+    /// The values which can be placed in the unassigned cell without repeating
+    /// a value of its row, its column or its block. Empty for assigned cells.
+    /// This only intersects the occupancy masks, so it takes constant time.
+    pub fn legal_values(&self, cell_id: usize) -> CandidateSet {
+        if self.cells[cell_id].0 != 0 {
+            return CandidateSet::empty();
+        }
+        !self.used_values(cell_id)
+    }
+
+    /// The candidates of the cell: all values which don't repeat a value of another
+    /// cell in its row, its column or its block. Unlike `legal_values`, the cell's
+    /// own value is ignored, so for an assigned cell these are the values it could
+    /// be changed to. Empty for cell IDs outside of the board.
+    pub fn candidates_for(&self, cell_id: usize) -> CandidateSet {
+        if cell_id >= Self::COUNT_VALUES {
+            return CandidateSet::empty();
+        }
+        if self.cells[cell_id].0 == 0 {
+            return !self.used_values(cell_id);
+        }
+        CandidateSet::all().iter().filter(|value| self.is_valid_placement(cell_id, *value)).collect()
+    }
+
+    /// The values occurring in the row, the column or the block of the cell
+    fn used_values(&self, cell_id: usize) -> CandidateSet {
+        if self.masks_stale {
+            return HOUSES_OF[cell_id].iter().fold(CandidateSet::empty(), |used, house_id| used | self.house_ref(*house_id).sudoku_values());
+        }
+        let (row_id, column_id, block_id) = Self::houses_of(cell_id);
+        CandidateSet::from_bits(self.row_masks[row_id] | self.column_masks[column_id] | self.block_masks[block_id])
+    }
+
+    /// Can `value` go into the cell without repeating a value of another cell
+    /// in its row, its column or its block? A value already in the cell is ignored,
+    /// so this also tells whether an entry conflicts with the rest of the board.
+    /// Returns `false` for cell IDs outside of the board and values outside of 1..=9.
+    pub fn is_valid_placement(&self, cell_id: usize, value: Value) -> bool {
+        if cell_id >= Self::COUNT_VALUES || !(1..=9).contains(&value) {
+            return false;
+        }
+        if self.cells[cell_id].0 == value {
+            // NOTE: the occupancy masks cannot tell whether a peer holds the value, too
+            return PEERS[cell_id].iter().all(|peer_id| self.cells[*peer_id].0 != value);
+        }
+        !self.used_values(cell_id).contains(value)
+    }
+
+    /// Return the cell given its zero-based row and column number
+    pub(crate) fn index_by_row_and_col(&self, row: usize, col: usize) -> Cell {
+        self[(row, col)]
+    }
+
+    /// Return the set of indices of unassigned values
+    pub(crate) fn unassigned(&self) -> Vec<usize> {
+        let mut unassigned = vec![];
+        for cell_id in 0..Board::COUNT_VALUES {
+            // ASSUME: cells with value "0" are "unassigned"
+            if self[cell_id].0 == 0 {
+                unassigned.push(cell_id);
+            }
+        }
+        unassigned
+    }
+
+    /// Return the cells of a block (9×9) given an identifier from 0 to 8.
+    /// 0 is at the top-left, 2 is at the top-right, 8 is at the bottom-right.
+    pub(crate) fn block(&self, block_id: usize) -> Slice {
+        self.house(18 + block_id)
+    }
+
+    /// Return the cells of a house given an identifier from 0 to 26 (see `topology::HOUSES`)
+    fn house(&self, house_id: usize) -> Slice {
+        self.house_ref(house_id).to_slice()
+    }
+
+    /// Borrowed view of a house given an identifier from 0 to 26 (see `topology::HOUSES`)
+    fn house_ref(&self, house_id: usize) -> RegionRef<'_> {
+        RegionRef::new(self, house_id)
+    }
+
+    /// Borrowed view of a row given a row identifier from 0 to 8. Unlike `rows`,
+    /// this does not copy the cells.
+    pub fn row_ref(&self, row_id: usize) -> RowRef<'_> {
+        assert!(row_id < Self::COUNT_ROWS, "row {row_id} is outside of the board");
+        self.house_ref(row_id)
+    }
+
+    /// Borrowed view of a column given a column identifier from 0 to 8
+    pub fn column_ref(&self, column_id: usize) -> ColumnRef<'_> {
+        assert!(column_id < Self::COUNT_COLUMNS, "column {column_id} is outside of the board");
+        self.house_ref(9 + column_id)
+    }
+
+    /// Borrowed view of a block given a block identifier from 0 to 8 (0 is at the top-left)
+    pub fn block_ref(&self, block_id: usize) -> BlockRef<'_> {
+        assert!(block_id < Self::COUNT_BLOCKS, "block {block_id} is outside of the board");
+        self.house_ref(18 + block_id)
+    }
+
+    /// Return the cells of a row given a row identifier from 0 to 8.
+    pub(crate) fn row(&self, row_id: usize) -> Slice {
+        self.house(row_id)
+    }
+
+    /// Return the cells of a column given a column identifier from 0 to 8.
+    pub(crate) fn column(&self, column_id: usize) -> Slice {
+        self.house(9 + column_id)
+    }
+
+    /// The cells whose values differ from `other` in increasing order of cell IDs.
+    /// Givens and pencil marks are not compared.
+    pub fn diff(&self, other: &Board) -> Vec<CellChange> {
+        (0..Self::COUNT_VALUES)
+            .filter(|cell_id| self.cells[*cell_id] != other.cells[*cell_id])
+            .map(|cell_id| CellChange { cell: Coord::from_cell_id(cell_id), before: self.cells[cell_id].0, after: other.cells[cell_id].0 })
+            .collect()
+    }
+
+    /// All rows from top to bottom together with their row identifiers
+    pub fn rows(&self) -> impl Iterator<Item = (usize, Slice)> + '_ {
+        (0..Self::COUNT_ROWS).map(|row_id| (row_id, self.row(row_id)))
+    }
+
+    /// All columns from left to right together with their column identifiers
+    pub fn columns(&self) -> impl Iterator<Item = (usize, Slice)> + '_ {
+        (0..Self::COUNT_COLUMNS).map(|column_id| (column_id, self.column(column_id)))
+    }
+
+    /// All blocks in reading order together with their block identifiers
+    pub fn blocks(&self) -> impl Iterator<Item = (usize, Slice)> + '_ {
+        (0..Self::COUNT_BLOCKS).map(|block_id| (block_id, self.block(block_id)))
+    }
+
+    /// Mutable view of a row given a row identifier from 0 to 8, e.g. to fill it at once
+    pub fn row_mut(&mut self, row_id: usize) -> RowMut<'_> {
+        assert!(row_id < Self::COUNT_ROWS, "row {row_id} is outside of the board");
+        RegionMut::new(self, row_id)
+    }
+
+    /// Mutable view of a column given a column identifier from 0 to 8
+    pub fn column_mut(&mut self, column_id: usize) -> ColumnMut<'_> {
+        assert!(column_id < Self::COUNT_COLUMNS, "column {column_id} is outside of the board");
+        RegionMut::new(self, 9 + column_id)
+    }
+
+    /// Mutable view of a block given a block identifier from 0 to 8 (0 is at the top-left)
+    pub fn block_mut(&mut self, block_id: usize) -> BlockMut<'_> {
+        assert!(block_id < Self::COUNT_BLOCKS, "block {block_id} is outside of the board");
+        RegionMut::new(self, 18 + block_id)
+    }
+
+    /// The unassigned cell with the fewest legal values together with those values
+    /// (ties are broken by the smallest cell ID). `None` if every cell is assigned.
+    /// A cell without any legal value means the board cannot be completed.
+    pub fn most_constrained_cell(&self) -> Option<(usize, CandidateSet)> {
+        self.unassigned().into_iter()
+            .map(|cell_id| (cell_id, self.legal_values(cell_id)))
+            .min_by_key(|(_, values)| values.len())
+    }
+
+    /// Store `value` in the cell after checking that the cell ID lies in `0..81`
+    /// and the value in `0..=9` (0 clears the cell). Fails with ``Error::IdError``,
+    /// ``Error::ValueError`` or, if the cell holds a given, ``Error::GivenError``
+    /// and leaves the board unchanged.
+    pub fn try_set(&mut self, cell_id: usize, value: Value) -> Result<(), Error> {
+        if cell_id >= Self::COUNT_VALUES {
+            return Err(Error::IdError { admissible: 0..Self::COUNT_VALUES, actual: cell_id });
+        }
+        if value > 9 {
+            return Err(Error::ValueError { value, expected: "0 (unassigned) or 1..=9".to_string() });
+        }
+        if self.is_given(cell_id) {
+            return Err(Error::GivenError { cell: Coord::from_cell_id(cell_id) });
+        }
+        self.place(cell_id, value);
+        Ok(())
+    }
+
+    /// Does the cell hold an original clue of the puzzle (rather than a later entry)?
+    pub fn is_given(&self, cell_id: usize) -> bool {
+        cell_id < Self::COUNT_VALUES && self.givens & (1 << cell_id) != 0
+    }
+
+    /// The IDs of all cells holding givens in increasing order
+    pub fn givens(&self) -> Vec<usize> {
+        (0..Self::COUNT_VALUES).filter(|cell_id| self.is_given(*cell_id)).collect()
+    }
+
+    /// Turn all assigned cells into givens, e.g. after entering a puzzle cell by cell
+    pub fn mark_givens(&mut self) {
+        self.givens = 0;
+        for cell_id in 0..Self::COUNT_VALUES {
+            if self.cells[cell_id].0 != 0 {
+                self.givens |= 1 << cell_id;
+            }
+        }
+    }
+
+    /// Return to the original puzzle by clearing every cell which is not a given
+    pub fn reset(&mut self) {
+        for cell_id in 0..Self::COUNT_VALUES {
+            if !self.is_given(cell_id) && self.cells[cell_id].0 != 0 {
+                self.unplace(cell_id);
+            }
+        }
+    }
+
+    /// Like `try_set`, but additionally refuse values repeating a value of another
+    /// cell in the same row, column or block with ``Error::ConstraintError``
+    /// for the first such house.
+    pub fn try_set_consistent(&mut self, cell_id: usize, value: Value) -> Result<(), Error> {
+        if cell_id < Self::COUNT_VALUES && value != 0 && !self.is_given(cell_id) && !self.is_valid_placement(cell_id, value) {
+            let mut updated = self.clone();
+            updated.place(cell_id, value);
+            let house_id = HOUSES_OF[cell_id].into_iter()
+                .find(|house_id| !updated.house_ref(*house_id).has_unique_sudoku_values())
+                .unwrap_or(HOUSES_OF[cell_id][0]);
+            return Err(Error::ConstraintError { region: house_name(house_id), slice: updated.house(house_id) });
+        }
+        self.try_set(cell_id, value)
+    }
+
+    /// Apply the move like `try_set_consistent`. Also fails with ``Error::IdError``
+    /// if the move's row or column lies outside of the board.
+    pub(crate) fn try_move(&mut self, placement: &Move) -> Result<(), Error> {
+        if placement.cell.row >= Self::COUNT_ROWS || placement.cell.col >= Self::COUNT_COLUMNS {
+            return Err(Error::IdError { admissible: 0..Self::COUNT_VALUES, actual: placement.cell_id() });
+        }
+        self.try_set_consistent(placement.cell_id(), placement.value)
+    }
+
+    /// Return a copy of the board with all moves applied in order.
+    /// Every move is checked against the board as left by the previous ones, like
+    /// `try_set_consistent`. The first move which fails yields its error; this
+    /// board is never changed.
+    pub fn apply_moves(&self, placements: &[Move]) -> Result<Board, Error> {
+        let mut board = self.clone();
+        for placement in placements {
+            board.try_move(placement)?;
+        }
+        Ok(board)
+    }
+
+    /// Add `value` to the pencil marks of the cell and return whether it was missing.
+    /// Values outside of 1..=9 are ignored.
+    pub fn add_note(&mut self, cell_id: usize, value: Value) -> bool {
+        assert!(cell_id < Self::COUNT_VALUES, "cell {cell_id} is outside of the board");
+        if self.notes.is_empty() {
+            self.notes = vec![CandidateSet::empty(); Self::COUNT_VALUES];
+        }
+        self.notes[cell_id].insert(value)
+    }
+
+    /// Remove `value` from the pencil marks of the cell and return whether it was noted
+    pub fn remove_note(&mut self, cell_id: usize, value: Value) -> bool {
+        self.notes.get_mut(cell_id).is_some_and(|notes| notes.remove(value))
+    }
+
+    /// Remove all pencil marks of the cell
+    pub fn clear_notes(&mut self, cell_id: usize) {
+        if let Some(notes) = self.notes.get_mut(cell_id) {
+            *notes = CandidateSet::empty();
+        }
+    }
+
+    /// The pencil marks of the cell. They are managed by the player only, so
+    /// unlike `legal_values` they are not updated by placements on the board.
+    pub fn notes(&self, cell_id: usize) -> CandidateSet {
+        self.notes.get(cell_id).copied().unwrap_or_default()
+    }
+
+    /// Return a copy of the board with the move applied, checked like `try_set`: fails
+    /// with ``Error::IdError`` if the move's cell lies outside of the board, with
+    /// ``Error::ValueError`` for values above 9 and with ``Error::GivenError`` for a given.
+    /// Unlike `apply_moves`, the value may repeat one of its row, column or block.
+    pub fn with_move(&self, placement: &Move) -> Result<Board, Error> {
+        if placement.cell.row >= Self::COUNT_ROWS || placement.cell.col >= Self::COUNT_COLUMNS {
+            return Err(Error::IdError { admissible: 0..Self::COUNT_VALUES, actual: placement.cell_id() });
+        }
+        let mut board = self.clone();
+        board.try_set(placement.cell_id(), placement.value)?;
+        Ok(board)
+    }
+
+    /// Store `value` in the cell of this board without copying it
+    pub(crate) fn place(&mut self, cell_id: usize, value: Value) {
+        self.set_cell(cell_id, value);
+    }
+
+    /// Mark the cell as unassigned again and return the value it held
+    pub(crate) fn unplace(&mut self, cell_id: usize) -> Value {
+        let value = self.cells[cell_id].0;
+        self.set_cell(cell_id, 0);
+        value
+    }
+
+    /// Parse a grid of 9 lines of 9 cells each, as copied from websites and newspapers.
+    /// Values are digits and blanks are `.` or `0`. Whitespace, the separators `|`, `-`
+    /// and `+` as well as box-drawing characters are ignored, so lines holding nothing
+    /// else (e.g. `------+-------+------`) separate the blocks. All assigned cells become givens.
+    pub fn parse_grid(s: &str) -> Result<Self, Error> {
+        let error = |reason: String| Error::ParseError { input: s.to_string(), reason };
+
+        let mut values = vec![];
+        let mut count_rows = 0;
+        for (line_id, line) in s.lines().enumerate() {
+            let mut row = vec![];
+            for symbol in line.chars().filter(|symbol| !is_separator(*symbol)) {
+                match symbol {
+                    '.' | '0' => row.push(0),
+                    '1'..='9' => row.push(symbol as Value - b'0'),
+                    _ => return Err(error(format!("unexpected character {symbol:?} in line {}", line_id + 1))),
+                }
+            }
+            if row.is_empty() {
+                continue;
+            }
+            if row.len() != Self::COUNT_COLUMNS {
+                return Err(error(format!("expected {} cells in line {} but got {}", Self::COUNT_COLUMNS, line_id + 1, row.len())));
+            }
+            count_rows += 1;
+            values.extend(row);
+        }
+        match values.try_into() {
+            Ok(values) => Ok(Self::from_flattened_values(&values)),
+            Err(_) => Err(error(format!("expected {} rows but got {count_rows}", Self::COUNT_ROWS))),
+        }
+    }
+
+    /// Parse the 81 cells of a board in reading order, writing values as symbols of the set
+    /// and blanks as `.` or `0` (see `SymbolSet::value_of`). Whitespace is ignored.
+    /// All assigned cells become givens.
+    pub fn parse_with(s: &str, symbols: &SymbolSet) -> Result<Self, Error> {
+        let error = |reason: String| Error::ParseError { input: s.to_string(), reason };
+
+        let mut values = vec![];
+        for symbol in s.chars().filter(|symbol| !symbol.is_whitespace()) {
+            match symbols.value_of(symbol) {
+                Some(value) => values.push(value),
+                None => return Err(error(format!("unexpected character {symbol:?} for the symbols {symbols}"))),
+            }
+        }
+        match values.try_into() {
+            Ok(values) => Ok(Self::from_flattened_values(&values)),
+            Err(values) => Err(error(format!("expected {} cells but got {}", Self::COUNT_VALUES, values.len()))),
+        }
+    }
+
+    /// The one-line format read by `Board::from_str`: the 81 cells in reading order
+    /// with digits for values and `blank` for unassigned and invalid cells,
+    /// e.g. `53..7....6..195...` with the blank `.`.
+    pub fn to_line_string(&self, blank: char) -> String {
+        self.cells.iter().map(|cell| match cell.0 {
+            value @ 1..=9 => (b'0' + value) as char,
+            _ => blank,
+        }).collect()
+    }
+
+    /// String representation of the `Board` like `Display`, but with the values
+    /// written as symbols of the set and unassigned cells as `SymbolSet::BLANK`.
+    /// The columns are as wide as the widest symbol, e.g. two characters for emoji.
+    pub fn to_string_with(&self, symbols: &SymbolSet) -> String {
+        let border = "─".repeat(Self::COUNT_COLUMNS * (symbols.width() + 2));
+        let mut out = format!("┌{border}┐\n");
+        for row_id in 0..Self::COUNT_ROWS {
+            out.push('│');
+            for cell in self.cells[9 * row_id..9 * row_id + 9].iter() {
+                out.push_str(&symbols.to_padded_string(cell.0));
+            }
+            out.push_str("│\n");
+        }
+        out.push_str(&format!("└{border}┘\n"));
+        out
+    }
+
+    /// String representation of the `Board`, but highlight the cell at the given index
+    pub fn to_highlighted_string(&self, highlighted_cell: usize) -> String {
+        let mut out = format!("┌{}┐\n", "─".repeat(27));
+
+        for row_id in 0..Self::COUNT_ROWS {
+            out.push('│');
+            for column_id in 0..Self::COUNT_COLUMNS {
+                let cell_id = Self::COUNT_COLUMNS * row_id + column_id;
+                let cell = self.index_by_row_and_col(row_id, column_id);
+                if cell_id == highlighted_cell {
+                    out.push_str(&format!("\x1B[0;33m{}\x1B[0;39m", cell));
+                } else {
+                    out.push_str(&format!("{}", cell));
+                }
+            }
+            out.push_str("│\n");
+        }
+
+        out.push_str(&format!("└{}┘", "─".repeat(27)));
+        out
+    }
+
+    /// String representation of the `Board` with the pencil marks of every unassigned cell.
+    /// Each cell takes 3×3 characters: an assigned cell shows its value in the center,
+    /// an unassigned cell shows each noted value `v` at position `v` of a phone keypad.
+    pub fn to_notes_string(&self) -> String {
+        let block_line = |left: &str, middle: &str, right: &str| {
+            format!("{}{}{}\n", left, vec!["─".repeat(3 * 3 + 4); 3].join(middle), right)
+        };
+
+        let mut out = block_line("┌", "┬", "┐");
+        for row_id in 0..Self::COUNT_ROWS {
+            if row_id > 0 && row_id % 3 == 0 {
+                out.push_str(&block_line("├", "┼", "┤"));
+            }
+            for line in 0..3 {
+                out.push('│');
+                for column_id in 0..Self::COUNT_COLUMNS {
+                    out.push(' ');
+                    let cell_id = Self::COUNT_COLUMNS * row_id + column_id;
+                    for position in 0..3 {
+                        let value = (3 * line + position + 1) as Value;
+                        let shown = match self.cells[cell_id].0 {
+                            0 if self.notes(cell_id).contains(value) => (b'0' + value) as char,
+                            0 => '.',
+                            _ if line != 1 || position != 1 => ' ',
+                            assigned if (1..=9).contains(&assigned) => (b'0' + assigned) as char,
+                            // NOTE: "I" as in "invalid"
+                            _ => 'I',
+                        };
+                        out.push(shown);
+                    }
+                    if column_id % 3 == 2 {
+                        out.push_str(" │");
+                    }
+                }
+                out.push('\n');
+            }
+        }
+        out.push_str(&block_line("└", "┴", "┘"));
+        out
+    }
+}
+
+impl Index<usize> for Board {
+    type Output = Cell;
+
+    fn index(&self, id: usize) -> &Self::Output {
+        &self.cells[id]
+    }
+}
+
+impl IndexMut<usize> for Board {
+    /// Panics if the cell holds a given, like for a cell outside of the board (see `Board::try_set`)
+    fn index_mut(&mut self, id: usize) -> &mut Self::Output {
+        assert!(!self.is_given(id), "cell {} holds a given of the puzzle which cannot be changed", Coord::from_cell_id(id));
+        self.masks_stale = true;
+        &mut self.cells[id]
+    }
+}
+
+impl Index<Coord> for Board {
+    type Output = Cell;
+
+    fn index(&self, coord: Coord) -> &Self::Output {
+        &self[Self::cell_id_of(coord.row, coord.col)]
+    }
+}
+
+impl IndexMut<Coord> for Board {
+    fn index_mut(&mut self, coord: Coord) -> &mut Self::Output {
+        &mut self[Self::cell_id_of(coord.row, coord.col)]
+    }
+}
+
+impl Index<(usize, usize)> for Board {
+    type Output = Cell;
+
+    /// Index by zero-based `(row, column)`
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        &self[Self::cell_id_of(row, col)]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Board {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        &mut self[Self::cell_id_of(row, col)]
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self {
+            cells: [Cell(0); 9 * 9],
+            row_masks: [0; Self::COUNT_ROWS],
+            column_masks: [0; Self::COUNT_COLUMNS],
+            block_masks: [0; Self::COUNT_BLOCKS],
+            givens: 0,
+            notes: vec![],
+            masks_stale: false,
+        }
+    }
+}
+
+impl FromStr for Board {
+    type Err = Error;
+
+    /// Parse the one-line format: the 81 cells in reading order with digits for values
+    /// and `.` or `0` for blanks, e.g. `53..7....6..195...`. Surrounding whitespace is
+    /// ignored. All assigned cells become givens. `Board::to_line_string` writes it.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let error = |reason: String| Error::ParseError { input: s.to_string(), reason };
+
+        let line = s.trim();
+        let mut values = [0; Self::COUNT_VALUES];
+        for (i, symbol) in line.chars().enumerate() {
+            let value = match symbol {
+                '.' | '0' => 0,
+                '1'..='9' => symbol as Value - b'0',
+                _ => return Err(error(format!("unexpected character {symbol:?} at position {}", i + 1))),
+            };
+            if i < Self::COUNT_VALUES {
+                values[i] = value;
+            }
+        }
+        let count_cells = line.chars().count();
+        if count_cells != Self::COUNT_VALUES {
+            return Err(error(format!("expected {} cells but got {count_cells}", Self::COUNT_VALUES)));
+        }
+        Ok(Self::from_flattened_values(&values))
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "┌{}┐", "─".repeat(27))?;
+
+        for row_id in 0..9 {
+            write!(f, "│")?;
+            write!(f, "{}", self.cells[9 * row_id..9 * row_id + 9].iter().map(|cell| (*cell).to_string()).collect::<Vec<String>>().join(""))?;
+            writeln!(f, "│")?;
+        }
+
+        writeln!(f, "└{}┘", "─".repeat(27))
+    }
+}
+
+
+/// The board as serialized: the values in the one-line format with `.` for unassigned
+/// cells, the IDs of the cells holding givens and the pencil marks of unassigned cells
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BoardData {
+    cells: String,
+    /// All assigned cells if missing
+    #[serde(default)]
+    givens: Option<Vec<usize>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    notes: Vec<NotesData>,
+}
+
+/// The pencil marks of a cell
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct NotesData {
+    cell: usize,
+    values: Vec<Value>,
+}
+
+#[cfg(feature = "serde")]
+impl From<Board> for BoardData {
+    fn from(board: Board) -> Self {
+        let cells = board.to_line_string('.');
+        let notes = (0..Board::COUNT_VALUES)
+            .filter(|cell_id| !board.notes(*cell_id).is_empty())
+            .map(|cell_id| NotesData { cell: cell_id, values: board.notes(cell_id).iter().collect() })
+            .collect();
+        Self { cells, givens: Some(board.givens()), notes }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<BoardData> for Board {
+    type Error = Error;
+
+    fn try_from(data: BoardData) -> Result<Self, Error> {
+        let mut board: Board = data.cells.parse()?;
+        if let Some(givens) = data.givens {
+            if let Some(cell_id) = givens.iter().find(|cell_id| **cell_id >= Board::COUNT_VALUES || board[**cell_id].0 == 0) {
+                return Err(Error::ParseError { input: data.cells, reason: format!("given {cell_id} is not an assigned cell") });
+            }
+            board.givens = givens.iter().fold(0, |givens, cell_id| givens | 1 << cell_id);
+        }
+        for notes in data.notes {
+            if notes.cell >= Board::COUNT_VALUES {
+                return Err(Error::IdError { admissible: 0..Board::COUNT_VALUES, actual: notes.cell });
+            }
+            for value in notes.values {
+                board.add_note(notes.cell, value);
+            }
+        }
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coord::Coord;
+
+    const PUZZLE: &str = "..75......6..2..4....3.65.22......8...8.3.1...9......79.32.7....7..9..5......89..";
+
+    /// The board of the one-line format, with all assigned cells as givens
+    fn board_of(cells: &str) -> Board {
+        let values: Vec<Value> = cells.chars().map(|symbol| symbol.to_digit(10).unwrap_or(0) as Value).collect();
+        Board::from_flattened_values(&values.try_into().unwrap())
+    }
+
+    #[test]
+    fn with_move_places_the_value() {
+        let board = board_of(PUZZLE);
+        let placed = board.with_move(&Move::new(Coord::new(0, 0), 1)).unwrap();
+        assert_eq!(placed[0].value(), 1);
+        assert_eq!(board[0].value(), 0);
+    }
+
+    #[test]
+    fn with_move_fails_outside_of_the_board() {
+        // NOTE: r4c13 would otherwise be taken for r5c4
+        let placement = Move::new(Coord::new(3, 12), 1);
+        assert!(matches!(Board::default().with_move(&placement), Err(Error::IdError { .. })));
+    }
+
+    #[test]
+    fn try_set_checks_the_cell_and_the_value() {
+        let mut board = Board::default();
+        assert!(matches!(board.try_set(81, 1), Err(Error::IdError { actual: 81, .. })));
+        assert!(matches!(board.try_set(0, 10), Err(Error::ValueError { value: 10, .. })));
+        board.try_set(0, 5).unwrap();
+        assert_eq!(board[0].value(), 5);
+        board.try_set(0, 0).unwrap();
+        assert_eq!(board[0].value(), 0);
+    }
+
+    #[test]
+    fn try_set_consistent_refuses_repeated_values() {
+        let mut board = board_of(PUZZLE);
+        // NOTE: r1c1 sees the 7 of r1c3, the 2 of r4c1 and the 6 of r2c2
+        for value in [7, 2, 6] {
+            assert!(matches!(board.try_set_consistent(0, value), Err(Error::ConstraintError { .. })), "{value}");
+        }
+        board.try_set_consistent(0, 1).unwrap();
+        assert_eq!(board[0].value(), 1);
+    }
+
+    #[test]
+    fn index_by_coord_and_row_and_column() {
+        let mut board = Board::default();
+        board[Coord::new(3, 4)] = Cell::new(7);
+        assert_eq!(board[(3, 4)].value(), 7);
+        assert_eq!(board[3 * 9 + 4].value(), 7);
+        // NOTE: the masks are rebuilt after indexing mutably
+        assert!(!board.legal_values(3 * 9 + 5).contains(7));
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_panics_outside_of_the_board() {
+        let _ = Board::default()[(0, 9)];
+    }
+
+    #[test]
+    fn givens_cannot_be_overwritten() {
+        let mut board = board_of(PUZZLE);
+        assert!(board.is_given(2));
+        assert!(matches!(board.try_set(2, 1), Err(Error::GivenError { .. })));
+        assert!(matches!(board.with_move(&Move::new(Coord::new(0, 2), 1)), Err(Error::GivenError { .. })));
+        board.try_set(0, 1).unwrap();
+        assert!(!board.is_given(0));
+        board.reset();
+        assert_eq!(board[0].value(), 0);
+        assert_eq!(board[2].value(), 7);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_mut_panics_for_a_given() {
+        let mut board = board_of(PUZZLE);
+        board[(0, 2)] = Cell::new(1);
+    }
+
+    #[test]
+    fn diff_lists_the_changed_cells() {
+        let board = board_of(PUZZLE);
+        let solved = board.with_move(&Move::new(Coord::new(0, 0), 1)).unwrap();
+        let changes = board.diff(&solved);
+        assert_eq!(changes.len(), 1);
+        assert_eq!((changes[0].cell, changes[0].before, changes[0].after), (Coord::new(0, 0), 0, 1));
+        assert!(board.diff(&board).is_empty());
+    }
+
+    #[test]
+    fn apply_moves_is_atomic() {
+        let board = board_of(PUZZLE);
+        let placements = [Move::new(Coord::new(0, 0), 1), Move::new(Coord::new(0, 1), 1)];
+        assert!(board.apply_moves(&placements).is_err());
+        let applied = board.apply_moves(&placements[..1]).unwrap();
+        assert_eq!(applied[0].value(), 1);
+        assert_eq!(board[0].value(), 0);
+    }
+
+    #[test]
+    fn parse_the_one_line_format() {
+        let board: Board = PUZZLE.parse().unwrap();
+        assert_eq!(board.values(), board_of(PUZZLE).values());
+        assert_eq!(board.givens().len(), PUZZLE.chars().filter(|symbol| *symbol != '.').count());
+        assert_eq!(PUZZLE.replace('.', "0").parse::<Board>().unwrap().values(), board.values());
+    }
+
+    #[test]
+    fn parse_rejects_malformed_boards() {
+        assert!(PUZZLE[1..].parse::<Board>().is_err());
+        assert!(format!("{PUZZLE}.").parse::<Board>().is_err());
+        assert!(PUZZLE.replace('7', "x").parse::<Board>().is_err());
+        assert!(Board::parse_grid(&PUZZLE[..72]).is_err());
+    }
+
+    #[test]
+    fn grid_round_trip() {
+        let board = board_of(PUZZLE);
+        let grid = Board::parse_grid(&board.to_string_with(&SymbolSet::digits())).unwrap();
+        assert_eq!(grid.values(), board.values());
+        let symbols = Board::parse_with(&PUZZLE.replace('.', "0"), &SymbolSet::digits()).unwrap();
+        assert_eq!(symbols.values(), board.values());
+    }
+
+    #[test]
+    fn line_round_trip() {
+        let board: Board = PUZZLE.parse().unwrap();
+        assert_eq!(board.to_line_string('.'), PUZZLE);
+    }
+}
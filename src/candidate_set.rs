@@ -0,0 +1,191 @@
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not, Sub};
+
+use crate::board::Value;
+
+/// A set of Sudoku values stored as `u16` bitmask (bit `v` stands for value `v`).
+/// Membership tests, unions and intersections are single bit operations.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub struct CandidateSet(u16);
+
+impl CandidateSet {
+    /// Bits of the admissible Sudoku values 1..=9
+    const MASK: u16 = 0b11_1111_1110;
+
+    /// The set without any value
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// The set of all Sudoku values 1..=9
+    pub const fn all() -> Self {
+        Self(Self::MASK)
+    }
+
+    /// The set containing only `value`
+    pub fn single(value: Value) -> Self {
+        Self::empty().with(value)
+    }
+
+    /// The set given its bitmask. Bits other than 1..=9 are ignored.
+    pub const fn from_bits(bits: u16) -> Self {
+        Self(bits & Self::MASK)
+    }
+
+    /// The bitmask of this set
+    pub const fn bits(&self) -> u16 {
+        self.0
+    }
+
+    /// The set with `value` added; values outside of 1..=9 are ignored
+    pub fn with(mut self, value: Value) -> Self {
+        self.insert(value);
+        self
+    }
+
+    /// Add `value` and return whether it was missing before.
+    /// Values outside of 1..=9 are ignored.
+    pub fn insert(&mut self, value: Value) -> bool {
+        if !(1..=9).contains(&value) || self.contains(value) {
+            return false;
+        }
+        self.0 |= 1 << value;
+        true
+    }
+
+    /// Remove `value` and return whether it was contained
+    pub fn remove(&mut self, value: Value) -> bool {
+        if !self.contains(value) {
+            return false;
+        }
+        self.0 &= !(1 << value);
+        true
+    }
+
+    /// Does the set contain `value`?
+    pub fn contains(&self, value: Value) -> bool {
+        (1..=9).contains(&value) && self.0 & (1 << value) != 0
+    }
+
+    /// The number of values in the set
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The smallest value of the set
+    pub fn first(&self) -> Option<Value> {
+        if self.is_empty() { None } else { Some(self.0.trailing_zeros() as Value) }
+    }
+
+    /// The only value of the set or `None` if the set does not have exactly one value
+    pub fn single_value(&self) -> Option<Value> {
+        if self.len() == 1 { self.first() } else { None }
+    }
+
+    /// Is every value of this set also contained in `other`?
+    pub fn is_subset(&self, other: CandidateSet) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    /// The values of the set in increasing order
+    pub fn iter(&self) -> Values {
+        Values(self.0)
+    }
+}
+
+/// Iterator over the values of a `CandidateSet` in increasing order
+#[derive(Clone,Debug)]
+pub struct Values(u16);
+
+impl Iterator for Values {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        if self.0 == 0 {
+            return None;
+        }
+        let value = self.0.trailing_zeros() as Value;
+        // NOTE: clear the lowest set bit
+        self.0 &= self.0 - 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Values {}
+
+impl IntoIterator for CandidateSet {
+    type Item = Value;
+    type IntoIter = Values;
+
+    fn into_iter(self) -> Values {
+        self.iter()
+    }
+}
+
+impl FromIterator<Value> for CandidateSet {
+    fn from_iter<I: IntoIterator<Item = Value>>(values: I) -> Self {
+        values.into_iter().fold(Self::empty(), Self::with)
+    }
+}
+
+impl BitAnd for CandidateSet {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+
+impl BitAndAssign for CandidateSet {
+    fn bitand_assign(&mut self, other: Self) {
+        self.0 &= other.0;
+    }
+}
+
+impl BitOr for CandidateSet {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for CandidateSet {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl Sub for CandidateSet {
+    type Output = Self;
+
+    /// The values of `self` which are not contained in `other`
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+}
+
+impl Not for CandidateSet {
+    type Output = Self;
+
+    /// The Sudoku values missing in this set
+    fn not(self) -> Self {
+        Self(!self.0 & Self::MASK)
+    }
+}
+
+impl fmt::Display for CandidateSet {
+    /// The values of the set as digits in increasing order, e.g. `{1,4,7}`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{{}}}", self.iter().map(|value| value.to_string()).collect::<Vec<String>>().join(","))
+    }
+}
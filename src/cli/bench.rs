@@ -0,0 +1,122 @@
+use std::error;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "examples")]
+use sudoku::{corpus, Difficulty};
+use sudoku::solver::SolveOutcome;
+use sudoku::PuzzleCollection;
+
+use crate::cli::{read_puzzles, solver, style, Args, CliResult, UsageError, Verbosity, ENGINES};
+
+pub const USAGE: &str = "\
+Time a solver on every puzzle of a corpus and print the fastest, the median,
+the 95th percentile and the total of the times, to compare solvers and machines.
+
+Usage: sudokuSolver bench [OPTIONS]
+
+The corpus is a collection file (.sdm, .csv, .opensudoku or .json), e.g. the
+top1465 collection, or with the built-in examples one of the names examples,
+easy, medium, hard, expert and pathological. The built-in examples are the
+default if they are built in. Only classic puzzles are solved.
+
+With --verbose, the outcome and the time of every puzzle are printed as well.
+
+Options:
+      --corpus <CORPUS>  the puzzles to solve
+      --engine <ENGINE>  backtracking (the default), backjumping, dlx, sat,
+                         iterative, parallel or logic
+      --runs <N>         solve every puzzle N times and take the fastest time,
+                         1 if missing
+  -h, --help             print this help
+";
+
+pub fn run(mut args: Args) -> CliResult {
+    let corpus = args.value("--corpus")?;
+    let engine = args.value("--engine")?.unwrap_or_else(|| ENGINES[0].to_string());
+    let runs: usize = args.parsed("--runs")?.unwrap_or(1);
+    args.positionals()?;
+    if runs == 0 {
+        return Err(UsageError("expected at least 1 run".to_string()).into());
+    }
+    let solver = solver(&engine)?;
+    let collection = load_corpus(corpus.as_deref())?;
+    if collection.iter().any(|puzzle| !puzzle.variant().is_classic()) {
+        return Err(UsageError("only classic puzzles can be benchmarked".to_string()).into());
+    }
+    if collection.is_empty() {
+        return Err(UsageError("the corpus has no puzzles".to_string()).into());
+    }
+
+    let verbosity = style().verbosity;
+    let (mut solved, mut unsolvable, mut stalled) = (0, 0, 0);
+    let mut times = Vec::with_capacity(collection.len());
+    for (i, board) in collection.boards().enumerate() {
+        let mut fastest = Duration::MAX;
+        let mut outcome = SolveOutcome::Unsolvable;
+        for _ in 0..runs {
+            let started = Instant::now();
+            outcome = solver.solve(board);
+            fastest = fastest.min(started.elapsed());
+        }
+        let name = match outcome {
+            SolveOutcome::Solved(_) => {
+                solved += 1;
+                "solved"
+            },
+            SolveOutcome::Unsolvable => {
+                unsolvable += 1;
+                "unsolvable"
+            },
+            _ => {
+                stalled += 1;
+                "stalled"
+            },
+        };
+        if verbosity >= Verbosity::Verbose {
+            eprintln!("puzzle {}: {name} in {:.3} ms", i + 1, fastest.as_secs_f64() * 1000.0);
+        }
+        times.push(fastest);
+    }
+
+    times.sort_unstable();
+    if verbosity >= Verbosity::Normal {
+        println!("{engine} on {} puzzles: {solved} solved, {unsolvable} unsolvable, {stalled} stalled", times.len());
+    }
+    let total: Duration = times.iter().sum();
+    let rows = [("min", times[0]), ("median", percentile(&times, 0.5)), ("p95", percentile(&times, 0.95)), ("total", total)];
+    for (name, time) in rows {
+        println!("{name:<8}{:>12.3} ms", time.as_secs_f64() * 1000.0);
+    }
+    Ok(())
+}
+
+/// The time which `share` of the sorted times do not exceed, by the nearest rank
+fn percentile(sorted: &[Duration], share: f64) -> Duration {
+    let rank = (share * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// The puzzles of the corpus: built-in ones by name or those of a collection file
+fn load_corpus(corpus: Option<&str>) -> Result<PuzzleCollection, Box<dyn error::Error>> {
+    if let Some(collection) = built_in(corpus) {
+        return Ok(collection);
+    }
+    match corpus {
+        Some(source) => Ok(read_puzzles(source)?),
+        None => Err(UsageError("expected a corpus, see --help".to_string()).into()),
+    }
+}
+
+/// The built-in examples of the name, all of them without a name
+#[cfg(feature = "examples")]
+fn built_in(name: Option<&str>) -> Option<PuzzleCollection> {
+    match name {
+        None | Some("examples") => Some(corpus::all()),
+        Some(name) => name.parse::<Difficulty>().ok().map(corpus::by_difficulty),
+    }
+}
+
+#[cfg(not(feature = "examples"))]
+fn built_in(_name: Option<&str>) -> Option<PuzzleCollection> {
+    None
+}
@@ -0,0 +1,145 @@
+use sudoku::{Board, CellChange, Value};
+
+use crate::cli::{read_puzzle, show, style, Args, CliResult, UsageError, Verbosity};
+
+pub const USAGE: &str = "\
+Show the differences between two boards, e.g. a game in progress and the
+solution, or two versions of a puzzle.
+
+Usage: sudokuSolver compare [OPTIONS] <BOARD> <OTHER>
+
+The grid shows the values of OTHER. Cells assigned in OTHER only are added
+(green or +), cells assigned in BOARD only are removed and show the removed
+value (red or -), and cells with different values are changed (yellow or ~).
+A summary with the number of cells of each kind follows.
+
+With --quiet, only the summary is printed, and --verbose lists every
+difference as well. The boards are read like the puzzle of `solve`; givens,
+pencil marks and variants are not compared.
+
+With --format, OTHER is printed in the format like with `solve` instead of the
+grid of differences, and the list and the summary move to the standard error.
+
+Options:
+      --format <FORMAT>  pretty, line, grid, json, csv, svg or html
+  -h, --help             print this help
+";
+
+/// How a cell differs between the two boards
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+enum Difference {
+    Same,
+    Added,
+    Removed,
+    Changed,
+}
+
+impl Difference {
+    fn of(before: Value, after: Value) -> Self {
+        match (before, after) {
+            _ if before == after => Difference::Same,
+            (0, _) => Difference::Added,
+            (_, 0) => Difference::Removed,
+            _ => Difference::Changed,
+        }
+    }
+
+    fn of_change(change: &CellChange) -> Self {
+        Difference::of(change.before, change.after)
+    }
+}
+
+pub fn run(mut args: Args) -> CliResult {
+    let format = args.given_format()?;
+    let positionals = args.positionals()?;
+    let [board, other] = positionals.as_slice() else {
+        return Err(UsageError("expected two boards".to_string()).into());
+    };
+    let board = read_puzzle(board)?.board().clone();
+    let other_puzzle = read_puzzle(other)?;
+    let other = other_puzzle.board().clone();
+
+    let changes = board.diff(&other);
+    let verbosity = style().verbosity;
+    let mut out = String::new();
+    if verbosity >= Verbosity::Verbose {
+        for change in changes.iter() {
+            let describe = match Difference::of_change(change) {
+                Difference::Added => format!("{} added", change.after),
+                Difference::Removed => format!("{} removed", change.before),
+                _ => format!("{} changed to {}", change.before, change.after),
+            };
+            out.push_str(&format!("{}: {describe}\n", change.cell));
+        }
+    }
+    out.push_str(&format!("{}\n", summary(&changes)));
+    match format {
+        Some(format) => {
+            print!("{}", show(&other_puzzle, format));
+            eprint!("{out}");
+        },
+        None if verbosity >= Verbosity::Normal => print!("{}{out}", draw(&board, &other)),
+        None => print!("{out}"),
+    }
+    Ok(())
+}
+
+/// The grid of `other` marking how every cell differs from `board`
+fn draw(board: &Board, other: &Board) -> String {
+    let (color, ascii) = (style().color, style().ascii);
+    let line = |left: char, middle: char, right: char| {
+        let dashes = if ascii { "-" } else { "─" }.repeat(10);
+        format!("{left}{dashes}{middle}{dashes}{middle}{dashes}{right}\n")
+    };
+    let (top, middle, bottom, bar) = match ascii {
+        true => (line('+', '+', '+'), line('+', '+', '+'), line('+', '+', '+'), '|'),
+        false => (line('┌', '┬', '┐'), line('├', '┼', '┤'), line('└', '┴', '┘'), '│'),
+    };
+
+    let mut out = top;
+    for row in 0..Board::COUNT_ROWS {
+        if row > 0 && row % 3 == 0 {
+            out.push_str(&middle);
+        }
+        out.push(bar);
+        for col in 0..Board::COUNT_COLUMNS {
+            let cell_id = row * Board::COUNT_COLUMNS + col;
+            let (before, after) = (board[cell_id].value(), other[cell_id].value());
+            let difference = Difference::of(before, after);
+            let shown = match difference {
+                Difference::Removed => before,
+                _ => after,
+            };
+            let symbol = match shown {
+                0 => '.',
+                value => char::from(b'0' + value),
+            };
+            out.push(' ');
+            match (color, difference) {
+                (_, Difference::Same) => out.push_str(&format!(" {symbol}")),
+                (true, Difference::Added) => out.push_str(&format!(" \x1b[32m{symbol}\x1b[0m")),
+                (true, Difference::Removed) => out.push_str(&format!(" \x1b[31m{symbol}\x1b[0m")),
+                (true, Difference::Changed) => out.push_str(&format!(" \x1b[33m{symbol}\x1b[0m")),
+                (false, Difference::Added) => out.push_str(&format!("+{symbol}")),
+                (false, Difference::Removed) => out.push_str(&format!("-{symbol}")),
+                (false, Difference::Changed) => out.push_str(&format!("~{symbol}")),
+            }
+            if col % 3 == 2 {
+                out.push_str(&format!(" {bar}"));
+            }
+        }
+        out.push('\n');
+    }
+    out.push_str(&bottom);
+    out
+}
+
+/// The numbers of cells of every kind, or that the boards are equal
+fn summary(changes: &[CellChange]) -> String {
+    if changes.is_empty() {
+        return "the boards are equal".to_string();
+    }
+    let count = |difference: Difference| changes.iter().filter(|change| Difference::of_change(change) == difference).count();
+    let unchanged = Board::COUNT_VALUES - changes.len();
+    format!("{} added, {} removed, {} changed, {unchanged} cells equal", count(Difference::Added), count(Difference::Removed), count(Difference::Changed))
+}
@@ -0,0 +1,174 @@
+use std::fs;
+use std::path::Path;
+
+use sudoku::export::html::{self, HtmlOptions};
+use sudoku::export::svg::{self, SvgOptions};
+use sudoku::{Error, Puzzle, PuzzleCollection};
+
+use crate::cli::{read_puzzles_as, show, Args, CliResult, OutputFormat, UsageError, INPUT_FORMATS};
+
+pub const USAGE: &str = "\
+Translate puzzles from one format into another, a single puzzle or all puzzles
+of a collection file.
+
+Usage: sudokuSolver convert [OPTIONS] <INPUT> [OUTPUT]
+
+The input is a file, an f-puzzles link or - for the standard input. Without
+--from its format is recognized by the extension of the file or by its content.
+The output is written to the standard output unless a file is given, whose
+extension names the format if --to is missing.
+
+Formats:
+  line          one line of 81 cells per puzzle with . for unassigned ones
+  grid          9 lines of 9 cells
+  sdk           SadMan Sudoku with the title, author, source, date and
+                difficulty
+  sdm           a collection of one line of 81 cells per puzzle with 0 for
+                unassigned ones
+  ss            Simple Sudoku
+  csv           comma-separated values: 9 rows of 9 values for one puzzle,
+                a row of 81 values per puzzle for several
+  json          JSON with everything known about the puzzles
+  opensudoku    the XML collections of OpenSudoku
+  fpuzzles-url  a link to f-puzzles per puzzle (fpuzzles for short)
+  html          an HTML snippet, for output only
+  svg           an SVG image, for output only
+
+The formats grid, sdk, ss, html and svg hold one puzzle, so several puzzles are
+separated by a blank line on the standard output and written to numbered files
+like out-1.svg, out-2.svg and so on otherwise.
+
+With --scramble, every classic puzzle is turned into an equivalent one which
+looks different: the values are relabeled, rows and columns are permuted within
+their bands and stacks, bands and stacks are permuted, and the board may be
+transposed. The same seed scrambles the same way.
+
+Options:
+      --from <FORMAT>  the format to read
+      --to <FORMAT>    the format to write
+      --candidates     show the candidates (html) or pencil marks (svg) of
+                       unassigned cells
+      --scramble       scramble the puzzles
+      --seed <SEED>    the seed of --scramble, a seed from the clock if missing
+  -h, --help           print this help
+";
+
+/// The names of the formats which can be written
+const OUTPUT_FORMATS: [&str; 11] = ["line", "grid", "sdk", "sdm", "ss", "csv", "json", "opensudoku", "fpuzzles-url", "html", "svg"];
+
+pub fn run(mut args: Args) -> CliResult {
+    let from = args.value("--from")?.map(unalias);
+    let to = args.value("--to")?.map(unalias);
+    let candidates = args.flag(&["--candidates"]);
+    let scramble = args.flag(&["--scramble"]);
+    let seed = args.seed()?;
+    let positionals = args.positionals()?;
+    let (input, output) = match positionals.as_slice() {
+        [input] => (input, None),
+        [input, output] => (input, Some(output.as_str())),
+        [] => return Err(UsageError("expected the input".to_string()).into()),
+        _ => return Err(UsageError("expected an input and at most one output".to_string()).into()),
+    };
+
+    if let Some(from) = from.as_deref().filter(|from| !INPUT_FORMATS.contains(from)) {
+        return Err(UsageError(format!("unknown format {from:?} to read, expected one of {}", INPUT_FORMATS.join(", "))).into());
+    }
+    let to = match (to, output.and_then(extension)) {
+        (Some(to), _) => to,
+        (None, Some(extension)) => extension,
+        (None, None) => return Err(UsageError("expected the format to write with --to".to_string()).into()),
+    };
+    if !OUTPUT_FORMATS.contains(&to.as_str()) {
+        return Err(UsageError(format!("unknown format {to:?} to write, expected one of {}", OUTPUT_FORMATS.join(", "))).into());
+    }
+
+    let mut collection = read_puzzles_as(input, from.as_deref())?;
+    if scramble {
+        if collection.iter().any(|puzzle| !puzzle.variant().is_classic()) {
+            return Err(UsageError("only classic puzzles can be scrambled".to_string()).into());
+        }
+        // NOTE: every puzzle gets a seed of its own, so equal puzzles are scrambled differently
+        let mut scrambled: PuzzleCollection = collection.iter().enumerate()
+            .map(|(i, puzzle)| puzzle.clone().with_board(puzzle.board().scrambled(seed.wrapping_add(i as u64))))
+            .collect();
+        if let Some(name) = collection.name() {
+            scrambled = scrambled.with_name(name);
+        }
+        if let Some(level) = collection.level() {
+            scrambled = scrambled.with_level(level);
+        }
+        collection = scrambled;
+    }
+    let texts = write(&collection, &to, candidates)?;
+    match output {
+        None => print!("{}", texts.join("\n")),
+        Some(path) if texts.len() == 1 => save(path, &texts[0])?,
+        Some(path) => {
+            for (i, text) in texts.iter().enumerate() {
+                save(&numbered(path, i + 1), text)?;
+            }
+        },
+    }
+    Ok(())
+}
+
+/// The puzzles in the format, as a single text or, for formats holding one puzzle, a text per puzzle
+fn write(collection: &PuzzleCollection, format: &str, candidates: bool) -> Result<Vec<String>, Error> {
+    let each = |write: &dyn Fn(&Puzzle) -> String| collection.iter().map(write).collect::<Vec<String>>();
+
+    let texts = match format {
+        "line" => vec![each(&|puzzle| show(puzzle, OutputFormat::Line)).concat()],
+        "sdm" => vec![collection.to_sdm()],
+        "csv" if collection.len() == 1 => vec![collection[0].board().to_csv()],
+        "csv" => vec![collection.to_csv()],
+        "opensudoku" => vec![collection.to_opensudoku()],
+        "json" if collection.len() == 1 => vec![format!("{}\n", collection[0].to_json())],
+        "json" => vec![collection.to_json()],
+        "fpuzzles-url" => {
+            let links = collection.iter().map(Puzzle::to_fpuzzles_url).collect::<Result<Vec<String>, Error>>()?;
+            vec![links.iter().map(|link| format!("{link}\n")).collect()]
+        },
+        "grid" => each(&|puzzle| show(puzzle, OutputFormat::Grid)),
+        "sdk" => each(&|puzzle| puzzle.to_sdk()),
+        "ss" => each(&|puzzle| puzzle.board().to_ss()),
+        "html" => each(&|puzzle| html::puzzle_to_html(puzzle, &HtmlOptions { candidates, ..HtmlOptions::default() })),
+        // ASSUME: the format names are checked by `run`
+        _ => each(&|puzzle| svg::puzzle_to_svg(puzzle, &SvgOptions { notes: candidates, ..SvgOptions::default() })),
+    };
+    Ok(texts)
+}
+
+/// The name of the format of the file by its extension, if it is one of `OUTPUT_FORMATS`
+fn extension(path: &str) -> Option<String> {
+    let extension = Path::new(path).extension()?.to_str()?.to_ascii_lowercase();
+    match extension.as_str() {
+        "xml" => Some("opensudoku".to_string()),
+        "txt" => Some("line".to_string()),
+        "htm" => Some("html".to_string()),
+        "fpuzzles" => Some("fpuzzles-url".to_string()),
+        _ => OUTPUT_FORMATS.contains(&extension.as_str()).then_some(extension),
+    }
+}
+
+/// The name of the format for its short name, e.g. `fpuzzles-url` for `fpuzzles`
+fn unalias(format: String) -> String {
+    match format.as_str() {
+        "fpuzzles" => "fpuzzles-url".to_string(),
+        _ => format,
+    }
+}
+
+/// The path with the number before the extension, e.g. `out-2.svg` for `out.svg`
+fn numbered(path: &str, number: usize) -> String {
+    let path = Path::new(path);
+    let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+    let name = match path.extension().and_then(|extension| extension.to_str()) {
+        Some(extension) => format!("{stem}-{number}.{extension}"),
+        None => format!("{stem}-{number}"),
+    };
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
+
+fn save(path: &str, content: &str) -> Result<(), Error> {
+    fs::write(path, content).map_err(|io_error| Error::IoError { path: path.to_string(), reason: io_error.to_string() })
+}
@@ -0,0 +1,102 @@
+use std::time::Instant;
+
+use sudoku::solver::{dlx, SolveConfig};
+
+use crate::cli::{describe_search, read_any_puzzle, solver, style, AnyPuzzle, Args, CliResult, ExitStatus, ProgressLine, TimeoutError, UsageError, Verbosity, EXIT_NOT_UNIQUE, EXIT_UNSOLVABLE};
+
+pub const USAGE: &str = "\
+Count the solutions of a puzzle, stopping at a limit.
+
+Usage: sudokuSolver count [OPTIONS] <PUZZLE>
+
+The exit status tells scripts how many solutions there are: 0 for exactly one,
+3 for none and 4 for more than one. Invalid input exits with 2 and running out
+of the time of --timeout with 5. With --quiet, only the number is printed,
+which is the limit if there are more solutions. With --progress, the nodes of
+the search, an estimate of its share done and the time so far are printed on
+the standard error every second.
+
+With --engine dlx, the solutions of a classic puzzle are counted by Algorithm X
+instead of the search, without --timeout and --progress. The other engines of
+`solve` cannot count.
+
+A board of another size than 9×9, e.g. 4×4 or 16×16 like with `solve`, is
+counted by the search without --engine dlx, --timeout and --progress.
+
+Options:
+      --limit <N>        stop counting at N solutions, at least 2, 1000 if
+                         missing
+      --engine <ENGINE>  backtracking (the default) or dlx
+      --timeout <SECS>   give up after SECS seconds
+      --progress         report the progress of a long search
+  -h, --help             print this help
+";
+
+pub fn run(mut args: Args) -> CliResult {
+    // NOTE: a limit of 1 cannot tell one solution from more
+    let limit = args.parsed::<usize>("--limit")?.unwrap_or(1000).max(2);
+    let timeout = args.timeout()?;
+    let progress = args.flag(&["--progress"]);
+    let engine = args.value("--engine")?;
+    if let Some(engine) = engine.as_deref() {
+        solver(engine)?;
+    }
+    let dlx = match engine.as_deref() {
+        None | Some("backtracking") => false,
+        Some("dlx") => true,
+        Some(engine) => return Err(UsageError(format!("the {engine} engine cannot count solutions, expected backtracking or dlx")).into()),
+    };
+    if dlx && (timeout.is_some() || progress) {
+        return Err(UsageError("--engine dlx cannot be combined with --timeout or --progress".to_string()).into());
+    }
+    let puzzle = match read_any_puzzle(&args.positional("a puzzle")?)? {
+        AnyPuzzle::Classic(puzzle) => *puzzle,
+        AnyPuzzle::Sized(_) if dlx || timeout.is_some() || progress => {
+            return Err(UsageError("a board of another size than 9×9 takes none of --engine dlx, --timeout and --progress".to_string()).into());
+        },
+        AnyPuzzle::Sized(board) => {
+            let started = Instant::now();
+            return report(board.count_solutions(limit), limit, started);
+        },
+    };
+    if dlx && !puzzle.variant().is_classic() {
+        return Err(UsageError("--engine dlx counts classic puzzles only".to_string()).into());
+    }
+
+    let config = SolveConfig { timeout, ..SolveConfig::default() };
+    let started = Instant::now();
+    let sudoku = puzzle.to_sudoku();
+    let count = match progress {
+        // NOTE: like `Sudoku::count_solutions`, a board violating the rules has no solutions
+        _ if dlx && sudoku.verify_board().is_err() => Some(0),
+        _ if dlx => Some(dlx::count_solutions(puzzle.board(), limit)),
+        true => {
+            let mut line = ProgressLine::start();
+            let count = sudoku.count_solutions_observed(limit, &config, &mut |progress| line.update(|| describe_search(progress)));
+            line.finish();
+            count
+        },
+        false => sudoku.count_solutions_with(limit, &config),
+    };
+    let count = count.ok_or_else(|| TimeoutError(timeout.unwrap_or_default()))?;
+    report(count, limit, started)
+}
+
+/// Print the number of solutions counted since `started` and exit with the status of the number
+fn report(count: usize, limit: usize, started: Instant) -> CliResult {
+    let verbosity = style().verbosity;
+    match count {
+        _ if verbosity == Verbosity::Quiet => println!("{count}"),
+        _ if count >= limit => println!("at least {limit} solutions"),
+        1 => println!("1 solution"),
+        _ => println!("{count} solutions"),
+    }
+    if verbosity >= Verbosity::Verbose {
+        eprintln!("counted in {:.3} ms", started.elapsed().as_secs_f64() * 1000.0);
+    }
+    match count {
+        0 => Err(ExitStatus(EXIT_UNSOLVABLE).into()),
+        1 => Ok(()),
+        _ => Err(ExitStatus(EXIT_NOT_UNIQUE).into()),
+    }
+}
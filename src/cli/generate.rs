@@ -0,0 +1,79 @@
+use std::time::Instant;
+
+use sudoku::generator::{Generator, GeneratorConfig};
+use sudoku::Difficulty;
+
+use crate::cli::{show, show_all, style, Args, CliResult, OutputFormat, ProgressLine, Verbosity};
+
+pub const USAGE: &str = "\
+Create new puzzles with a unique solution.
+
+Usage: sudokuSolver generate [OPTIONS]
+
+With --progress, the puzzle being generated, its attempts so far and the time
+are printed on the standard error every second, since a puzzle of a rare grade
+may take many attempts.
+
+Options:
+      --difficulty <GRADE>  easy, medium, hard or expert, any grade if missing
+      --count <N>           how many puzzles to create, 1 if missing
+      --seed <SEED>         reproduce the puzzles of an earlier run with the
+                            same seed, a seed from the clock if missing
+      --asymmetric          place the givens freely instead of symmetric under a
+                            rotation by 180 degrees
+      --format <FORMAT>     pretty (the default), line, grid, json, csv, svg or
+                            html
+      --progress            report the progress of the generation
+  -h, --help                print this help
+";
+
+pub fn run(mut args: Args) -> CliResult {
+    let difficulty: Option<Difficulty> = args.parsed("--difficulty")?;
+    let count: usize = args.parsed("--count")?.unwrap_or(1);
+    let seed = args.seed()?;
+    let symmetric = !args.flag(&["--asymmetric"]);
+    let format = args.format()?;
+    let progress = args.flag(&["--progress"]);
+    args.positionals()?;
+
+    let verbosity = style().verbosity;
+    if verbosity >= Verbosity::Verbose {
+        eprintln!("seed {seed}");
+    }
+    let config = GeneratorConfig { difficulty, seed, symmetric, ..GeneratorConfig::default() };
+    let mut generator = Generator::new(config);
+    // NOTE: the formats of collections are printed at the end, the others show every puzzle as soon as it is generated
+    let collected = matches!(format, OutputFormat::Json | OutputFormat::Csv);
+    let mut puzzles = vec![];
+    let mut line = progress.then(ProgressLine::start);
+    let generating = Instant::now();
+    for i in 0..count {
+        let started = Instant::now();
+        let puzzle = match line.as_mut() {
+            Some(line) => {
+                let puzzle = generator.next_puzzle_observed(&mut |attempt| line.update(|| {
+                    let percent = i as f64 * 100.0 / count as f64;
+                    format!("progress: puzzle {} of {count} ({percent:.1}% done), attempt {attempt}, {:.1} s", i + 1, generating.elapsed().as_secs_f64())
+                }));
+                line.finish();
+                puzzle?
+            },
+            None => generator.next_puzzle()?,
+        };
+        if verbosity >= Verbosity::Verbose {
+            let difficulty = puzzle.difficulty().unwrap_or("unrated");
+            eprintln!("puzzle {}: {difficulty} with {} givens in {:.3} ms", i + 1, puzzle.board().givens().len(), started.elapsed().as_secs_f64() * 1000.0);
+        }
+        match collected {
+            true => puzzles.push(puzzle),
+            false => {
+                let separator = if i > 0 && format != OutputFormat::Line { "\n" } else { "" };
+                print!("{separator}{}", show(&puzzle, format));
+            },
+        }
+    }
+    if collected {
+        print!("{}", show_all(&puzzles, format));
+    }
+    Ok(())
+}
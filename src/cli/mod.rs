@@ -0,0 +1,670 @@
+//! The command line interface: a subcommand followed by its options and arguments,
+//! e.g. `sudokuSolver solve puzzle.sdk`. Every subcommand explains itself with `--help`.
+
+mod bench;
+mod compare;
+mod convert;
+mod count;
+mod generate;
+mod play;
+mod rate;
+mod repl;
+mod solve;
+mod verify;
+
+use std::error;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use sudoku::export::html::{self, HtmlOptions};
+use sudoku::export::svg::{self, SvgOptions};
+use sudoku::solver::{BacktrackingSolver, DlxSolver, IterativeSolver, LogicSolver, ParallelSolver, SatSolver, SearchProgress, Solver};
+use sudoku::{Board, BoardSpec, Error, Puzzle, PuzzleCollection, SizedBoard, Sudoku, SymbolSet, Variant};
+
+/// What running a subcommand fails with
+pub type CliResult = Result<(), Box<dyn error::Error>>;
+
+/// A subcommand of the binary
+struct Command {
+    name: &'static str,
+    /// One line for the overview of all subcommands
+    summary: &'static str,
+    /// The text printed for `--help`
+    usage: &'static str,
+    run: fn(Args) -> CliResult,
+}
+
+const COMMANDS: [Command; 10] = [
+    Command { name: "solve", summary: "print the solution of a puzzle", usage: solve::USAGE, run: solve::run },
+    Command { name: "count", summary: "count the solutions of a puzzle", usage: count::USAGE, run: count::run },
+    Command { name: "verify", summary: "check a board against the rules", usage: verify::USAGE, run: verify::run },
+    Command { name: "compare", summary: "show the differences between two boards", usage: compare::USAGE, run: compare::run },
+    Command { name: "generate", summary: "create new puzzles", usage: generate::USAGE, run: generate::run },
+    Command { name: "rate", summary: "grade how hard a puzzle is", usage: rate::USAGE, run: rate::run },
+    Command { name: "bench", summary: "time a solver on a corpus of puzzles", usage: bench::USAGE, run: bench::run },
+    Command { name: "convert", summary: "write a puzzle in another format", usage: convert::USAGE, run: convert::run },
+    Command { name: "play", summary: "play a puzzle in the terminal", usage: play::USAGE, run: play::run },
+    Command { name: "repl", summary: "work on a puzzle with one command per line", usage: repl::USAGE, run: repl::run },
+];
+
+/// Run the subcommand named by the first argument with the remaining ones.
+/// The options of all commands may come before or after the subcommand.
+pub fn run(args: Vec<String>) -> CliResult {
+    let mut args = Args::new(args);
+    let no_color = args.flag(&["--no-color"]) || env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    let ascii = args.flag(&["--ascii"]);
+    let verbosity = match (args.flag(&["-q", "--quiet"]), args.flag(&["-vv"]), args.flag(&["-v", "--verbose"])) {
+        (true, _, _) => Verbosity::Quiet,
+        (false, true, _) => Verbosity::VeryVerbose,
+        (false, false, true) => Verbosity::Verbose,
+        (false, false, false) => Verbosity::Normal,
+    };
+    // NOTE: `run` is entered once per process, so the style is never set before
+    let _ = STYLE.set(Style { color: !no_color, ascii, verbosity });
+
+    let (name, args) = match args.args.split_first() {
+        Some((name, args)) => (name.clone(), args.to_vec()),
+        None => return Err(UsageError(overview()).into()),
+    };
+    if matches!(name.as_str(), "help" | "--help" | "-h") {
+        print!("{}", overview());
+        return Ok(());
+    }
+    let command = COMMANDS.iter()
+        .find(|command| command.name == name)
+        .ok_or_else(|| UsageError(format!("unknown subcommand {name:?}\n\n{}", overview())))?;
+    let mut args = Args::new(args);
+    if args.flag(&["--help", "-h"]) {
+        print!("{}", command.usage);
+        return Ok(());
+    }
+    (command.run)(args)
+}
+
+/// The usage of the binary listing all subcommands
+fn overview() -> String {
+    let mut out = String::from("Usage: sudokuSolver <COMMAND> [OPTIONS]\n\nCommands:\n");
+    for command in COMMANDS.iter() {
+        out.push_str(&format!("  {:<10}{}\n", command.name, command.summary));
+    }
+    out.push_str("\nOptions of all commands:\n");
+    out.push_str("      --ascii     draw boards with +, - and | instead of box-drawing characters\n");
+    out.push_str("      --no-color  print no colors or other styles, as does a non-empty NO_COLOR\n");
+    out.push_str("  -q, --quiet     print the results only, e.g. no summaries or notes\n");
+    out.push_str("  -v, --verbose   print statistics as well, e.g. of the search for a solution\n");
+    out.push_str("  -vv             print every step of the work as well\n");
+    out.push_str("\nExit status:\n");
+    out.push_str("  0  success\n");
+    out.push_str("  1  an error which is no fault of the input, e.g. a file cannot be written\n");
+    out.push_str("  2  invalid input: wrong arguments, a malformed puzzle or a broken rule\n");
+    out.push_str("  3  the puzzle has no solution\n");
+    out.push_str("  4  the puzzle has several solutions, but a unique one is required\n");
+    out.push_str("  5  the time of --timeout ran out\n");
+    out.push_str("\nSee `sudokuSolver <COMMAND> --help` for the options of a command.\n");
+    out
+}
+
+/// How the output looks and how much of it there is, as chosen by the options of all commands
+#[derive(Clone,Copy,Debug)]
+pub struct Style {
+    /// May the output contain colors and other ANSI styles?
+    pub color: bool,
+    /// Draw with ASCII characters only?
+    pub ascii: bool,
+    pub verbosity: Verbosity,
+}
+
+/// How much is printed beyond the results. Everything but the results goes to the standard error.
+#[derive(Clone,Copy,Debug,PartialEq,Eq,PartialOrd,Ord)]
+pub enum Verbosity {
+    /// The results only, for scripts
+    Quiet,
+    /// The results with summaries and notes
+    Normal,
+    /// Statistics as well, e.g. of the search for a solution
+    Verbose,
+    /// Every step of the work as well
+    VeryVerbose,
+}
+
+static STYLE: OnceLock<Style> = OnceLock::new();
+
+/// The style of this run, colored, with box-drawing characters and normal verbosity unless the options say otherwise
+pub fn style() -> Style {
+    STYLE.get().copied().unwrap_or(Style { color: true, ascii: false, verbosity: Verbosity::Normal })
+}
+
+/// Prints the progress of long work to the standard error, at most one line a second
+/// and none during the first second. On a terminal, every line replaces the one before.
+pub struct ProgressLine {
+    started: Instant,
+    printed: Option<Instant>,
+    terminal: bool,
+}
+
+impl ProgressLine {
+    pub fn start() -> Self {
+        Self { started: Instant::now(), printed: None, terminal: io::stderr().is_terminal() }
+    }
+
+    /// Print the line `describe` makes unless the last one is too recent
+    pub fn update(&mut self, describe: impl FnOnce() -> String) {
+        let last = self.printed.unwrap_or(self.started);
+        if last.elapsed() < Duration::from_secs(1) {
+            return;
+        }
+        self.printed = Some(Instant::now());
+        match self.terminal {
+            true => eprint!("\r{}\x1b[K", describe()),
+            false => eprintln!("{}", describe()),
+        }
+    }
+
+    /// Clear the last line on a terminal, so the output goes on below it
+    pub fn finish(&mut self) {
+        if self.printed.take().is_some() && self.terminal {
+            eprint!("\r\x1b[K");
+        }
+    }
+}
+
+/// The progress of a search as a line of `ProgressLine`
+pub fn describe_search(progress: &SearchProgress) -> String {
+    format!("progress: {} nodes, {:.1}% of the search tree explored, {:.1} s", progress.nodes, progress.explored * 100.0, progress.elapsed.as_secs_f64())
+}
+
+/// Wrong arguments, reported together with the usage
+#[derive(Debug)]
+pub struct UsageError(pub String);
+
+impl error::Error for UsageError {}
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The engine of `--engine` gave up on a puzzle without deciding it, e.g. the
+/// logic solver lacking a technique
+#[derive(Debug)]
+pub struct EngineStalled(pub String);
+
+impl error::Error for EngineStalled {}
+
+impl fmt::Display for EngineStalled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the {} engine got stuck before solving the puzzle", self.0)
+    }
+}
+
+/// The time limit of `--timeout` ran out before a puzzle was done with
+#[derive(Debug)]
+pub struct TimeoutError(pub Duration);
+
+impl error::Error for TimeoutError {}
+
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "timed out after {} s", self.0.as_secs_f64())
+    }
+}
+
+/// The board of a puzzle violates its rules
+#[derive(Debug)]
+pub struct InvalidBoard {
+    pub count_violations: usize,
+}
+
+impl error::Error for InvalidBoard {}
+
+impl fmt::Display for InvalidBoard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.count_violations {
+            1 => write!(f, "the board violates 1 rule"),
+            count => write!(f, "the board violates {count} rules"),
+        }
+    }
+}
+
+/// A puzzle has several solutions, but a unique one was asked for
+#[derive(Debug)]
+pub struct NotUnique;
+
+impl error::Error for NotUnique {}
+
+impl fmt::Display for NotUnique {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the puzzle has more than one solution")
+    }
+}
+
+/// The exit status of errors which are no fault of the input, e.g. failing to write a file
+pub const EXIT_FAILURE: i32 = 1;
+/// The exit status of wrong arguments, unreadable or malformed puzzles and boards violating their rules
+pub const EXIT_INVALID: i32 = 2;
+/// The exit status of puzzles without a solution
+pub const EXIT_UNSOLVABLE: i32 = 3;
+/// The exit status of puzzles with several solutions if a unique one is required
+pub const EXIT_NOT_UNIQUE: i32 = 4;
+/// The exit status of running out of the time of `--timeout`
+pub const EXIT_TIMEOUT: i32 = 5;
+
+/// The exit status for the error a subcommand failed with
+pub fn exit_status(error: &(dyn error::Error + 'static)) -> i32 {
+    if let Some(ExitStatus(status)) = error.downcast_ref() {
+        return *status;
+    }
+    if error.is::<UsageError>() || error.is::<InputError>() || error.is::<InvalidBoard>() {
+        return EXIT_INVALID;
+    }
+    if error.is::<NotUnique>() {
+        return EXIT_NOT_UNIQUE;
+    }
+    if error.is::<TimeoutError>() {
+        return EXIT_TIMEOUT;
+    }
+    match error.downcast_ref::<Error>() {
+        Some(Error::Unsolvable) => EXIT_UNSOLVABLE,
+        Some(Error::IoError { .. } | Error::GenerateError { .. }) | None => EXIT_FAILURE,
+        // NOTE: the other errors of the library reject values, moves or layouts of the input
+        Some(_) => EXIT_INVALID,
+    }
+}
+
+/// The end of a subcommand which printed everything and only exits with the status,
+/// e.g. to tell shell scripts how many solutions there are
+#[derive(Debug)]
+pub struct ExitStatus(pub i32);
+
+impl error::Error for ExitStatus {}
+
+impl fmt::Display for ExitStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "exit status {}", self.0)
+    }
+}
+
+/// The arguments of a subcommand. Options are taken out by name, and whatever
+/// remains must be positional arguments.
+pub struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    fn new(args: Vec<String>) -> Self {
+        Self { args }
+    }
+
+    /// Take out the flag given by any of its names. Is it set?
+    pub fn flag(&mut self, names: &[&str]) -> bool {
+        let count = self.args.len();
+        self.args.retain(|arg| !names.contains(&arg.as_str()));
+        self.args.len() != count
+    }
+
+    /// Take out the option, given as `--name VALUE` or `--name=VALUE`. The last one counts.
+    pub fn value(&mut self, name: &str) -> Result<Option<String>, UsageError> {
+        let mut value = None;
+        let mut i = 0;
+        while i < self.args.len() {
+            if self.args[i] == name {
+                if i + 1 == self.args.len() {
+                    return Err(UsageError(format!("{name} expects a value")));
+                }
+                value = Some(self.args.remove(i + 1));
+                self.args.remove(i);
+            } else if let Some(inline) = self.args[i].strip_prefix(name).and_then(|rest| rest.strip_prefix('=')) {
+                value = Some(inline.to_string());
+                self.args.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+        Ok(value)
+    }
+
+    /// Take out the option like `value` and parse it
+    pub fn parsed<T: FromStr>(&mut self, name: &str) -> Result<Option<T>, UsageError> {
+        match self.value(name)? {
+            Some(value) => value.parse().map(Some).map_err(|_| UsageError(format!("invalid value {value:?} for {name}"))),
+            None => Ok(None),
+        }
+    }
+
+    /// The positional arguments, failing for options which no one took out.
+    /// A single `-` is positional, it stands for the standard input.
+    pub fn positionals(self) -> Result<Vec<String>, UsageError> {
+        match self.args.iter().find(|arg| arg.starts_with('-') && arg.len() > 1) {
+            Some(option) => Err(UsageError(format!("unknown option {option}"))),
+            None => Ok(self.args),
+        }
+    }
+
+    /// The single positional argument
+    pub fn positional(self, what: &str) -> Result<String, UsageError> {
+        let mut positionals = self.positionals()?;
+        match positionals.len() {
+            1 => Ok(positionals.remove(0)),
+            0 => Err(UsageError(format!("expected {what}"))),
+            _ => Err(UsageError(format!("expected only {what} but got {}", positionals.join(" ")))),
+        }
+    }
+}
+
+/// The puzzle of a file, of the standard input for `-` or of an f-puzzles or
+/// SudokuPad link. Files ending in `.sdk`, `.ss` and `.json` are read in these
+/// formats, others as one line of 81 cells or as a grid of 9 lines. The format
+/// of the standard input is recognized by its content.
+pub fn read_puzzle(source: &str) -> Result<Puzzle, InputError> {
+    let error = |reason: String| InputError { source: source.to_string(), reason };
+
+    if is_link(source) {
+        return Puzzle::from_fpuzzles_url(source).map_err(|parse_error| error(reason(parse_error)));
+    }
+    let (content, extension) = read_content(source)?;
+    parse_puzzle(&content, extension.as_deref()).map_err(|parse_error| error(reason(parse_error)))
+}
+
+/// A puzzle read by `read_any_puzzle`
+pub enum AnyPuzzle {
+    /// A puzzle of the classic 9×9 board, maybe of a variant
+    Classic(Box<Puzzle>),
+    /// A board of another size, e.g. a 4×4 or 16×16 grid
+    Sized(SizedBoard),
+}
+
+/// The puzzle of the source like `read_puzzle`, or a board of another size than 9×9
+/// if the content is no classic puzzle but a square number of cells, e.g. 256 for
+/// a 16×16 board with the values from 10 written as letters from `A`
+pub fn read_any_puzzle(source: &str) -> Result<AnyPuzzle, InputError> {
+    let error = |reason: String| InputError { source: source.to_string(), reason };
+
+    if is_link(source) {
+        return read_puzzle(source).map(|puzzle| AnyPuzzle::Classic(Box::new(puzzle)));
+    }
+    let (content, extension) = read_content(source)?;
+    match parse_puzzle(&content, extension.as_deref()) {
+        Ok(puzzle) => Ok(AnyPuzzle::Classic(Box::new(puzzle))),
+        Err(parse_error) => match content.parse::<SizedBoard>() {
+            Ok(board) if board.spec() != BoardSpec::CLASSIC => Ok(AnyPuzzle::Sized(board)),
+            _ => Err(error(reason(parse_error))),
+        },
+    }
+}
+
+/// The puzzles of a collection file or a file of a single puzzle like `read_puzzle`.
+/// Files ending in `.sdm`, `.csv`, `.opensudoku` and `.xml` are collections, as are
+/// `.json` files with an array of puzzles. The standard input is a collection if it
+/// is one in these formats or if it has several lines of 81 cells.
+pub fn read_puzzles(source: &str) -> Result<PuzzleCollection, InputError> {
+    read_puzzles_as(source, None)
+}
+
+/// Like `read_puzzles`, but in the format given by its name (see `INPUT_FORMATS`) instead
+/// of the one of the file extension, e.g. `line` for a file of one puzzle per line
+pub fn read_puzzles_as(source: &str, format: Option<&str>) -> Result<PuzzleCollection, InputError> {
+    let error = |reason: String| InputError { source: source.to_string(), reason };
+
+    if format.is_none() && is_link(source) {
+        return read_puzzle(source).map(|puzzle| [puzzle].into_iter().collect());
+    }
+    let (content, extension) = read_content(source)?;
+    parse_puzzles(&content, format.or(extension.as_deref())).map_err(|parse_error| error(reason(parse_error)))
+}
+
+/// Hosts of f-puzzles and SudokuPad links, which are recognized without a scheme as well
+const LINK_HOSTS: [&str; 3] = ["www.f-puzzles.com/", "f-puzzles.com/", "sudokupad.app/"];
+
+/// Is the source a link rather than a file, i.e. does it start with an HTTP scheme or a host of `LINK_HOSTS`?
+pub fn is_link(source: &str) -> bool {
+    let source = source.to_ascii_lowercase();
+    let address = source.strip_prefix("https://").or_else(|| source.strip_prefix("http://"));
+    address.is_some() || LINK_HOSTS.iter().any(|host| source.starts_with(host))
+}
+
+/// The names of the formats `read_puzzles_as` reads
+pub const INPUT_FORMATS: [&str; 9] = ["line", "grid", "sdk", "sdm", "ss", "csv", "json", "opensudoku", "fpuzzles-url"];
+
+/// Parse the puzzles in the format of the file extension, or guess the format from the content
+fn parse_puzzles(content: &str, extension: Option<&str>) -> Result<PuzzleCollection, Error> {
+    let trimmed = content.trim();
+    let json_collection = trimmed.starts_with('[') || (trimmed.starts_with('{') && trimmed.contains("\"puzzles\""));
+    let lines: Vec<&str> = trimmed.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+    match extension {
+        Some("sdm" | "line") => PuzzleCollection::from_sdm(content),
+        // NOTE: a single puzzle may be 9 rows of 9 values as written by `Board::to_csv`
+        Some("csv") => PuzzleCollection::from_csv(content)
+            .or_else(|csv_error| Board::from_csv(content).map(|board| [board].into_iter().collect()).map_err(|_| csv_error)),
+        Some("opensudoku" | "xml") => PuzzleCollection::from_opensudoku(content),
+        Some("json") if json_collection => PuzzleCollection::from_json(content),
+        Some("fpuzzles-url" | "fpuzzles") => lines.into_iter().map(Puzzle::from_fpuzzles_url).collect(),
+        None if json_collection => PuzzleCollection::from_json(content),
+        None if trimmed.starts_with('<') => PuzzleCollection::from_opensudoku(content),
+        None if lines.len() > 1 && lines.iter().all(|line| line.chars().count() == Board::COUNT_VALUES) => PuzzleCollection::from_sdm(content),
+        _ => parse_puzzle(content, extension).map(|puzzle| [puzzle].into_iter().collect()),
+    }
+}
+
+/// The content of the file or of the standard input for `-`, with the lowercase extension of the file
+fn read_content(source: &str) -> Result<(String, Option<String>), InputError> {
+    let error = |reason: String| InputError { source: source.to_string(), reason };
+
+    match source {
+        "-" => io::read_to_string(io::stdin()).map(|content| (content, None)).map_err(|io_error| error(io_error.to_string())),
+        _ => {
+            let content = fs::read_to_string(source).map_err(|io_error| error(io_error.to_string()))?;
+            let extension = Path::new(source).extension().and_then(|extension| extension.to_str()).map(|extension| extension.to_ascii_lowercase());
+            Ok((content, extension))
+        },
+    }
+}
+
+/// Parse the puzzle in the format of the file extension, or guess the format from the content
+fn parse_puzzle(content: &str, extension: Option<&str>) -> Result<Puzzle, Error> {
+    let trimmed = content.trim();
+    match extension {
+        Some("sdk") => Puzzle::from_sdk(content),
+        Some("ss") => Board::from_ss(content).map(Puzzle::new),
+        Some("json") => Puzzle::from_json(content),
+        Some(_) => parse_board(content).map(Puzzle::new),
+        None if trimmed.starts_with('{') => Puzzle::from_json(trimmed),
+        None if is_link(trimmed) => Puzzle::from_fpuzzles_url(trimmed),
+        None if trimmed.starts_with('#') => Puzzle::from_sdk(content),
+        None if trimmed.contains('|') => Board::from_ss(content).map(Puzzle::new),
+        None => parse_board(content).map(Puzzle::new),
+    }
+}
+
+/// Parse a single line in the one-line format and more lines as a grid
+fn parse_board(content: &str) -> Result<Board, Error> {
+    match content.lines().filter(|line| !line.trim().is_empty()).count() {
+        0 | 1 => content.parse(),
+        _ => Board::parse_grid(content),
+    }
+}
+
+/// The reason of a parse error without the input, which may be a whole file
+fn reason(error: Error) -> String {
+    match error {
+        Error::ParseError { reason, .. } => reason,
+        error => error.to_string(),
+    }
+}
+
+/// A puzzle which cannot be read or is malformed
+#[derive(Debug)]
+pub struct InputError {
+    /// The file or link, `-` for the standard input
+    pub source: String,
+    pub reason: String,
+}
+
+impl error::Error for InputError {}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.source.as_str() {
+            "-" => write!(f, "cannot read the puzzle of the standard input: {}", self.reason),
+            source => write!(f, "cannot read the puzzle of {source}: {}", self.reason),
+        }
+    }
+}
+
+/// How subcommands print boards, chosen with `--format`
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum OutputFormat {
+    /// Drawn with borders, showing the regions and decorations of variants
+    Pretty,
+    /// One line of 81 cells
+    Line,
+    /// 9 lines of 9 cells
+    Grid,
+    /// The JSON format of `Puzzle::to_json`, a collection for several puzzles
+    Json,
+    /// Comma-separated values: 9 rows of 9 values for one puzzle, a row of 81 values per puzzle for several
+    Csv,
+    /// An SVG image per puzzle
+    Svg,
+    /// An HTML snippet per puzzle
+    Html,
+}
+
+impl FromStr for OutputFormat {
+    type Err = UsageError;
+
+    fn from_str(s: &str) -> Result<Self, UsageError> {
+        match s {
+            "pretty" => Ok(OutputFormat::Pretty),
+            "line" => Ok(OutputFormat::Line),
+            "grid" => Ok(OutputFormat::Grid),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            "svg" => Ok(OutputFormat::Svg),
+            "html" => Ok(OutputFormat::Html),
+            _ => Err(UsageError(format!("unknown format {s:?}, expected pretty, line, grid, json, csv, svg or html"))),
+        }
+    }
+}
+
+impl Args {
+    /// Take out the `--format` option, `pretty` if it is missing
+    pub fn format(&mut self) -> Result<OutputFormat, UsageError> {
+        Ok(self.given_format()?.unwrap_or(OutputFormat::Pretty))
+    }
+
+    /// Take out the `--format` option if it is given, for subcommands which print a report without it
+    pub fn given_format(&mut self) -> Result<Option<OutputFormat>, UsageError> {
+        self.value("--format")?.map(|format| format.parse()).transpose()
+    }
+
+    /// Take out the `--timeout` option, a positive number of seconds like `2.5`
+    pub fn timeout(&mut self) -> Result<Option<Duration>, UsageError> {
+        match self.parsed::<f64>("--timeout")? {
+            Some(seconds) if seconds > 0.0 && seconds.is_finite() => Ok(Some(Duration::from_secs_f64(seconds))),
+            Some(seconds) => Err(UsageError(format!("invalid value {seconds} for --timeout, expected a positive number of seconds"))),
+            None => Ok(None),
+        }
+    }
+
+    /// Take out the `--seed` option, a seed from the clock if it is missing
+    pub fn seed(&mut self) -> Result<u64, UsageError> {
+        match self.parsed("--seed")? {
+            Some(seed) => Ok(seed),
+            None => Ok(SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_nanos() as u64)),
+        }
+    }
+}
+
+/// The board of the puzzle in the format, ending with a newline. Unassigned cells are `.`.
+pub fn show(puzzle: &Puzzle, format: OutputFormat) -> String {
+    let line = puzzle.board().to_line_string('.');
+    match format {
+        OutputFormat::Pretty => render(puzzle.board(), puzzle.variant()),
+        OutputFormat::Line => format!("{line}\n"),
+        OutputFormat::Grid => line.as_bytes().chunks(Board::COUNT_COLUMNS).map(|row| format!("{}\n", String::from_utf8_lossy(row))).collect(),
+        OutputFormat::Json => format!("{}\n", puzzle.to_json()),
+        OutputFormat::Csv => puzzle.board().to_csv(),
+        OutputFormat::Svg => svg::puzzle_to_svg(puzzle, &SvgOptions::default()),
+        OutputFormat::Html => html::puzzle_to_html(puzzle, &HtmlOptions::default()),
+    }
+}
+
+/// The puzzles in the format like `show`. The formats of collections hold all of them in
+/// one document, the others are separated by blank lines except for lines.
+pub fn show_all(puzzles: &[Puzzle], format: OutputFormat) -> String {
+    let shown: Vec<String> = puzzles.iter().map(|puzzle| show(puzzle, format)).collect();
+    match format {
+        OutputFormat::Json if puzzles.len() != 1 => puzzles.iter().cloned().collect::<PuzzleCollection>().to_json(),
+        OutputFormat::Csv if puzzles.len() != 1 => puzzles.iter().cloned().collect::<PuzzleCollection>().to_csv(),
+        OutputFormat::Line => shown.concat(),
+        _ => shown.join("\n"),
+    }
+}
+
+/// The board of another size than 9×9 in the format like `show`. Only pretty, line
+/// and grid print such boards, so the other formats fail with ``UsageError``.
+pub fn show_sized(board: &SizedBoard, format: OutputFormat) -> Result<String, UsageError> {
+    let symbols = SymbolSet::alphanumeric(board.size());
+    let line: String = board.values().iter().map(|value| symbols.symbol_of(*value).unwrap_or('?')).collect();
+    match format {
+        OutputFormat::Pretty if style().ascii => Ok(to_ascii(&board.to_string())),
+        OutputFormat::Pretty => Ok(board.to_string()),
+        OutputFormat::Line => Ok(format!("{line}\n")),
+        OutputFormat::Grid => Ok(line.chars().collect::<Vec<char>>().chunks(board.size()).map(|row| format!("{}\n", row.iter().collect::<String>())).collect()),
+        _ => Err(UsageError("only pretty, line and grid print boards of another size than 9×9".to_string())),
+    }
+}
+
+/// The board drawn with `.` for unassigned cells and with the regions and decorations of the variant
+pub fn render(board: &Board, variant: &Variant) -> String {
+    let drawn = match variant.is_classic() {
+        true => board.to_string_with(&SymbolSet::digits()),
+        false => {
+            let mut sudoku = Sudoku::default().with_variant(variant.clone());
+            sudoku.init_board(board);
+            sudoku.to_variant_string()
+        },
+    };
+    match style().ascii {
+        true => to_ascii(&drawn),
+        false => drawn,
+    }
+}
+
+/// The drawing with the box-drawing characters and shading replaced by ASCII characters
+fn to_ascii(drawn: &str) -> String {
+    drawn.chars()
+        .map(|c| match c {
+            '─' | '━' | '═' => '-',
+            '│' | '┃' | '║' => '|',
+            '‖' => '"',
+            '░' => ':',
+            '\u{2500}'..='\u{257f}' => '+',
+            c => c,
+        })
+        .collect()
+}
+
+/// The names of the engines of `--engine` in the order of the usage
+pub const ENGINES: [&str; 7] = ["backtracking", "backjumping", "dlx", "sat", "iterative", "parallel", "logic"];
+
+/// The solver of the engine for `--engine`
+pub fn solver(engine: &str) -> Result<Box<dyn Solver>, UsageError> {
+    match engine {
+        "backtracking" => Ok(Box::new(BacktrackingSolver::default())),
+        "backjumping" => Ok(Box::new(BacktrackingSolver { backjumping: true, ..BacktrackingSolver::default() })),
+        "dlx" => Ok(Box::new(DlxSolver)),
+        "sat" => Ok(Box::new(SatSolver)),
+        "iterative" => Ok(Box::new(IterativeSolver::default())),
+        "parallel" => Ok(Box::new(ParallelSolver::default())),
+        "logic" => Ok(Box::new(LogicSolver::default())),
+        _ => Err(UsageError(format!("unknown engine {engine}, expected one of {}", ENGINES.join(", ")))),
+    }
+}
@@ -0,0 +1,267 @@
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+
+use sudoku::{Board, Coord, Error, Move, Sudoku, Variant};
+
+use crate::cli::{read_puzzle, render, style, Args, CliResult, UsageError};
+
+pub const USAGE: &str = "\
+Play a puzzle in the terminal. The board fills the screen, and a cursor is
+moved over the cells with the arrow keys. Givens cannot be changed.
+
+Usage: sudokuSolver play [OPTIONS] <PUZZLE>
+
+Keys:
+  arrows                 move the cursor
+  1-9                    place the value in the cell under the cursor
+  0, space, backspace    clear the cell under the cursor
+  c                      check the board against the rules
+  h                      explain the next logical step
+  u                      take back the last move
+  r                      place the move taken back again
+  q                      stop playing
+
+The state of the board is printed as one line on quitting and, with --save,
+written to a JSON file which keeps the givens apart from the entries, so the
+game continues with `sudokuSolver play <FILE>`.
+
+Without a terminal, e.g. when the moves come from a script, one command is read
+per line: r4c5=7 to place 7 in row 4, column 5, r4c5=0 to clear the cell,
+undo, redo, hint, check and quit.
+
+Options:
+      --save <FILE>  write the state of the board to FILE on quitting
+      --lines        read one command per line even in a terminal
+  -h, --help         print this help
+";
+
+pub fn run(mut args: Args) -> CliResult {
+    let save = args.value("--save")?;
+    let lines = args.flag(&["--lines"]);
+    let source = args.positional("a puzzle")?;
+    if source == "-" {
+        return Err(UsageError("the moves are read from the standard input, so the puzzle must be a file or a link".to_string()).into());
+    }
+    let puzzle = read_puzzle(&source)?;
+    let mut sudoku = puzzle.to_sudoku();
+
+    match !lines && io::stdin().is_terminal() && io::stdout().is_terminal() {
+        true => play_screen(&mut sudoku, puzzle.variant())?,
+        false => play_lines(&mut sudoku, puzzle.variant())?,
+    }
+
+    if sudoku.finished() {
+        print!("{}", render(sudoku.board(), puzzle.variant()));
+        println!("solved, well done!");
+    } else {
+        // NOTE: the state is kept as one line, which every subcommand reads back
+        println!("{}", sudoku.board().to_line_string('.'));
+    }
+    if let Some(path) = save {
+        let content = format!("{}\n", puzzle.with_board(sudoku.board().clone()).to_json());
+        fs::write(&path, content).map_err(|io_error| Error::IoError { path: path.clone(), reason: io_error.to_string() })?;
+    }
+    Ok(())
+}
+
+/// Play one command per line until the board is finished or the input ends
+fn play_lines(sudoku: &mut Sudoku, variant: &Variant) -> CliResult {
+    let mut input = io::stdin().lock().lines();
+    loop {
+        print!("{}", render(sudoku.board(), variant));
+        if sudoku.finished() {
+            return Ok(());
+        }
+        print!("> ");
+        io::stdout().flush()?;
+        let Some(line) = input.next().transpose()? else { return Ok(()) };
+        match line.trim() {
+            "" => {},
+            "undo" => if sudoku.undo().is_none() { println!("nothing to undo") },
+            "redo" => if sudoku.redo().is_none() { println!("nothing to redo") },
+            "hint" => println!("{}", hint(sudoku)),
+            "check" => println!("{}", check(sudoku)),
+            "quit" => return Ok(()),
+            command => match command.parse::<Move>() {
+                Ok(placement) => if let Err(error) = sudoku.apply_move(&placement) { println!("{error}") },
+                Err(error) => println!("{error}"),
+            },
+        }
+    }
+}
+
+/// A key pressed in the full-screen mode
+enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Char(char),
+}
+
+/// Play on the whole screen of the terminal until the board is finished or the player quits
+fn play_screen(sudoku: &mut Sudoku, variant: &Variant) -> CliResult {
+    let _raw_mode = RawMode::enter()?;
+    let mut out = io::stdout().lock();
+    // NOTE: the alternate screen keeps the scrollback of the terminal as it was
+    write!(out, "\x1b[?1049h\x1b[?25l")?;
+
+    let mut stdin = io::stdin().lock();
+    let (mut row, mut col) = (0, 0);
+    let mut message = if variant.is_classic() { String::new() } else { "the puzzle follows the rules of a variant, see `check`".to_string() };
+    let result = loop {
+        if let Err(error) = draw(&mut out, sudoku.board(), row, col, &message) {
+            break Err(error);
+        }
+        if sudoku.finished() {
+            break Ok(());
+        }
+        let key = match read_key(&mut stdin) {
+            Ok(Some(key)) => key,
+            Ok(None) => break Ok(()),
+            Err(error) => break Err(error),
+        };
+        message.clear();
+        match key {
+            Key::Up => row = (row + Board::COUNT_ROWS - 1) % Board::COUNT_ROWS,
+            Key::Down => row = (row + 1) % Board::COUNT_ROWS,
+            Key::Left => col = (col + Board::COUNT_COLUMNS - 1) % Board::COUNT_COLUMNS,
+            Key::Right => col = (col + 1) % Board::COUNT_COLUMNS,
+            Key::Char(digit @ '0'..='9') => message = place(sudoku, row, col, digit as u8 - b'0'),
+            Key::Char(' ' | '\x08' | '\x7f') => message = place(sudoku, row, col, 0),
+            Key::Char('c') => message = check(sudoku),
+            Key::Char('h') => message = hint(sudoku),
+            Key::Char('u') => if sudoku.undo().is_none() { message = "nothing to undo".to_string() },
+            Key::Char('r') => if sudoku.redo().is_none() { message = "nothing to redo".to_string() },
+            // NOTE: Ctrl-C and Ctrl-D arrive as characters in raw mode
+            Key::Char('q' | '\x03' | '\x04') => break Ok(()),
+            Key::Char(_) => {},
+        }
+    };
+    write!(out, "\x1b[?25h\x1b[?1049l")?;
+    out.flush()?;
+    Ok(result?)
+}
+
+/// Apply the move to the cell, returning the error to show if it fails
+fn place(sudoku: &mut Sudoku, row: usize, col: usize, value: u8) -> String {
+    match sudoku.apply_move(&Move::new(Coord::new(row, col), value)) {
+        Ok(()) => String::new(),
+        Err(error) => error.to_string(),
+    }
+}
+
+/// Whether the board violates a rule, and the first violation if it does
+fn check(sudoku: &Sudoku) -> String {
+    match sudoku.violations().as_slice() {
+        [] => "no rule is violated".to_string(),
+        [violation] => violation.to_string(),
+        [violation, others @ ..] => format!("{violation} and {} more violations", others.len()),
+    }
+}
+
+fn hint(sudoku: &Sudoku) -> String {
+    match sudoku.next_hint() {
+        Some(hint) => hint.to_string(),
+        None => "no logical step found".to_string(),
+    }
+}
+
+/// Draw the board with the cursor on the cell, givens in bold, and the message and the keys below.
+/// Without colors, the cursor is shown by brackets and givens are not set apart.
+fn draw(out: &mut impl Write, board: &Board, row: usize, col: usize, message: &str) -> io::Result<()> {
+    let color = style().color;
+    // NOTE: in raw mode a line feed does not return the carriage
+    let mut screen = String::from("\x1b[H\x1b[2J\r\n");
+    for grid_row in 0..Board::COUNT_ROWS {
+        if grid_row % 3 == 0 {
+            screen.push_str("  +-------+-------+-------+\r\n");
+        }
+        screen.push_str("  |");
+        for grid_col in 0..Board::COUNT_COLUMNS {
+            let cell_id = grid_row * Board::COUNT_COLUMNS + grid_col;
+            let symbol = match board[cell_id].value() {
+                0 => '.',
+                value => char::from(b'0' + value),
+            };
+            // NOTE: without colors, brackets around the cursor take the spaces next to it
+            let before = match (color, grid_row == row, grid_col) {
+                (false, true, _) if grid_col == col => '[',
+                (false, true, _) if grid_col == col + 1 && grid_col % 3 != 0 => ']',
+                _ => ' ',
+            };
+            let shown = match (color, grid_row == row && grid_col == col, board.is_given(cell_id)) {
+                (true, true, true) => format!("\x1b[1;7m{symbol}\x1b[0m"),
+                (true, true, false) => format!("\x1b[7m{symbol}\x1b[0m"),
+                (true, false, true) => format!("\x1b[1m{symbol}\x1b[0m"),
+                _ => symbol.to_string(),
+            };
+            screen.push(before);
+            screen.push_str(&shown);
+            if grid_col % 3 == 2 {
+                let after = if !color && grid_row == row && grid_col == col { ']' } else { ' ' };
+                screen.push(after);
+                screen.push('|');
+            }
+        }
+        screen.push_str("\r\n");
+    }
+    screen.push_str("  +-------+-------+-------+\r\n\r\n");
+    screen.push_str(&format!("  r{}c{}  {message}\r\n\r\n", row + 1, col + 1));
+    screen.push_str("  arrows move   1-9 place   0 clear   c check   h hint   u undo   r redo   q quit\r\n");
+    out.write_all(screen.as_bytes())?;
+    out.flush()
+}
+
+/// The next key, or `None` at the end of the input. Escape sequences other than the arrows are skipped.
+fn read_key(input: &mut impl Read) -> io::Result<Option<Key>> {
+    let mut byte = [0];
+    loop {
+        if input.read(&mut byte)? == 0 {
+            return Ok(None);
+        }
+        if byte[0] != b'\x1b' {
+            return Ok(Some(Key::Char(char::from(byte[0]))));
+        }
+        let mut sequence = [0; 2];
+        input.read_exact(&mut sequence)?;
+        match sequence {
+            [b'[', b'A'] => return Ok(Some(Key::Up)),
+            [b'[', b'B'] => return Ok(Some(Key::Down)),
+            [b'[', b'C'] => return Ok(Some(Key::Right)),
+            [b'[', b'D'] => return Ok(Some(Key::Left)),
+            _ => {},
+        }
+    }
+}
+
+/// The terminal reading every key as it is pressed without echoing it, until dropped
+struct RawMode {
+    /// The settings before, as printed by `stty -g`
+    saved: String,
+}
+
+impl RawMode {
+    fn enter() -> io::Result<Self> {
+        let saved = stty(&["-g"])?;
+        stty(&["raw", "-echo"])?;
+        Ok(Self { saved: saved.trim().to_string() })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        // NOTE: a terminal which cannot be restored cannot show the error either
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+/// Run `stty` on the terminal of the standard input, returning its output
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty").args(args).stdin(Stdio::inherit()).output()?;
+    match output.status.success() {
+        true => Ok(String::from_utf8_lossy(&output.stdout).into_owned()),
+        false => Err(io::Error::other(format!("stty failed: {}", String::from_utf8_lossy(&output.stderr).trim()))),
+    }
+}
@@ -0,0 +1,120 @@
+use std::time::{Duration, Instant};
+
+use sudoku::{Puzzle, Rating};
+
+use crate::cli::{read_puzzles, show_all, style, Args, CliResult, TimeoutError, UsageError, Verbosity};
+
+pub const USAGE: &str = "\
+Grade how hard classic puzzles are for a human who never guesses: the
+difficulty, the hardest technique needed, how often each technique is applied
+and a score summing up the weights of all steps.
+
+Usage: sudokuSolver rate [OPTIONS] <PUZZLES>
+
+The puzzles are a file of one puzzle, a collection file (.sdm, .csv,
+.opensudoku or .json) or - for the standard input.
+
+With --quiet, only the difficulty of every puzzle is printed, one per line.
+With --timeout, a puzzle which is not rated within the time limit is reported
+as timed out.
+
+With --format, the puzzles are printed in the format like with `solve`, in
+json with their difficulty, and the report moves to the standard error, so the
+output can be passed on.
+
+Options:
+      --table            print one row per puzzle instead of a report
+      --timeout <SECS>   give up on a puzzle after SECS seconds
+      --format <FORMAT>  pretty, line, grid, json, csv, svg or html
+  -h, --help             print this help
+";
+
+pub fn run(mut args: Args) -> CliResult {
+    let table = args.flag(&["--table"]);
+    let timeout = args.timeout()?;
+    let format = args.given_format()?;
+    let collection = read_puzzles(&args.positional("puzzles")?)?;
+    if collection.iter().any(|puzzle| !puzzle.variant().is_classic()) {
+        return Err(UsageError("only classic puzzles can be rated".to_string()).into());
+    }
+
+    let verbosity = style().verbosity;
+    let ratings: Vec<Option<Rating>> = collection.boards().enumerate()
+        .map(|(i, board)| {
+            let started = Instant::now();
+            let rating = match timeout {
+                Some(timeout) => Rating::of_within(board, timeout),
+                None => Some(Rating::of(board)),
+            };
+            if verbosity >= Verbosity::Verbose {
+                eprintln!("puzzle {}: rated in {:.3} ms", i + 1, started.elapsed().as_secs_f64() * 1000.0);
+            }
+            rating
+        })
+        .collect();
+    let out = match verbosity {
+        Verbosity::Quiet => ratings.iter().map(|rating| format!("{}\n", rating.as_ref().map_or("timeout", |rating| rating.difficulty.name()))).collect(),
+        _ if table => table_of(&ratings),
+        _ => reports(&ratings, timeout.unwrap_or_default()),
+    };
+    match format {
+        Some(format) => {
+            let rated: Vec<Puzzle> = collection.iter().zip(ratings.iter())
+                .map(|(puzzle, rating)| match rating {
+                    Some(rating) => puzzle.clone().with_difficulty(rating.difficulty.name()),
+                    None => puzzle.clone(),
+                })
+                .collect();
+            print!("{}", show_all(&rated, format));
+            eprint!("{out}");
+        },
+        None => print!("{out}"),
+    }
+    Ok(())
+}
+
+/// One row per puzzle below a header
+fn table_of(ratings: &[Option<Rating>]) -> String {
+    let mut out = format!("{:>5}  {:<12}  {:>5}  {:>5}  hardest technique\n", "#", "difficulty", "score", "steps");
+    for (i, rating) in ratings.iter().enumerate() {
+        let Some(rating) = rating else {
+            out.push_str(&format!("{:>5}  timed out\n", i + 1));
+            continue;
+        };
+        let hardest = rating.hardest.map_or("-".to_string(), |technique| technique.to_string());
+        let hardest = if rating.solved { hardest } else { format!("{hardest}, guessing needed") };
+        out.push_str(&format!("{:>5}  {:<12}  {:>5}  {:>5}  {hardest}\n", i + 1, rating.difficulty.name(), rating.score, rating.step_count()));
+    }
+    out
+}
+
+/// The report of every puzzle, headed by its number if there are several
+fn reports(ratings: &[Option<Rating>], timeout: Duration) -> String {
+    let mut out = String::new();
+    for (i, rating) in ratings.iter().enumerate() {
+        if ratings.len() > 1 {
+            out.push_str(&format!("{}puzzle {}\n", if i > 0 { "\n" } else { "" }, i + 1));
+        }
+        match rating {
+            Some(rating) => out.push_str(&report(rating)),
+            None => out.push_str(&format!("{}\n", TimeoutError(timeout))),
+        }
+    }
+    out
+}
+
+/// The rating over several lines, listing the steps per technique
+fn report(rating: &Rating) -> String {
+    let mut out = format!("difficulty: {}\nscore: {}\n", rating.difficulty, rating.score);
+    if let Some(hardest) = rating.hardest {
+        out.push_str(&format!("hardest technique: {hardest}\n"));
+    }
+    out.push_str(&format!("steps: {}\n", rating.step_count()));
+    for (technique, count) in rating.steps.iter() {
+        out.push_str(&format!("  {:<24}{count:>4}\n", technique.name()));
+    }
+    if !rating.solved {
+        out.push_str("the techniques get stuck, so solving needs guessing\n");
+    }
+    out
+}
@@ -0,0 +1,145 @@
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use sudoku::{Board, Coord, Error, Move, Puzzle, Sudoku, Value};
+
+use crate::cli::{read_puzzle, render, Args, CliResult, UsageError};
+
+pub const USAGE: &str = "\
+Work on a puzzle with one command per line and see the board after every
+command, a lightweight alternative to `play` for terminals and scripts.
+
+Usage: sudokuSolver repl [PUZZLE]
+
+The session starts with the puzzle, or with the empty board without one. The
+commands are read from the standard input, and a prompt is shown on a terminal.
+
+Commands:
+  load <PUZZLE>         start over with another puzzle
+  set <CELL> <VALUE>    place the value in the cell, e.g. set r4c5 7
+  clear <CELL>          remove the value of the cell
+  undo                  take back the last move
+  redo                  place the move taken back again
+  candidates <CELL>     the values the cell can still hold
+  hint                  explain the next logical step
+  check                 check the board against the rules
+  solve                 fill in the solution, one move per cell for undo
+  show                  print the board
+  save <FILE>           write the state to a JSON file, like `play --save`
+  help                  list the commands
+  quit                  end the session, as does the end of the input
+
+Options:
+  -h, --help  print this help
+";
+
+/// The commands of the session, as `help` lists them
+const COMMANDS: &str = "load <PUZZLE>, set <CELL> <VALUE>, clear <CELL>, undo, redo, candidates <CELL>, hint, check, solve, show, save <FILE>, help, quit";
+
+pub fn run(args: Args) -> CliResult {
+    let positionals = args.positionals()?;
+    let mut puzzle = match positionals.as_slice() {
+        [] => Puzzle::new(Board::default()),
+        [source] => read_puzzle(source)?,
+        _ => return Err(UsageError("expected at most one puzzle".to_string()).into()),
+    };
+    let mut sudoku = puzzle.to_sudoku();
+
+    let prompt = io::stdin().is_terminal();
+    let mut input = io::stdin().lock().lines();
+    print!("{}", render(sudoku.board(), puzzle.variant()));
+    loop {
+        if prompt {
+            print!("> ");
+            io::stdout().flush()?;
+        }
+        let Some(line) = input.next().transpose()? else { return Ok(()) };
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let message = match words.as_slice() {
+            [] => continue,
+            ["quit" | "exit"] => return Ok(()),
+            ["help"] => {
+                println!("{COMMANDS}");
+                continue;
+            },
+            ["load", source] => match read_puzzle(source) {
+                Ok(loaded) => {
+                    puzzle = loaded;
+                    sudoku = puzzle.to_sudoku();
+                    None
+                },
+                Err(error) => Some(error.to_string()),
+            },
+            ["set", cell, value] => set(&mut sudoku, cell, value),
+            ["clear", cell] => set(&mut sudoku, cell, "0"),
+            ["undo"] => sudoku.undo().map_or(Some("nothing to undo".to_string()), |_| None),
+            ["redo"] => sudoku.redo().map_or(Some("nothing to redo".to_string()), |_| None),
+            ["candidates", cell] => Some(candidates(&sudoku, cell)),
+            ["hint"] => Some(sudoku.next_hint().map_or("no logical step found".to_string(), |hint| hint.to_string())),
+            ["check"] => Some(check(&sudoku)),
+            ["solve"] => solve(&mut sudoku),
+            ["show"] => None,
+            ["save", path] => {
+                let content = format!("{}\n", puzzle.clone().with_board(sudoku.board().clone()).to_json());
+                match fs::write(path, content) {
+                    Ok(()) => Some(format!("saved to {path}")),
+                    Err(io_error) => Some(Error::IoError { path: path.to_string(), reason: io_error.to_string() }.to_string()),
+                }
+            },
+            [command, ..] => Some(format!("unknown command {command} or wrong arguments, expected one of: {COMMANDS}")),
+        };
+        if let Some(message) = message {
+            println!("{message}");
+        }
+        print!("{}", render(sudoku.board(), puzzle.variant()));
+        if sudoku.finished() {
+            println!("solved!");
+        }
+    }
+}
+
+/// Place the value in the cell, 0 clearing it. Returns the error to show if it fails.
+fn set(sudoku: &mut Sudoku, cell: &str, value: &str) -> Option<String> {
+    let cell: Coord = match cell.parse() {
+        Ok(cell) => cell,
+        Err(error) => return Some(error.to_string()),
+    };
+    let value: Value = match value.parse() {
+        Ok(value) if value <= 9 => value,
+        _ => return Some(format!("expected a value from 0 to 9 but got {value}")),
+    };
+    sudoku.apply_move(&Move::new(cell, value)).err().map(|error| error.to_string())
+}
+
+/// The values the cell can still hold, or its value if it is assigned
+fn candidates(sudoku: &Sudoku, cell: &str) -> String {
+    let cell: Coord = match cell.parse() {
+        Ok(cell) => cell,
+        Err(error) => return error.to_string(),
+    };
+    match sudoku.board()[cell.cell_id()].value() {
+        0 => format!("{cell}: {}", sudoku.legal_values(cell.cell_id())),
+        value => format!("{cell} holds {value}"),
+    }
+}
+
+/// Whether the board violates a rule, listing every violation
+fn check(sudoku: &Sudoku) -> String {
+    match sudoku.violations().as_slice() {
+        [] => "no rule is violated".to_string(),
+        violations => violations.iter().map(|violation| format!("violation: {violation}")).collect::<Vec<String>>().join("\n"),
+    }
+}
+
+/// Place the values of the solution in the unassigned cells as one move each
+fn solve(sudoku: &mut Sudoku) -> Option<String> {
+    let solution = match sudoku.solve() {
+        Ok(solution) => solution,
+        Err(error) => return Some(error.to_string()),
+    };
+    let moves: Vec<Move> = (0..Board::COUNT_VALUES)
+        .filter(|cell_id| sudoku.board()[*cell_id].value() == 0)
+        .map(|cell_id| Move::new(Coord::from_cell_id(cell_id), solution[cell_id].value()))
+        .collect();
+    sudoku.apply_moves(&moves).err().map(|error| error.to_string())
+}
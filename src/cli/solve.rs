@@ -0,0 +1,408 @@
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use sudoku::solver::{SolveConfig, SolveOutcome, SolveStats, Solver};
+use sudoku::{Board, Error, Puzzle, SizedBoard};
+
+use crate::cli::{describe_search, is_link, read_any_puzzle, read_puzzle, read_puzzles, show, show_all, show_sized, solver, style, AnyPuzzle, Args, CliResult, EngineStalled, NotUnique, OutputFormat, ProgressLine, TimeoutError, UsageError, Verbosity};
+
+pub const USAGE: &str = "\
+Print the solution of a puzzle, or solve all puzzles of a collection file.
+
+Usage: sudokuSolver solve [OPTIONS] <PUZZLE>
+       sudokuSolver solve --batch <PUZZLES> [--out <FILE>]
+       sudokuSolver solve --stdin-lines
+
+The puzzle is a file, - for the standard input or an f-puzzles or SudokuPad
+link. Files ending in .sdk, .ss and .json are read in these formats, others as
+one line of 81 cells or as a grid of 9 lines. The format of the standard input
+is recognized by its content.
+
+A line or grid of another square number of cells is a board of that size, e.g.
+16 cells for 4×4 or 256 for 16×16 with the values from 10 written as letters
+from A. Such a board is printed as pretty, line or grid and takes none of
+--engine, --seed, --timeout, --max-solutions, --progress and --stats.
+
+With --batch, every puzzle of the collection file (.sdm, .csv, .opensudoku or
+.json) is solved in order, and its solution is written as one line of 81 cells,
+or the line `unsolvable` or `invalid` for a puzzle without a solution or one
+violating the rules. A summary with the counts and the time follows on the
+standard error unless --quiet is given, and --verbose adds the time of every
+puzzle.
+
+With --stdin-lines, the tool is a filter: every line of the standard input is
+a puzzle of 81 cells, and the line of its solution, or `unsolvable`, `invalid`
+or `malformed` for a line which is not a puzzle, is printed as soon as it is
+found. Blank lines stay blank.
+
+With --timeout, the search for a solution of a puzzle gives up after the time
+limit, and --batch and --stdin-lines print the line `timeout` for it.
+
+With --engine, classic puzzles are solved by another solver, e.g. to compare
+the solutions. The logic engine may get stuck, which fails with exit status 1,
+and --batch and --stdin-lines print the line `stalled` for it. The engines
+take no seed, no time limit and report no search, so --engine cannot be
+combined with the options from --seed to --watch.
+
+With --max-solutions, up to N solutions are printed, and a note on the standard
+error tells if there are more.
+
+With --progress, the nodes of the search, an estimate of its share done and
+the time so far are printed on the standard error every second, to tell a slow
+search from a stuck one.
+
+With --stats, the statistics of the search follow the solution on the standard
+error: the nodes visited, the guesses undone, the values placed by propagating
+singles, the most guesses on the way to a node and the time. With --format
+json, they are the member `stats` of the solution instead.
+
+With --watch, the puzzle file is solved again whenever it changes, until the
+tool is interrupted with Ctrl-C. The screen is cleared and shows whether the
+puzzle violates the rules, has no, a unique or several solutions, and a
+solution.
+
+With --verbose, the time and the numbers of guesses and backtracks of the
+search follow on the standard error, and -vv adds every step of the search.
+
+A puzzle with several solutions gets the first one the search finds, or with
+--seed a pseudo-random one, the same for the same seed. With --unique, such a
+puzzle fails instead.
+
+Exit status: 0 if the puzzle was solved, 2 for invalid input or a board which
+violates the rules, 3 for a puzzle without a solution, 4 for one with several
+solutions and --unique, and 5 if the time of --timeout ran out. --batch and
+--stdin-lines exit with 0 unless the input cannot be read.
+
+Options:
+      --format <FORMAT>  pretty (the default), line, grid, json, csv, svg or
+                         html
+      --batch <PUZZLES>  solve all puzzles of the file
+      --out <FILE>       write the solutions of --batch to FILE instead of the
+                         standard output
+      --stdin-lines      solve the puzzles of the lines of the standard input
+      --engine <ENGINE>  backtracking, backjumping, dlx, sat, iterative,
+                         parallel or logic instead of the search of the library
+      --seed <SEED>      try the values of a cell in pseudo-random order
+      --timeout <SECS>   give up on a puzzle after SECS seconds
+      --max-solutions <N>
+                         print up to N solutions of the puzzle instead of one
+      --unique           fail if the puzzle has more than one solution
+      --progress         report the progress of a long search
+      --stats            report the statistics of the search
+      --watch            solve the puzzle file again whenever it changes
+  -h, --help             print this help
+";
+
+pub fn run(mut args: Args) -> CliResult {
+    let seed = args.parsed("--seed")?;
+    let config = SolveConfig { seed, timeout: args.timeout()?, ..SolveConfig::default() };
+    let max_solutions: Option<usize> = args.parsed("--max-solutions")?;
+    if max_solutions.is_some() && (seed.is_some() || config.timeout.is_some()) {
+        return Err(UsageError("--max-solutions cannot be combined with --seed or --timeout".to_string()).into());
+    }
+    let unique = args.flag(&["--unique"]);
+    let progress = args.flag(&["--progress"]);
+    let stats = args.flag(&["--stats"]);
+    if max_solutions.is_some() && (unique || progress || stats) {
+        return Err(UsageError("--max-solutions cannot be combined with --unique, --progress or --stats".to_string()).into());
+    }
+    let watch = args.flag(&["--watch"]);
+    if watch && (max_solutions.is_some() || unique || progress || stats) {
+        return Err(UsageError("--watch cannot be combined with --max-solutions, --unique, --progress or --stats".to_string()).into());
+    }
+    let engine = match args.value("--engine")? {
+        Some(name) => Some((solver(&name)?, name)),
+        None => None,
+    };
+    if engine.is_some() && (seed.is_some() || config.timeout.is_some() || max_solutions.is_some() || unique || progress || stats || watch) {
+        return Err(UsageError("--engine cannot be combined with --seed, --timeout, --max-solutions, --unique, --progress, --stats or --watch".to_string()).into());
+    }
+    let solver = engine.as_ref().map(|(solver, _)| solver.as_ref());
+    let batch = args.value("--batch")?;
+    let lines = args.flag(&["--stdin-lines"]);
+    if (max_solutions.is_some() || unique || progress || stats || watch) && (batch.is_some() || lines) {
+        return Err(UsageError("--max-solutions, --unique, --progress, --stats and --watch apply to a single puzzle only".to_string()).into());
+    }
+    if let Some(batch) = batch {
+        let out = args.value("--out")?;
+        args.positionals()?;
+        return solve_batch(&batch, out.as_deref(), &config, solver);
+    }
+    if lines {
+        args.positionals()?;
+        return solve_lines(&config, solver);
+    }
+    let format = args.format()?;
+    let source = args.positional("a puzzle")?;
+    if watch {
+        return watch_file(&source, format, &config);
+    }
+    let puzzle = match read_any_puzzle(&source)? {
+        AnyPuzzle::Classic(puzzle) => *puzzle,
+        AnyPuzzle::Sized(board) => {
+            if engine.is_some() || seed.is_some() || config.timeout.is_some() || max_solutions.is_some() || progress || stats {
+                return Err(UsageError("a board of another size than 9×9 takes none of --engine, --seed, --timeout, --max-solutions, --progress and --stats".to_string()).into());
+            }
+            return solve_sized(&board, format, unique);
+        },
+    };
+    puzzle.to_sudoku().verify_board()?;
+    if let Some(max_solutions) = max_solutions {
+        return print_solutions(&puzzle, max_solutions, format);
+    }
+    if solver.is_some() && !puzzle.variant().is_classic() {
+        return Err(UsageError("--engine solves classic puzzles only".to_string()).into());
+    }
+    let verbosity = style().verbosity;
+    let sudoku = puzzle.to_sudoku();
+    let started = Instant::now();
+    let mut line = progress.then(ProgressLine::start);
+    if unique {
+        let count = match line.as_mut() {
+            Some(line) => sudoku.count_solutions_observed(2, &config, &mut |progress| line.update(|| describe_search(progress))),
+            None => sudoku.count_solutions_with(2, &config),
+        };
+        if let Some(line) = line.as_mut() {
+            line.finish();
+        }
+        match count {
+            None => return Err(TimeoutError(config.timeout.unwrap_or_default()).into()),
+            Some(0) => return Err(Error::Unsolvable.into()),
+            Some(1) => {},
+            Some(_) => return Err(NotUnique.into()),
+        }
+    }
+    // NOTE: only the search without a seed, a timeout, progress reports or statistics is traced, and only for classic puzzles
+    let traced = verbosity >= Verbosity::Verbose && config.seed.is_none() && config.timeout.is_none() && line.is_none() && !stats
+        && puzzle.variant().is_classic();
+    let (outcome, search_stats, trace) = match (solver, line.as_mut()) {
+        (Some(solver), _) => (solver.solve(puzzle.board()), None, None),
+        (None, Some(line)) => {
+            let (outcome, search_stats) = sudoku.solve_observed(&config, &mut |progress| line.update(|| describe_search(progress)));
+            (outcome, Some(search_stats), None)
+        },
+        (None, None) if traced => match sudoku.solve_traced() {
+            Ok((solution, trace)) => (SolveOutcome::Solved(solution), None, Some(trace)),
+            Err(_) => (SolveOutcome::Unsolvable, None, None),
+        },
+        (None, None) if stats => {
+            let (outcome, search_stats) = sudoku.solve_with_stats(&config);
+            (outcome, Some(search_stats), None)
+        },
+        (None, None) => (sudoku.solve_with(&config), None, None),
+    };
+    if let Some(line) = line.as_mut() {
+        line.finish();
+    }
+    let elapsed = started.elapsed();
+    let solution = match outcome {
+        SolveOutcome::Solved(solution) => solution,
+        SolveOutcome::Aborted(_) => return Err(TimeoutError(config.timeout.unwrap_or_default()).into()),
+        SolveOutcome::Stalled(_) => return Err(EngineStalled(engine.map(|(_, name)| name).unwrap_or_default()).into()),
+        SolveOutcome::Unsolvable => return Err(Error::Unsolvable.into()),
+    };
+
+    if let Some(trace) = trace.as_ref().filter(|_| verbosity >= Verbosity::VeryVerbose) {
+        eprint!("{trace}");
+    }
+    let solved = Puzzle::new(solution).with_variant(puzzle.variant().clone());
+    match search_stats.filter(|_| stats) {
+        Some(search_stats) if format == OutputFormat::Json => println!("{}", solved.to_json_with_stats(&search_stats)),
+        Some(search_stats) => {
+            print!("{}", show(&solved, format));
+            eprintln!("{}", describe_stats(&search_stats));
+        },
+        None => print!("{}", show(&solved, format)),
+    }
+    if verbosity >= Verbosity::Verbose {
+        match trace {
+            Some(trace) => eprintln!("solved in {:.3} ms with {} guesses and {} backtracks", elapsed.as_secs_f64() * 1000.0, trace.count_guesses(), trace.count_backtracks()),
+            None => eprintln!("solved in {:.3} ms", elapsed.as_secs_f64() * 1000.0),
+        }
+    }
+    Ok(())
+}
+
+/// Solve the puzzle of the file and show the result again whenever the file changes
+fn watch_file(source: &str, format: OutputFormat, config: &SolveConfig) -> CliResult {
+    if source == "-" || is_link(source) {
+        return Err(UsageError("--watch needs a puzzle file".to_string()).into());
+    }
+    let version = |source: &str| fs::metadata(source).ok().map(|metadata| (metadata.modified().ok(), metadata.len()));
+    if version(source).is_none() {
+        read_puzzle(source)?;
+    }
+
+    let terminal = io::stdout().is_terminal();
+    let mut shown = None;
+    loop {
+        let current = version(source);
+        // NOTE: editors may replace the file on saving, so a missing file is awaited instead of reported
+        if current.is_some() && current != shown {
+            shown = current;
+            let clear = if terminal { "\x1b[H\x1b[2J" } else { "\n" };
+            print!("{clear}{}", watch_report(source, format, config));
+            if terminal {
+                println!("\nwatching {source}, press Ctrl-C to stop");
+            }
+            io::stdout().flush()?;
+        }
+        thread::sleep(Duration::from_millis(200));
+    }
+}
+
+/// What `--watch` shows for the puzzle of the file: the violations of the rules,
+/// the number of solutions up to two and a solution
+fn watch_report(source: &str, format: OutputFormat, config: &SolveConfig) -> String {
+    let puzzle = match read_puzzle(source) {
+        Ok(puzzle) => puzzle,
+        Err(error) => return format!("error: {error}\n"),
+    };
+    let sudoku = puzzle.to_sudoku();
+    let violations = sudoku.violations();
+    if !violations.is_empty() {
+        return violations.iter().map(|violation| format!("violation: {violation}\n")).collect();
+    }
+
+    let timed_out = || format!("{}\n", TimeoutError(config.timeout.unwrap_or_default()));
+    let status = match sudoku.count_solutions_with(2, config) {
+        None => return timed_out(),
+        Some(0) => return "no solution\n".to_string(),
+        Some(1) => "unique solution",
+        Some(_) => "several solutions, one of them is",
+    };
+    match sudoku.solve_with(config) {
+        SolveOutcome::Solved(solution) => format!("{status}\n{}", show(&Puzzle::new(solution).with_variant(puzzle.variant().clone()), format)),
+        _ => timed_out(),
+    }
+}
+
+/// The statistics of a search as a line for the standard error
+fn describe_stats(stats: &SolveStats) -> String {
+    let elapsed = stats.elapsed.as_secs_f64() * 1000.0;
+    format!("stats: {} nodes, {} backtracks, {} propagations, max depth {}, {elapsed:.3} ms", stats.nodes, stats.backtracks, stats.propagations, stats.max_depth)
+}
+
+/// Print the solution of a board of another size than 9×9, which has to be unique with `unique`
+fn solve_sized(board: &SizedBoard, format: OutputFormat, unique: bool) -> CliResult {
+    show_sized(board, format)?;
+    board.verify()?;
+    if unique {
+        match board.count_solutions(2) {
+            0 => return Err(Error::Unsolvable.into()),
+            1 => {},
+            _ => return Err(NotUnique.into()),
+        }
+    }
+    let started = Instant::now();
+    let solution = board.solve()?;
+    print!("{}", show_sized(&solution, format)?);
+    if style().verbosity >= Verbosity::Verbose {
+        eprintln!("solved in {:.3} ms", started.elapsed().as_secs_f64() * 1000.0);
+    }
+    Ok(())
+}
+
+/// Print up to `limit` solutions, noting on the standard error if there are more
+fn print_solutions(puzzle: &Puzzle, limit: usize, format: OutputFormat) -> CliResult {
+    // NOTE: one solution more tells whether the output is truncated
+    let mut solutions = puzzle.to_sudoku().solutions(limit.saturating_add(1));
+    let truncated = solutions.len() > limit;
+    solutions.truncate(limit);
+    if solutions.is_empty() && limit > 0 {
+        return Err(Error::Unsolvable.into());
+    }
+
+    let solutions: Vec<Puzzle> = solutions.into_iter().map(|solution| Puzzle::new(solution).with_variant(puzzle.variant().clone())).collect();
+    print!("{}", show_all(&solutions, format));
+    if truncated && style().verbosity >= Verbosity::Normal {
+        eprintln!("stopped after {limit} solutions, there are more");
+    }
+    Ok(())
+}
+
+/// Solve the puzzles of the file one by one, writing a line per puzzle to the file or the standard output
+fn solve_batch(source: &str, out: Option<&str>, config: &SolveConfig, solver: Option<&dyn Solver>) -> CliResult {
+    if out.is_some_and(|out| out == source) {
+        return Err(UsageError("the solutions would overwrite the puzzles".to_string()).into());
+    }
+    let collection = read_puzzles(source)?;
+    if solver.is_some() && collection.iter().any(|puzzle| !puzzle.variant().is_classic()) {
+        return Err(UsageError("--engine solves classic puzzles only".to_string()).into());
+    }
+    let started = Instant::now();
+
+    let (mut solved, mut unsolvable, mut invalid, mut timeouts, mut stalled) = (0, 0, 0, 0, 0);
+    let mut lines = String::new();
+    let verbosity = style().verbosity;
+    for (i, puzzle) in collection.iter().enumerate() {
+        let puzzle_started = Instant::now();
+        let line = solution_line(puzzle, config, solver);
+        if verbosity >= Verbosity::Verbose {
+            let result = if line.len() == Board::COUNT_VALUES { "solved" } else { line.as_str() };
+            eprintln!("puzzle {}: {result} in {:.3} ms", i + 1, puzzle_started.elapsed().as_secs_f64() * 1000.0);
+        }
+        match line.as_str() {
+            "invalid" => invalid += 1,
+            "unsolvable" => unsolvable += 1,
+            "timeout" => timeouts += 1,
+            "stalled" => stalled += 1,
+            _ => solved += 1,
+        }
+        lines.push_str(&format!("{line}\n"));
+    }
+    match out {
+        Some(path) => fs::write(path, lines).map_err(|io_error| Error::IoError { path: path.to_string(), reason: io_error.to_string() })?,
+        None => print!("{lines}"),
+    }
+
+    if verbosity == Verbosity::Quiet {
+        return Ok(());
+    }
+    let elapsed = started.elapsed();
+    eprintln!(
+        "{} puzzles: {solved} solved, {unsolvable} unsolvable, {invalid} invalid, {timeouts} timed out, {stalled} stalled in {:.3} s",
+        collection.len(), elapsed.as_secs_f64(),
+    );
+    Ok(())
+}
+
+/// Solve the puzzle of every line of the standard input, printing the line of the solution right away
+fn solve_lines(config: &SolveConfig, solver: Option<&dyn Solver>) -> CliResult {
+    let mut out = io::stdout().lock();
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        let solution = match line.trim() {
+            "" => String::new(),
+            cells => match cells.parse::<Board>() {
+                Ok(board) => solution_line(&Puzzle::new(board), config, solver),
+                Err(_) => "malformed".to_string(),
+            },
+        };
+        writeln!(out, "{solution}")?;
+        // NOTE: the program at the other end of the pipe may wait for every line
+        out.flush()?;
+    }
+    Ok(())
+}
+
+/// The solution as one line of 81 cells, or `invalid` if the puzzle violates a rule, `unsolvable`
+/// if it has no solution, `timeout` if the search gave up and `stalled` if the engine got stuck
+fn solution_line(puzzle: &Puzzle, config: &SolveConfig, solver: Option<&dyn Solver>) -> String {
+    let sudoku = puzzle.to_sudoku();
+    if sudoku.verify_board().is_err() {
+        return "invalid".to_string();
+    }
+    let outcome = match solver {
+        Some(solver) => solver.solve(puzzle.board()),
+        None => sudoku.solve_with(config),
+    };
+    match outcome {
+        SolveOutcome::Solved(solution) => solution.to_line_string('.'),
+        SolveOutcome::Aborted(_) => "timeout".to_string(),
+        SolveOutcome::Stalled(_) => "stalled".to_string(),
+        SolveOutcome::Unsolvable => "unsolvable".to_string(),
+    }
+}
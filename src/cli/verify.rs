@@ -0,0 +1,59 @@
+use sudoku::Board;
+
+use crate::cli::{read_puzzle, show, style, Args, CliResult, InvalidBoard, Verbosity};
+
+pub const USAGE: &str = "\
+Check a board against the rules of its puzzle. Lists every violation, e.g. each
+value repeated in a row, column or block with the cells holding it, and tells
+whether the board is complete and whether the puzzle has a solution and a
+unique one. Fails with exit status 2 if a rule is violated. With --quiet, only
+the violations are printed.
+
+Usage: sudokuSolver verify [OPTIONS] <PUZZLE>
+
+With --format, the board is printed in the format like with `solve`, and the
+report moves to the standard error, so the output can be passed on.
+
+Options:
+      --format <FORMAT>  pretty, line, grid, json, csv, svg or html
+  -h, --help             print this help
+";
+
+pub fn run(mut args: Args) -> CliResult {
+    let format = args.given_format()?;
+    let puzzle = read_puzzle(&args.positional("a puzzle")?)?;
+    let sudoku = puzzle.to_sudoku();
+    let violations = sudoku.violations();
+    let unassigned = (0..Board::COUNT_VALUES).filter(|cell_id| sudoku.board()[*cell_id].value() == 0).count();
+
+    let mut out = String::new();
+    for violation in violations.iter() {
+        out.push_str(&format!("violation: {violation}\n"));
+    }
+    if style().verbosity >= Verbosity::Normal {
+        match unassigned {
+            0 => out.push_str("complete: yes\n"),
+            _ => out.push_str(&format!("complete: no, {unassigned} unassigned cells\n")),
+        }
+        // NOTE: a board which violates a rule has no solutions
+        let solutions = sudoku.count_solutions(2);
+        out.push_str(&format!("solvable: {}\n", if solutions > 0 { "yes" } else { "no" }));
+        out.push_str(&format!("unique solution: {}\n", if solutions == 1 { "yes" } else { "no" }));
+    }
+    match format {
+        Some(format) => {
+            print!("{}", show(&puzzle, format));
+            eprint!("{out}");
+        },
+        None => print!("{out}"),
+    }
+
+    result(violations.len())
+}
+
+fn result(count_violations: usize) -> CliResult {
+    match count_violations {
+        0 => Ok(()),
+        _ => Err(InvalidBoard { count_violations }.into()),
+    }
+}
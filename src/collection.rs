@@ -0,0 +1,192 @@
+use std::collections::HashSet;
+use std::fs;
+use std::ops::Index;
+use std::path::Path;
+
+use crate::board::Board;
+use crate::error::Error;
+use crate::puzzle::Puzzle;
+
+/// An ordered collection of puzzles, e.g. all puzzles of an `.sdm` file,
+/// optionally with a name and a level as in the puzzle packs of OpenSudoku.
+/// Batch tools solving, rating or deduplicating many puzzles work on it.
+#[derive(Clone,Debug,Default)]
+pub struct PuzzleCollection {
+    puzzles: Vec<Puzzle>,
+    name: Option<String>,
+    /// The difficulty of the puzzles as described by the author, e.g. `easy`
+    level: Option<String>,
+}
+
+impl PuzzleCollection {
+    /// The empty collection
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// This collection with the name
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// This collection with the level describing the difficulty of the puzzles
+    pub fn with_level(mut self, level: impl Into<String>) -> Self {
+        self.level = Some(level.into());
+        self
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn level(&self) -> Option<&str> {
+        self.level.as_deref()
+    }
+
+    /// Number of puzzles
+    pub fn len(&self) -> usize {
+        self.puzzles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.puzzles.is_empty()
+    }
+
+    /// The puzzle at the index or `None` past the end
+    pub fn get(&self, index: usize) -> Option<&Puzzle> {
+        self.puzzles.get(index)
+    }
+
+    /// Add the puzzle, e.g. a `Board`, after the others
+    pub fn push(&mut self, puzzle: impl Into<Puzzle>) {
+        self.puzzles.push(puzzle.into());
+    }
+
+    /// Iterate over the puzzles in order
+    pub fn iter(&self) -> std::slice::Iter<'_, Puzzle> {
+        self.puzzles.iter()
+    }
+
+    /// Iterate over the boards of the puzzles in order
+    pub fn boards(&self) -> impl Iterator<Item = &Board> + '_ {
+        self.puzzles.iter().map(Puzzle::board)
+    }
+
+    /// The first puzzle satisfying the predicate
+    pub fn find(&self, predicate: impl Fn(&Puzzle) -> bool) -> Option<&Puzzle> {
+        self.puzzles.iter().find(|puzzle| predicate(puzzle))
+    }
+
+    /// The collection of the puzzles satisfying the predicate, in order and with the name and the level of this one
+    pub fn filter(&self, mut predicate: impl FnMut(&Puzzle) -> bool) -> Self {
+        let puzzles = self.puzzles.iter().filter(|puzzle| predicate(puzzle)).cloned().collect();
+        Self { puzzles, name: self.name.clone(), level: self.level.clone() }
+    }
+
+    /// The puzzles of the difficulty, regardless of case, e.g. `hard`
+    pub fn by_difficulty(&self, difficulty: &str) -> Self {
+        self.filter(|puzzle| puzzle.difficulty().is_some_and(|other| other.eq_ignore_ascii_case(difficulty)))
+    }
+
+    /// The puzzles of the author, regardless of case
+    pub fn by_author(&self, author: &str) -> Self {
+        self.filter(|puzzle| puzzle.author().is_some_and(|other| other.eq_ignore_ascii_case(author)))
+    }
+
+    /// The collection without the puzzles equivalent to an earlier one, i.e. with the same `Puzzle::fingerprint`
+    pub fn deduplicated(&self) -> Self {
+        let mut seen = HashSet::new();
+        self.filter(|puzzle| seen.insert(puzzle.fingerprint()))
+    }
+
+    /// Read the collection of the file in the format of its extension: `.sdm`, `.csv`,
+    /// `.json` or `.opensudoku` (also `.xml`). Fails with ``Error::IoError`` if the file
+    /// is unreadable, and with ``Error::ParseError`` for other extensions or malformed content.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let format = Format::of(path)?;
+        let content = fs::read_to_string(path).map_err(|error| Error::IoError { path: path.display().to_string(), reason: error.to_string() })?;
+        match format {
+            Format::Sdm => Self::from_sdm(&content),
+            Format::Csv => Self::from_csv(&content),
+            Format::Json => Self::from_json(&content),
+            Format::OpenSudoku => Self::from_opensudoku(&content),
+        }
+    }
+
+    /// Write the collection to the file in the format of its extension like `load`.
+    /// Only the JSON and the OpenSudoku formats keep more than the boards.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let content = match Format::of(path)? {
+            Format::Sdm => self.to_sdm(),
+            Format::Csv => self.to_csv(),
+            Format::Json => self.to_json(),
+            Format::OpenSudoku => self.to_opensudoku(),
+        };
+        fs::write(path, content).map_err(|error| Error::IoError { path: path.display().to_string(), reason: error.to_string() })
+    }
+}
+
+/// The file formats of collections which `load` and `save` choose by the extension
+enum Format {
+    Sdm,
+    Csv,
+    Json,
+    OpenSudoku,
+}
+
+impl Format {
+    fn of(path: &Path) -> Result<Self, Error> {
+        let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or_default().to_ascii_lowercase();
+        match extension.as_str() {
+            "sdm" => Ok(Format::Sdm),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            "opensudoku" | "xml" => Ok(Format::OpenSudoku),
+            _ => Err(Error::ParseError {
+                input: path.display().to_string(),
+                reason: "unknown file extension, expected .sdm, .csv, .json or .opensudoku".to_string(),
+            }),
+        }
+    }
+}
+
+impl Index<usize> for PuzzleCollection {
+    type Output = Puzzle;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.puzzles[index]
+    }
+}
+
+impl FromIterator<Puzzle> for PuzzleCollection {
+    fn from_iter<I: IntoIterator<Item = Puzzle>>(iter: I) -> Self {
+        Self { puzzles: iter.into_iter().collect(), ..Self::default() }
+    }
+}
+
+impl FromIterator<Board> for PuzzleCollection {
+    fn from_iter<I: IntoIterator<Item = Board>>(iter: I) -> Self {
+        iter.into_iter().map(Puzzle::new).collect()
+    }
+}
+
+impl IntoIterator for PuzzleCollection {
+    type Item = Puzzle;
+    type IntoIter = std::vec::IntoIter<Puzzle>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.puzzles.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a PuzzleCollection {
+    type Item = &'a Puzzle;
+    type IntoIter = std::slice::Iter<'a, Puzzle>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.puzzles.iter()
+    }
+}
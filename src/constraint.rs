@@ -0,0 +1,225 @@
+//! Rules a board must satisfy, as pluggable `Constraint`s.
+//!
+//! A constraint checks the assigned values of a board and prunes the candidates
+//! of its unassigned cells. The classic rules are the `Columns`, `Rows` and
+//! `Blocks` constraints. The rules of the variants in `variant` are constraints
+//! as well, and other crates can implement their own and add them to a `Sudoku`.
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::board::{Board, Slice, Value};
+use crate::candidate_set::CandidateSet;
+use crate::coord::Coord;
+use crate::error::Error;
+use crate::strategies::house_name;
+use crate::topology::{BLOCK_OF, COLUMN_OF, HOUSES, ROW_OF};
+
+/// A rule on the values of a board
+pub trait Constraint: fmt::Debug + Send + Sync {
+    /// Check that the assigned values of the board obey the rule.
+    /// Unassigned cells never violate it.
+    fn check(&self, board: &Board) -> Result<(), Error>;
+
+    /// Remove the values the rule forbids, given the assigned values of the
+    /// board, from the candidates of the unassigned cells
+    fn prune(&self, board: &Board, candidates: &mut [CandidateSet; Board::COUNT_VALUES]);
+
+    /// Like `check`, but may skip the parts of the rule not involving the cell.
+    /// By default the whole board is checked.
+    fn check_cell(&self, board: &Board, _cell_id: usize) -> Result<(), Error> {
+        self.check(board)
+    }
+
+    /// All violations of the rule by the assigned values of the board, unlike `check`
+    /// which stops at the first one. By default this is the error of `check`.
+    fn violations(&self, board: &Board) -> Vec<Error> {
+        self.check(board).err().into_iter().collect()
+    }
+}
+
+/// The classic rules: columns, rows and blocks must hold distinct values, checked in this order
+pub fn classic() -> Vec<Arc<dyn Constraint>> {
+    vec![Arc::new(Columns), Arc::new(Rows), Arc::new(Blocks)]
+}
+
+/// Every row holds distinct values
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub struct Rows;
+
+/// Every column holds distinct values
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub struct Columns;
+
+/// Every 3×3 block holds distinct values
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub struct Blocks;
+
+impl Constraint for Rows {
+    fn check(&self, board: &Board) -> Result<(), Error> {
+        check_houses(board, 0..9)
+    }
+
+    fn prune(&self, board: &Board, candidates: &mut [CandidateSet; Board::COUNT_VALUES]) {
+        prune_houses(board, 0..9, candidates);
+    }
+
+    fn violations(&self, board: &Board) -> Vec<Error> {
+        house_violations(board, 0..9)
+    }
+
+    fn check_cell(&self, board: &Board, cell_id: usize) -> Result<(), Error> {
+        check_houses(board, [ROW_OF[cell_id]])
+    }
+}
+
+impl Constraint for Columns {
+    fn check(&self, board: &Board) -> Result<(), Error> {
+        check_houses(board, 9..18)
+    }
+
+    fn prune(&self, board: &Board, candidates: &mut [CandidateSet; Board::COUNT_VALUES]) {
+        prune_houses(board, 9..18, candidates);
+    }
+
+    fn violations(&self, board: &Board) -> Vec<Error> {
+        house_violations(board, 9..18)
+    }
+
+    fn check_cell(&self, board: &Board, cell_id: usize) -> Result<(), Error> {
+        check_houses(board, [9 + COLUMN_OF[cell_id]])
+    }
+}
+
+impl Constraint for Blocks {
+    fn check(&self, board: &Board) -> Result<(), Error> {
+        check_houses(board, 18..27)
+    }
+
+    fn prune(&self, board: &Board, candidates: &mut [CandidateSet; Board::COUNT_VALUES]) {
+        prune_houses(board, 18..27, candidates);
+    }
+
+    fn violations(&self, board: &Board) -> Vec<Error> {
+        house_violations(board, 18..27)
+    }
+
+    fn check_cell(&self, board: &Board, cell_id: usize) -> Result<(), Error> {
+        check_houses(board, [18 + BLOCK_OF[cell_id]])
+    }
+}
+
+/// Check the houses given their identifiers from 0 to 26 (see `topology::HOUSES`)
+fn check_houses(board: &Board, house_ids: impl IntoIterator<Item = usize>) -> Result<(), Error> {
+    house_ids.into_iter().try_for_each(|house_id| check_distinct(board, house_name(house_id), &HOUSES[house_id]))
+}
+
+/// A ``Error::ConflictError`` for every value repeated in one of the houses, given their identifiers from 0 to 26
+fn house_violations(board: &Board, house_ids: impl IntoIterator<Item = usize>) -> Vec<Error> {
+    house_ids.into_iter().flat_map(|house_id| repeated_values(board, &house_name(house_id), &HOUSES[house_id])).collect()
+}
+
+/// Prune the candidates by the houses given their identifiers from 0 to 26
+fn prune_houses(board: &Board, house_ids: impl IntoIterator<Item = usize>, candidates: &mut [CandidateSet; Board::COUNT_VALUES]) {
+    house_ids.into_iter().for_each(|house_id| prune_distinct(board, &HOUSES[house_id], candidates));
+}
+
+/// Up to 9 cells which must hold distinct values, e.g. a diagonal or an irregular region
+#[derive(Clone,Debug,PartialEq,Eq,Hash)]
+pub struct House {
+    name: String,
+    cells: Vec<usize>,
+}
+
+impl House {
+    /// The house of the cells described by `name`, e.g. `main diagonal`.
+    /// Panics if there are more than 9 cells or a cell ID is not below 81.
+    pub fn new(name: impl Into<String>, cells: Vec<usize>) -> Self {
+        assert!(cells.len() <= 9, "a house has at most 9 cells");
+        assert!(cells.iter().all(|cell_id| *cell_id < Board::COUNT_VALUES), "cell ID outside of the board");
+        Self { name: name.into(), cells }
+    }
+
+    /// The description of the house
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The IDs of the cells of the house
+    pub fn cells(&self) -> &[usize] {
+        &self.cells
+    }
+}
+
+impl Constraint for House {
+    fn check(&self, board: &Board) -> Result<(), Error> {
+        check_distinct(board, self.name.clone(), &self.cells)
+    }
+
+    fn prune(&self, board: &Board, candidates: &mut [CandidateSet; Board::COUNT_VALUES]) {
+        prune_distinct(board, &self.cells, candidates);
+    }
+
+    fn violations(&self, board: &Board) -> Vec<Error> {
+        repeated_values(board, &self.name, &self.cells)
+    }
+
+    fn check_cell(&self, board: &Board, cell_id: usize) -> Result<(), Error> {
+        match self.cells.contains(&cell_id) {
+            true => self.check(board),
+            false => Ok(()),
+        }
+    }
+}
+
+/// Fail with ``Error::ConstraintError`` if the cells repeat a value
+fn check_distinct(board: &Board, region: String, cells: &[usize]) -> Result<(), Error> {
+    let mut slice = Slice::default();
+    for (i, cell_id) in cells.iter().enumerate() {
+        slice.set(i, board[*cell_id]);
+    }
+    match slice.has_unique_sudoku_values() {
+        true => Ok(()),
+        false => Err(Error::ConstraintError { region, slice }),
+    }
+}
+
+/// A ``Error::ConflictError`` with the coordinates of the cells for every value the cells repeat
+fn repeated_values(board: &Board, region: &str, cells: &[usize]) -> Vec<Error> {
+    let mut errors = vec![];
+    for value in 1..=9 {
+        let repeated: Vec<Coord> = cells.iter().filter(|cell_id| board[**cell_id].value() == value).map(|cell_id| Coord::from_cell_id(*cell_id)).collect();
+        if repeated.len() > 1 {
+            errors.push(Error::ConflictError { region: region.to_string(), value, cells: repeated });
+        }
+    }
+    errors
+}
+
+/// Remove the values of the cells from the candidates of the others
+fn prune_distinct(board: &Board, cells: &[usize], candidates: &mut [CandidateSet; Board::COUNT_VALUES]) {
+    let used: CandidateSet = cells.iter().map(|cell_id| board[*cell_id].value()).collect();
+    for cell_id in cells.iter() {
+        candidates[*cell_id] &= !used;
+    }
+}
+
+/// Check the constraints on the values of a search and prune its candidates (bit `v`
+/// stands for value `v`) like a prune hook of `Search`. Returns `false` on a violation.
+pub(crate) fn prune_search(constraints: &[Arc<dyn Constraint>], board: &Board, cells: &[Value], candidates: &mut [u32]) -> bool {
+    let board = board.with_values(cells.try_into().unwrap());
+    if constraints.iter().any(|constraint| constraint.check(&board).is_err()) {
+        return false;
+    }
+    let mut sets = [CandidateSet::empty(); Board::COUNT_VALUES];
+    for (set, bits) in sets.iter_mut().zip(candidates.iter()) {
+        *set = CandidateSet::from_bits(*bits as u16);
+    }
+    for constraint in constraints.iter() {
+        constraint.prune(&board, &mut sets);
+    }
+    for (bits, set) in candidates.iter_mut().zip(sets) {
+        *bits &= set.bits() as u32;
+    }
+    true
+}
@@ -0,0 +1,79 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::board::Board;
+use crate::error::Error;
+
+/// Position of a cell given its zero-based row and column
+#[derive(Clone,Copy,Debug,PartialEq,Eq,PartialOrd,Ord,Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Coord {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Coord {
+    pub fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// The position of the cell with the given ID
+    pub fn from_cell_id(cell_id: usize) -> Self {
+        Self { row: cell_id / Board::COUNT_COLUMNS, col: cell_id % Board::COUNT_COLUMNS }
+    }
+
+    /// The cell ID of this position
+    pub fn cell_id(&self) -> usize {
+        self.row * Board::COUNT_COLUMNS + self.col
+    }
+}
+
+impl fmt::Display for Coord {
+    /// Standard notation with one-based numbers, e.g. `r4c5`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "r{}c{}", self.row + 1, self.col + 1)
+    }
+}
+
+impl FromStr for Coord {
+    type Err = Error;
+
+    /// Parse the standard notation `r4c5` (case-insensitive, one-based)
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let error = |reason: &str| Error::ParseError { input: s.to_string(), reason: reason.to_string() };
+
+        let lower = s.trim().to_ascii_lowercase();
+        let (row, col) = lower.strip_prefix('r')
+            .and_then(|rest| rest.split_once('c'))
+            .ok_or_else(|| error("expected a cell like r4c5"))?;
+        let row: usize = row.parse().map_err(|_| error("expected a row number"))?;
+        let col: usize = col.parse().map_err(|_| error("expected a column number"))?;
+        if !(1..=Board::COUNT_ROWS).contains(&row) || !(1..=Board::COUNT_COLUMNS).contains(&col) {
+            return Err(error("expected row and column numbers from 1 to 9"));
+        }
+
+        Ok(Self { row: row - 1, col: col - 1 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        for cell_id in 0..Board::COUNT_VALUES {
+            let cell = Coord::from_cell_id(cell_id);
+            assert_eq!(cell.to_string().parse::<Coord>().unwrap(), cell);
+            assert_eq!(cell.cell_id(), cell_id);
+        }
+        assert_eq!(" R4C5 ".parse::<Coord>().unwrap(), Coord::new(3, 4));
+    }
+
+    #[test]
+    fn parse_rejects_cells_outside_of_the_board() {
+        for notation in ["r0c1", "r1c0", "r10c1", "r1c10", "4c5", "r4", "rxc5"] {
+            assert!(notation.parse::<Coord>().is_err(), "{notation}");
+        }
+    }
+}
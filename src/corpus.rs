@@ -0,0 +1,124 @@
+//! A small built-in corpus of example puzzles of every `Difficulty`, for tests,
+//! benchmarks and games which need content without external files.
+//! It is only compiled with the `examples` feature.
+//!
+//! The grades were checked with `LogicSolver`: easy puzzles need singles only,
+//! medium ones locked candidates or subsets, hard ones more advanced techniques
+//! and the logic solver gets stuck on expert ones. All of them have a unique solution.
+//! The pathological puzzles are solver-killers: one is built against brute force,
+//! two have no solution, though proving it takes seconds for one of them, one
+//! has 435 solutions and the empty board has countless.
+
+use crate::board::Board;
+use crate::collection::PuzzleCollection;
+use crate::difficulty::Difficulty;
+use crate::puzzle::Puzzle;
+
+/// A puzzle of the corpus with what is known about it
+struct Example {
+    cells: &'static str,
+    title: Option<&'static str>,
+    author: Option<&'static str>,
+    source: Option<&'static str>,
+}
+
+const fn example(cells: &'static str) -> Example {
+    Example { cells, title: None, author: None, source: None }
+}
+
+const fn from_source(cells: &'static str, source: &'static str) -> Example {
+    Example { cells, title: None, author: None, source: Some(source) }
+}
+
+const EASY: [Example; 7] = [
+    from_source("53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79", "Wikipedia"),
+    example("...26.7.168..7..9.19...45..82.1...4...46.29...5...3.28..93...74.4..5..367.3.18..."),
+    from_source("..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..", "Project Euler"),
+    from_source("2...8.3...6..7..84.3.5..2.9...1.54.8.........4.27.6...3.1..7.4.72..4..6...4.1...3", "Project Euler"),
+    from_source("......9.7...42.18....7.5.261..9.4....5.....4....5.7..992.1.8....34.59...5.7......", "Project Euler"),
+    example(".2.81.74.7....31...9...28.5..9.4..874..2.8..316..3.2..3.27...6...56....8.76.51.9."),
+    example(".6234.75.1....56..57.....4.....948..4.......6..583.....3.....91..64....7.59.8326."),
+];
+
+const MEDIUM: [Example; 6] = [
+    example("1..92....524.1...........7..5...81.2.........4.27...9..6...........3.945....71..6"),
+    example("..19....39..7..16..3...5..7.5......9..43.26..2......7.6..1...3..42..7..65....68.."),
+    example("1.....3.8.7.4..............2.3.1...........958.........5.6...7.....8.2...4......."),
+    from_source("4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......", "top95"),
+    example("85...24..72......9..4.........1.7..23.5...9...4...........8..7..17..........36.4."),
+    example(".....3.17.15..9..8.6.......1....7.....9...2.....5....4.......2.5..6..34.34.2....."),
+];
+
+const HARD: [Example; 4] = [
+    example(".43.8.25.6.............1.949....4.7....6.8....1.2....382.5.............5.34.9.71."),
+    example("48.3............71.2.......7.5....6....2..8.............1.76...3.....4......5...."),
+    example("3.6.7...........518.........1.4.5...7.....6.....2......2.....4.....8.3.....5....."),
+    example("..5...987.4..5...1..7......2...48....9.1.....6..2.....3..6..2.......9.7.......5.."),
+];
+
+const EXPERT: [Example; 5] = [
+    Example {
+        cells: "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..",
+        title: Some("World's hardest Sudoku"),
+        author: Some("Arto Inkala"),
+        source: None,
+    },
+    Example {
+        cells: "1.......2.9.4...5...6...7...5.9.3.......7.......85..4.7.....6...3...9.8...2.....1",
+        title: Some("Easter Monster"),
+        author: None,
+        source: None,
+    },
+    example(".8......1..7..4.2.6..3..7....2..9...1...6...8.3.4.......17..6...9...8..5.......4."),
+    example("..53.....8......2..7..1.5..4....53...1..7...6..32...8..6.5....9..4....3......97.."),
+    example("12..4......5.69.1...9...5.........7.7...52.9..3......2.9.6...5.4..9..8.1..3...9.4"),
+];
+
+const PATHOLOGICAL: [Example; 5] = [
+    from_source("..............3.85..1.2.......5.7.....4...1...9.......5......73..2.1........4...9", "Wikipedia"),
+    example(".....5.8....6.1.43..........1.5........1.6...3.......553.....61........4........."),
+    example("12345678........9................................................................"),
+    example("..9..........1.6...8.4..7.2..5.9..3.4...7.....1...8.9..6.......3.......4.2.....5."),
+    example("................................................................................."),
+];
+
+/// The example puzzles of the difficulty, whose level is the name of the difficulty
+pub fn by_difficulty(difficulty: Difficulty) -> PuzzleCollection {
+    let examples: &[Example] = match difficulty {
+        Difficulty::Easy => &EASY,
+        Difficulty::Medium => &MEDIUM,
+        Difficulty::Hard => &HARD,
+        Difficulty::Expert => &EXPERT,
+        Difficulty::Pathological => &PATHOLOGICAL,
+    };
+    let mut collection = PuzzleCollection::new().with_name(format!("{difficulty} examples")).with_level(difficulty.name());
+    for example in examples {
+        collection.push(puzzle(example, difficulty));
+    }
+    collection
+}
+
+/// All example puzzles from the easiest to the pathological ones
+pub fn all() -> PuzzleCollection {
+    let mut collection = PuzzleCollection::new().with_name("examples");
+    for puzzle in Difficulty::ALL.into_iter().flat_map(by_difficulty) {
+        collection.push(puzzle);
+    }
+    collection
+}
+
+fn puzzle(example: &Example, difficulty: Difficulty) -> Puzzle {
+    // ASSUME: every example is a well-formed line of 81 cells
+    let board: Board = example.cells.parse().expect("malformed example puzzle");
+    let mut puzzle = Puzzle::new(board).with_difficulty(difficulty.name());
+    if let Some(title) = example.title {
+        puzzle = puzzle.with_title(title);
+    }
+    if let Some(author) = example.author {
+        puzzle = puzzle.with_author(author);
+    }
+    if let Some(source) = example.source {
+        puzzle = puzzle.with_source(source);
+    }
+    puzzle
+}
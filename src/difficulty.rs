@@ -0,0 +1,86 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::board::Board;
+use crate::error::Error;
+use crate::rating::Rating;
+use crate::solver::LogicSolver;
+use crate::strategies::{Technique, TechniqueSet};
+
+/// How hard a puzzle is for a human solver, graded by the techniques it needs
+#[derive(Clone,Copy,Debug,PartialEq,Eq,PartialOrd,Ord,Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Difficulty {
+    /// Naked and hidden singles suffice
+    Easy,
+    /// Locked candidates and naked or hidden subsets are needed as well
+    Medium,
+    /// Fish, wings, coloring and the other techniques of `strategies` are needed
+    Hard,
+    /// The techniques of `strategies` get stuck, so guessing is needed
+    Expert,
+    /// Puzzles built to defeat solvers: brute force takes very long, or there
+    /// is no solution or more than one
+    Pathological,
+}
+
+impl Difficulty {
+    /// All grades from the easiest to the hardest
+    pub const ALL: [Difficulty; 5] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard, Difficulty::Expert, Difficulty::Pathological];
+
+    /// Lowercase name of the grade, e.g. `hard`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+            Difficulty::Expert => "expert",
+            Difficulty::Pathological => "pathological",
+        }
+    }
+
+    /// The grade of a classic puzzle by the hardest technique it needs (see `Rating`),
+    /// `Expert` if the techniques do not solve it and `Pathological` without a unique solution
+    pub fn of(board: &Board) -> Self {
+        Rating::of(board).difficulty
+    }
+
+    /// The grade of puzzles whose hardest technique is `technique`
+    pub fn of_technique(technique: Technique) -> Self {
+        match technique {
+            Technique::NakedSingle | Technique::HiddenSingle => Difficulty::Easy,
+            Technique::PointingCandidates | Technique::BoxLineReduction
+                | Technique::NakedPair | Technique::HiddenPair | Technique::NakedTriple
+                | Technique::HiddenTriple | Technique::NakedQuad | Technique::HiddenQuad => Difficulty::Medium,
+            _ => Difficulty::Hard,
+        }
+    }
+
+    /// The techniques a human solver needs at most for puzzles of this grade.
+    /// Expert and pathological puzzles need guessing beyond all of them.
+    pub fn techniques(&self) -> TechniqueSet {
+        Technique::ALL.into_iter().filter(|technique| Difficulty::of_technique(*technique) <= *self).collect()
+    }
+
+    /// A logic solver with the techniques of this grade, which may rely on a unique solution
+    pub(crate) fn solver(&self) -> LogicSolver {
+        LogicSolver { assume_unique: true, techniques: self.techniques() }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = Error;
+
+    /// Parse the name of a grade regardless of case, e.g. `Hard`
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Difficulty::ALL.into_iter()
+            .find(|difficulty| difficulty.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| Error::ParseError { input: s.to_string(), reason: "expected easy, medium, hard, expert or pathological".to_string() })
+    }
+}
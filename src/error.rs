@@ -0,0 +1,78 @@
+use std::error;
+use std::fmt;
+use std::ops::Range;
+
+use crate::board::{Slice, Value};
+use crate::coord::Coord;
+
+/// The set of errors that can occur in this application
+#[derive(Debug)]
+pub enum Error {
+    IdError{ admissible: Range<usize>, actual: usize },
+    ValueError{ value: Value, expected: String },
+    ConstraintError{ region: String, slice: Slice },
+    Unsolvable,
+    ParseError{ input: String, reason: String },
+    GivenError{ cell: Coord },
+    /// A value occurs more than once among cells which must hold distinct values,
+    /// e.g. in a house of a board which is not 9×9 or in cells a knight's move apart
+    ConflictError{ region: String, value: Value, cells: Vec<Coord> },
+    /// The blocks of a board have no cells or the board has too many values
+    SizeError{ box_rows: usize, box_cols: usize },
+    /// The regions or other shapes of a variant are malformed
+    LayoutError{ reason: String },
+    /// The values of some cells violate a rule of a variant relating them,
+    /// e.g. consecutive values in orthogonally adjacent cells
+    RuleError{ rule: String, cells: Vec<(Coord, Value)> },
+    /// A file cannot be read or written
+    IoError{ path: String, reason: String },
+    /// No puzzle of the requested kind was found within the attempts of a generator
+    GenerateError{ reason: String },
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::ConstraintError { region, slice } => {
+                write!(f, "expected numbers 1..9 in {region} but got values {slice}")
+            },
+            Error::ValueError { value, expected } => {
+                write!(f, "expected {expected} as value but got {value}")
+            },
+            Error::IdError { admissible, actual } => {
+                write!(f, "expected valid ID in range {}..{} but got {}", admissible.start, admissible.end, actual)
+            },
+            Error::Unsolvable => {
+                write!(f, "no assignment of the unassigned cells satisfies all Sudoku constraints")
+            },
+            Error::ParseError { input, reason } => {
+                write!(f, "cannot parse {input:?}: {reason}")
+            },
+            Error::GivenError { cell } => {
+                write!(f, "cell {cell} holds a given of the puzzle which cannot be changed")
+            },
+            Error::ConflictError { region, value, cells } => {
+                let cells = cells.iter().map(|cell| cell.to_string()).collect::<Vec<String>>().join(", ");
+                write!(f, "expected unique values in {region} but got {value} in cells {cells}")
+            },
+            Error::SizeError { box_rows, box_cols } => {
+                write!(f, "expected blocks with 1 to {} cells but got {box_rows}×{box_cols} blocks", crate::sized::MAX_SIZE)
+            },
+            Error::LayoutError { reason } => {
+                write!(f, "invalid layout: {reason}")
+            },
+            Error::RuleError { rule, cells } => {
+                let cells = cells.iter().map(|(cell, value)| format!("{cell} = {value}")).collect::<Vec<String>>().join(", ");
+                write!(f, "expected {rule} but got {cells}")
+            },
+            Error::IoError { path, reason } => {
+                write!(f, "cannot access {path}: {reason}")
+            },
+            Error::GenerateError { reason } => {
+                write!(f, "cannot generate a puzzle: {reason}")
+            },
+        }
+    }
+}
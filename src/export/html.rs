@@ -0,0 +1,217 @@
+//! A self-contained HTML snippet of a board for blogs and newsletters: a table
+//! with a short style sheet, which needs neither scripts nor images.
+//!
+//! Givens are bold and entries blue. Optionally unassigned cells show their
+//! candidates in a 3×3 sub-grid. With the rules of a `Variant`, the thick borders
+//! follow its regions, the windows are shaded and the cells of diagonals,
+//! thermometers and arrows are marked: a line across diagonal cells, a large dot
+//! in the bulb and small dots along a thermometer, a ring in the circle and
+//! tiny dots along the shaft of an arrow.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::board::{Board, Value};
+use crate::candidate_set::CandidateSet;
+use crate::export::{caption, escape};
+use crate::puzzle::Puzzle;
+use crate::sudoku::Sudoku;
+use crate::variant::Variant;
+
+/// What to show
+#[derive(Clone,Debug,Default)]
+pub struct HtmlOptions {
+    /// Show the candidates of unassigned cells: their pencil marks, or if they
+    /// have none the values the rules allow (see `Sudoku::legal_values`)
+    pub candidates: bool,
+    /// IDs of the cells shown with a highlighted background
+    pub highlighted: Vec<usize>,
+    /// The rules whose regions and decorations are shown, the classic ones if `None`
+    pub variant: Option<Variant>,
+}
+
+/// The style sheet of the snippet, applying to tables of the class `sudoku` only
+const STYLE: &str = "<style>
+table.sudoku { border-collapse: collapse; border: 3px solid #000; font-family: sans-serif; }
+table.sudoku td { width: 2.4em; height: 2.4em; padding: 0; border: 1px solid #999; text-align: center; vertical-align: middle; font-size: 1.4em; }
+table.sudoku caption { padding: 0.3em; font-weight: bold; }
+table.sudoku td.given { font-weight: bold; }
+table.sudoku td.entry { color: #1a5fb4; }
+table.sudoku table.candidates { width: 100%; border-collapse: collapse; }
+table.sudoku table.candidates td { width: 33%; height: auto; border: none; font-size: 0.4em; line-height: 1.3; color: #505050; }
+</style>";
+
+/// Background of highlighted cells
+const HIGHLIGHT_COLOR: &str = "#fff3a0";
+/// Background of the cells of the windows of Hypersudoku
+const WINDOW_COLOR: &str = "#e0e0e0";
+/// Color of the marks of diagonals
+const DIAGONAL_COLOR: &str = "#808080";
+/// Color of the marks of thermometers and arrows
+const DECORATION_COLOR: &str = "#b0b0b0";
+
+/// Render the board as a style sheet followed by a table
+pub fn to_html(board: &Board, options: &HtmlOptions) -> String {
+    render(board, options.variant.as_ref().unwrap_or(&Variant::classic()), None, options)
+}
+
+/// Render the board of the puzzle like `to_html` by the rules of the puzzle
+/// (unless `options.variant` is set), with a caption above naming its title,
+/// its author, its difficulty, its source and its date as far as they are known
+pub fn puzzle_to_html(puzzle: &Puzzle, options: &HtmlOptions) -> String {
+    render(puzzle.board(), options.variant.as_ref().unwrap_or(puzzle.variant()), caption(puzzle), options)
+}
+
+/// Render the board like `to_html` and write the snippet to the file
+pub fn write_html(board: &Board, options: &HtmlOptions, path: impl AsRef<Path>) -> io::Result<()> {
+    fs::write(path, to_html(board, options))
+}
+
+fn render(board: &Board, variant: &Variant, caption: Option<String>, options: &HtmlOptions) -> String {
+    let region = |cell_id: usize| if variant.is_latin_square() { 0 } else { variant.regions().region_of(cell_id) };
+    let mut sudoku = Sudoku::default().with_variant(variant.clone());
+    sudoku.init_board(board);
+
+    let mut out = vec![STYLE.to_string(), r#"<table class="sudoku">"#.to_string()];
+    if let Some(caption) = caption {
+        out.push(format!("<caption>{}</caption>", escape(&caption)));
+    }
+    for row_id in 0..Board::COUNT_ROWS {
+        out.push("<tr>".to_string());
+        for column_id in 0..Board::COUNT_COLUMNS {
+            let cell_id = row_id * Board::COUNT_COLUMNS + column_id;
+            let mut style = vec![];
+            if column_id < 8 && region(cell_id) != region(cell_id + 1) {
+                style.push("border-right: 3px solid #000".to_string());
+            }
+            if row_id < 8 && region(cell_id) != region(cell_id + 9) {
+                style.push("border-bottom: 3px solid #000".to_string());
+            }
+            if let Some(background) = background(variant, cell_id, options.highlighted.contains(&cell_id)) {
+                style.push(format!("background: {background}"));
+            }
+            let style = match style.is_empty() {
+                true => String::new(),
+                false => format!(r#" style="{}""#, style.join("; ")),
+            };
+
+            let content = match board[cell_id].value() {
+                0 if options.candidates => {
+                    let candidates = match board.notes(cell_id).is_empty() {
+                        true => sudoku.legal_values(cell_id),
+                        false => board.notes(cell_id),
+                    };
+                    format!("<td{style}>{}</td>", candidates_table(candidates))
+                },
+                0 => format!("<td{style}></td>"),
+                value => {
+                    let class = if board.is_given(cell_id) { "given" } else { "entry" };
+                    format!(r#"<td class="{class}"{style}>{}</td>"#, symbol(value))
+                },
+            };
+            out.push(content);
+        }
+        out.push("</tr>".to_string());
+    }
+    out.push("</table>\n".to_string());
+    out.join("\n")
+}
+
+/// The CSS background of the cell: the marks of the decorations on top of the
+/// highlight or the shading of a window, `None` for a plain cell
+fn background(variant: &Variant, cell_id: usize, highlighted: bool) -> Option<String> {
+    let (row, col) = (cell_id / 9, cell_id % 9);
+    let mut layers = vec![];
+    let disc = |percent: usize| format!("radial-gradient(circle, {DECORATION_COLOR} {percent}%, transparent {}%)", percent + 2);
+    for thermometer in variant.thermometers().iter().filter(|thermometer| thermometer.cells().contains(&cell_id)) {
+        layers.push(disc(if thermometer.bulb() == cell_id { 45 } else { 25 }));
+    }
+    for arrow in variant.arrows().iter().filter(|arrow| arrow.cells().contains(&cell_id)) {
+        layers.push(match arrow.circle() == cell_id {
+            true => format!("radial-gradient(circle, transparent 40%, {DECORATION_COLOR} 42%, {DECORATION_COLOR} 46%, transparent 48%)"),
+            false => disc(8),
+        });
+    }
+    if variant.has_diagonals() {
+        let line = |direction: &str| format!("linear-gradient(to {direction}, transparent 48%, {DIAGONAL_COLOR} 50%, transparent 52%)");
+        if row == col {
+            layers.push(line("bottom right"));
+        }
+        if row + col == 8 {
+            layers.push(line("bottom left"));
+        }
+    }
+    match (highlighted, variant.is_shaded(row, col)) {
+        (true, _) => layers.push(HIGHLIGHT_COLOR.to_string()),
+        (false, true) => layers.push(WINDOW_COLOR.to_string()),
+        (false, false) => {},
+    }
+    (!layers.is_empty()).then(|| layers.join(", "))
+}
+
+/// The candidates at the positions of a phone keypad in a table of 3×3 small cells
+fn candidates_table(candidates: CandidateSet) -> String {
+    let rows: String = (0..3)
+        .map(|line| {
+            let cells: String = (1..=3)
+                .map(|position| {
+                    let value = (3 * line + position) as Value;
+                    match candidates.contains(value) {
+                        true => format!("<td>{value}</td>"),
+                        false => "<td>&nbsp;</td>".to_string(),
+                    }
+                })
+                .collect();
+            format!("<tr>{cells}</tr>")
+        })
+        .collect();
+    format!(r#"<table class="candidates">{rows}</table>"#)
+}
+
+/// The value as text, `?` for invalid values
+fn symbol(value: Value) -> String {
+    match value {
+        1..=9 => value.to_string(),
+        _ => "?".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "..75......6..2..4....3.65.22......8...8.3.1...9......79.32.7....7..9..5......89..";
+
+    #[test]
+    fn givens_and_entries() {
+        let mut board: Board = PUZZLE.parse().unwrap();
+        board.try_set(0, 1).unwrap();
+        let html = to_html(&board, &HtmlOptions::default());
+        assert!(html.starts_with("<style>"));
+        assert_eq!(html.matches("<tr>").count(), 9);
+        assert_eq!(html.matches(r#"class="given""#).count(), board.givens().len());
+        assert!(html.contains(r#"<td class="entry">1</td>"#));
+        assert_eq!(html.matches("border-right: 3px").count(), 18);
+    }
+
+    #[test]
+    fn candidates() {
+        let mut board: Board = PUZZLE.parse().unwrap();
+        board.add_note(1, 4);
+        let html = to_html(&board, &HtmlOptions { candidates: true, ..HtmlOptions::default() });
+        assert_eq!(html.matches(r#"<table class="candidates">"#).count(), Board::COUNT_VALUES - board.givens().len());
+        assert!(html.contains(r#"<table class="candidates"><tr><td>&nbsp;</td><td>&nbsp;</td><td>&nbsp;</td></tr><tr><td>4</td><td>&nbsp;</td>"#));
+        // NOTE: r1c1 sees 2, 5, 6, 7 and 9, so 1, 3, 4 and 8 remain
+        assert!(html.contains(r#"<table class="candidates"><tr><td>1</td><td>&nbsp;</td><td>3</td></tr><tr><td>4</td><td>&nbsp;</td><td>&nbsp;</td></tr><tr><td>&nbsp;</td><td>8</td><td>&nbsp;</td></tr></table>"#));
+    }
+
+    #[test]
+    fn variants_and_captions() {
+        let puzzle = Puzzle::new(Board::default()).with_title("Daily").with_author("Jane").with_difficulty("hard").with_variant(Variant::classic().with_windows());
+        let html = puzzle_to_html(&puzzle, &HtmlOptions { highlighted: vec![0], ..HtmlOptions::default() });
+        assert!(html.contains("<caption>Daily by Jane (hard)</caption>"));
+        assert_eq!(html.matches(WINDOW_COLOR).count(), 36);
+        assert!(html.contains(&format!(r#"<td style="background: {HIGHLIGHT_COLOR}"></td>"#)));
+    }
+}
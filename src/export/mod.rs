@@ -0,0 +1,26 @@
+//! Renderings of boards for print and the web, beyond the terminal output of `Display`.
+//! The renderings of a `Puzzle` follow its rules and show a caption with its title.
+
+pub mod html;
+pub mod svg;
+
+use crate::puzzle::Puzzle;
+
+/// The line describing the puzzle, e.g. `Daily Sudoku by Jane Doe (hard, 2024-05-01)`,
+/// or `None` if nothing is known about it
+fn caption(puzzle: &Puzzle) -> Option<String> {
+    let mut caption = puzzle.title().unwrap_or_default().to_string();
+    if let Some(author) = puzzle.author() {
+        caption = format!("{caption} by {author}").trim_start().to_string();
+    }
+    let details: Vec<&str> = [puzzle.difficulty(), puzzle.source(), puzzle.date()].into_iter().flatten().collect();
+    if !details.is_empty() {
+        caption = format!("{caption} ({})", details.join(", ")).trim_start().to_string();
+    }
+    (!caption.is_empty()).then_some(caption)
+}
+
+/// Replace the characters with a meaning in XML and HTML by their entities
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -0,0 +1,232 @@
+//! Scalable vector graphics of a board for print-quality output.
+//!
+//! Givens are drawn in bold black and entries in regular blue. Optionally the
+//! pencil marks of unassigned cells are drawn small at the positions of a phone
+//! keypad, and cells are highlighted. With the rules of a `Variant`, the region
+//! borders follow its regions and its diagonals, windows, thermometers and arrows
+//! are drawn as well, e.g. those of a `Puzzle` rendered by `puzzle_to_svg`.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::board::{Board, Value};
+use crate::export::{caption, escape};
+use crate::puzzle::Puzzle;
+use crate::variant::Variant;
+
+/// What to draw and how large
+#[derive(Clone,Debug)]
+pub struct SvgOptions {
+    /// Width and height of a cell in pixels
+    pub cell_size: f64,
+    /// Draw the pencil marks of unassigned cells
+    pub notes: bool,
+    /// IDs of the cells drawn with a highlighted background
+    pub highlighted: Vec<usize>,
+    /// The rules whose regions and decorations are drawn, the classic ones if `None`
+    pub variant: Option<Variant>,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self { cell_size: 50.0, notes: false, highlighted: vec![], variant: None }
+    }
+}
+
+/// Background of highlighted cells
+const HIGHLIGHT_COLOR: &str = "#fff3a0";
+/// Background of the cells of the windows of Hypersudoku
+const WINDOW_COLOR: &str = "#e0e0e0";
+/// Color of thermometers and of the lines and circles of arrows
+const DECORATION_COLOR: &str = "#b0b0b0";
+/// Color of entries, i.e. values which are not givens
+const ENTRY_COLOR: &str = "#1a5fb4";
+
+/// Render the board as a standalone SVG document
+pub fn to_svg(board: &Board, options: &SvgOptions) -> String {
+    render(board, options.variant.as_ref().unwrap_or(&Variant::classic()), None, options)
+}
+
+/// Render the board of the puzzle like `to_svg` by the rules of the puzzle
+/// (unless `options.variant` is set), with a caption below naming its title,
+/// its author, its difficulty, its source and its date as far as they are known
+pub fn puzzle_to_svg(puzzle: &Puzzle, options: &SvgOptions) -> String {
+    render(puzzle.board(), options.variant.as_ref().unwrap_or(puzzle.variant()), caption(puzzle), options)
+}
+
+/// Render the board like `to_svg` and write the document to the file
+pub fn write_svg(board: &Board, options: &SvgOptions, path: impl AsRef<Path>) -> io::Result<()> {
+    fs::write(path, to_svg(board, options))
+}
+
+fn render(board: &Board, variant: &Variant, caption: Option<String>, options: &SvgOptions) -> String {
+    let size = options.cell_size;
+    let margin = size / 10.0;
+    let extent = px(Board::COUNT_COLUMNS as f64 * size + 2.0 * margin);
+    let height = if caption.is_some() { px(extent + 0.6 * size) } else { extent };
+    // NOTE: the top-left corner of a cell and its center
+    let corner = |cell_id: usize| (margin + (cell_id % 9) as f64 * size, margin + (cell_id / 9) as f64 * size);
+    let center = |cell_id: usize| (corner(cell_id).0 + size / 2.0, corner(cell_id).1 + size / 2.0);
+
+    let mut out = vec![];
+    out.push(format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{extent}" height="{height}" viewBox="0 0 {extent} {height}">"#));
+    if let Some(caption) = &caption {
+        out.push(format!("<title>{}</title>", escape(caption)));
+    }
+    out.push(format!(r#"<rect width="{extent}" height="{height}" fill="white"/>"#));
+
+    for cell_id in 0..Board::COUNT_VALUES {
+        let fill = match (options.highlighted.contains(&cell_id), variant.is_shaded(cell_id / 9, cell_id % 9)) {
+            (true, _) => HIGHLIGHT_COLOR,
+            (false, true) => WINDOW_COLOR,
+            (false, false) => continue,
+        };
+        let (x, y) = corner(cell_id);
+        out.push(format!(r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{fill}"/>"#, px(x), px(y), px(size), px(size)));
+    }
+
+    if variant.has_diagonals() {
+        let (start, end) = (margin, margin + 9.0 * size);
+        out.push(format!(r##"<g stroke="#808080" stroke-width="{}" stroke-dasharray="{} {}">"##, px(size / 25.0), px(size / 10.0), px(size / 10.0)));
+        out.push(format!(r#"<line x1="{}" y1="{}" x2="{}" y2="{}"/>"#, px(start), px(start), px(end), px(end)));
+        out.push(format!(r#"<line x1="{}" y1="{}" x2="{}" y2="{}"/>"#, px(end), px(start), px(start), px(end)));
+        out.push("</g>".to_string());
+    }
+
+    for thermometer in variant.thermometers() {
+        let (x, y) = center(thermometer.bulb());
+        out.push(format!(r#"<circle cx="{}" cy="{}" r="{}" fill="{DECORATION_COLOR}"/>"#, px(x), px(y), px(0.4 * size)));
+        out.push(format!(r#"<polyline points="{}" fill="none" stroke="{DECORATION_COLOR}" stroke-width="{}" stroke-linecap="round" stroke-linejoin="round"/>"#,
+            points(thermometer.cells().iter().map(|cell_id| center(*cell_id))), px(0.3 * size)));
+    }
+
+    for arrow in variant.arrows() {
+        let (x, y) = center(arrow.circle());
+        let stroke = format!(r#"stroke="{DECORATION_COLOR}" stroke-width="{}" stroke-linecap="round" stroke-linejoin="round""#, px(size / 20.0));
+        out.push(format!(r#"<polyline points="{}" fill="none" {stroke}/>"#, points(arrow.cells().iter().map(|cell_id| center(*cell_id)))));
+        // NOTE: the head points along the last step of the shaft
+        let (tip, before) = (center(arrow.cells()[arrow.cells().len() - 1]), center(arrow.cells()[arrow.cells().len() - 2]));
+        let (dx, dy) = (tip.0 - before.0, tip.1 - before.1);
+        let length = (dx * dx + dy * dy).sqrt();
+        let (ux, uy) = (dx / length * 0.25 * size, dy / length * 0.25 * size);
+        let head = [(tip.0 - ux - uy, tip.1 - uy + ux), tip, (tip.0 - ux + uy, tip.1 - uy - ux)];
+        out.push(format!(r#"<polyline points="{}" fill="none" {stroke}/>"#, points(head.into_iter())));
+        out.push(format!(r#"<circle cx="{}" cy="{}" r="{}" fill="white" {stroke}/>"#, px(x), px(y), px(0.4 * size)));
+    }
+
+    // NOTE: thin lines between all cells, then thick lines between cells of different regions
+    out.push(format!(r##"<g stroke="#808080" stroke-width="{}">"##, px(size / 50.0)));
+    for i in 1..Board::COUNT_ROWS {
+        let offset = margin + i as f64 * size;
+        out.push(format!(r#"<line x1="{}" y1="{}" x2="{}" y2="{}"/>"#, px(offset), px(margin), px(offset), px(margin + 9.0 * size)));
+        out.push(format!(r#"<line x1="{}" y1="{}" x2="{}" y2="{}"/>"#, px(margin), px(offset), px(margin + 9.0 * size), px(offset)));
+    }
+    out.push("</g>".to_string());
+    let region = |cell_id: usize| if variant.is_latin_square() { 0 } else { variant.regions().region_of(cell_id) };
+    out.push(format!(r#"<g stroke="black" stroke-width="{}" stroke-linecap="square">"#, px(size / 16.0)));
+    for cell_id in 0..Board::COUNT_VALUES {
+        let (x, y) = corner(cell_id);
+        if cell_id % 9 < 8 && region(cell_id) != region(cell_id + 1) {
+            out.push(format!(r#"<line x1="{}" y1="{}" x2="{}" y2="{}"/>"#, px(x + size), px(y), px(x + size), px(y + size)));
+        }
+        if cell_id / 9 < 8 && region(cell_id) != region(cell_id + 9) {
+            out.push(format!(r#"<line x1="{}" y1="{}" x2="{}" y2="{}"/>"#, px(x), px(y + size), px(x + size), px(y + size)));
+        }
+    }
+    out.push("</g>".to_string());
+    out.push(format!(r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="black" stroke-width="{}"/>"#,
+        px(margin), px(margin), px(9.0 * size), px(9.0 * size), px(size / 12.0)));
+
+    out.push(r#"<g font-family="sans-serif" text-anchor="middle" dominant-baseline="central">"#.to_string());
+    for cell_id in 0..Board::COUNT_VALUES {
+        let (x, y) = center(cell_id);
+        match board[cell_id].value() {
+            0 if options.notes => {
+                for value in board.notes(cell_id).iter() {
+                    // NOTE: value `v` lies at position `v` of a phone keypad
+                    let (dx, dy) = (((value - 1) % 3) as f64 - 1.0, ((value - 1) / 3) as f64 - 1.0);
+                    out.push(format!(r##"<text x="{}" y="{}" font-size="{}" fill="#505050">{value}</text>"##,
+                        px(x + dx * size * 0.3), px(y + dy * size * 0.3), px(size * 0.25)));
+                }
+            },
+            0 => {},
+            value => {
+                let style = match board.is_given(cell_id) {
+                    true => r#"font-weight="bold" fill="black""#.to_string(),
+                    false => format!(r#"fill="{ENTRY_COLOR}""#),
+                };
+                out.push(format!(r#"<text x="{}" y="{}" font-size="{}" {style}>{}</text>"#, px(x), px(y), px(size * 0.6), symbol(value)));
+            },
+        }
+    }
+    out.push("</g>".to_string());
+    if let Some(caption) = &caption {
+        out.push(format!(r#"<text x="{}" y="{}" font-family="sans-serif" font-size="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+            px(extent / 2.0), px(extent + 0.25 * size), px(0.35 * size), escape(caption)));
+    }
+    out.push("</svg>\n".to_string());
+    out.join("\n")
+}
+
+/// The value as text, `?` for invalid values
+fn symbol(value: Value) -> String {
+    match value {
+        1..=9 => value.to_string(),
+        _ => "?".to_string(),
+    }
+}
+
+/// The coordinate rounded to a tenth of a pixel, which keeps the document short
+fn px(coordinate: f64) -> f64 {
+    (coordinate * 10.0).round() / 10.0
+}
+
+/// The points as the value of a `points` attribute
+fn points(points: impl Iterator<Item = (f64, f64)>) -> String {
+    points.map(|(x, y)| format!("{},{}", px(x), px(y))).collect::<Vec<String>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "..75......6..2..4....3.65.22......8...8.3.1...9......79.32.7....7..9..5......89..";
+
+    #[test]
+    fn givens_and_entries() {
+        let mut board: Board = PUZZLE.parse().unwrap();
+        board.try_set(0, 1).unwrap();
+        let svg = to_svg(&board, &SvgOptions::default());
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="460" height="460""#));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches(r#"font-weight="bold""#).count(), board.givens().len());
+        assert_eq!(svg.matches(ENTRY_COLOR).count(), 1);
+        assert!(svg.contains(r##"<text x="30" y="30" font-size="30" fill="#1a5fb4">1</text>"##));
+    }
+
+    #[test]
+    fn notes_and_highlights() {
+        let mut board = Board::default();
+        board.add_note(0, 1);
+        board.add_note(0, 9);
+        let plain = to_svg(&board, &SvgOptions::default());
+        assert!(!plain.contains("#505050"));
+        let options = SvgOptions { notes: true, highlighted: vec![40], ..SvgOptions::default() };
+        let svg = to_svg(&board, &options);
+        assert_eq!(svg.matches(r##"fill="#505050""##).count(), 2);
+        assert!(svg.contains(&format!(r#"<rect x="205" y="205" width="50" height="50" fill="{HIGHLIGHT_COLOR}"/>"#)));
+    }
+
+    #[test]
+    fn variants_and_captions() {
+        let variant = Variant::classic().with_windows().with_diagonals();
+        let puzzle = Puzzle::new(PUZZLE.parse().unwrap()).with_title("Daily <X>").with_variant(variant);
+        let svg = puzzle_to_svg(&puzzle, &SvgOptions::default());
+        assert_eq!(svg.matches(WINDOW_COLOR).count(), 36);
+        assert!(svg.contains("stroke-dasharray"));
+        assert!(svg.contains("<title>Daily &lt;X&gt;</title>"));
+        assert!(svg.contains(r#"height="490""#));
+        assert!(!to_svg(puzzle.board(), &SvgOptions::default()).contains("<title>"));
+    }
+}
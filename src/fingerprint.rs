@@ -0,0 +1,243 @@
+//! Stable IDs of puzzles which agree for equivalent puzzles.
+//!
+//! Relabeling the values, permuting the rows within a band, the bands, the
+//! columns within a stack and the stacks, and transposing the board turn a
+//! classic puzzle into an equivalent one with the same solving path. The
+//! canonical form is the smallest of all 3,359,232 arrangements of the givens,
+//! compared as lines of digits after numbering the values in order of first
+//! occurrence. The fingerprint hashes it with FNV-1a, which is the same on
+//! every platform and in every release.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::board::{Board, Value};
+use crate::error::Error;
+use crate::variant::Variant;
+
+/// The hash identifying a puzzle, written as 16 hexadecimal digits
+#[derive(Clone,Copy,Debug,PartialEq,Eq,PartialOrd,Ord,Hash)]
+pub struct Fingerprint(u64);
+
+impl Fingerprint {
+    /// The fingerprint of the givens of the board, played by the rules of the variant.
+    /// Variants are only canonicalized if they are classic, since
+    /// their decorations and regions do not survive the transformations.
+    pub(crate) fn of(board: &Board, variant: &Variant) -> Self {
+        let mut givens = [0; Board::COUNT_VALUES];
+        for cell_id in board.givens() {
+            givens[cell_id] = board[cell_id].value();
+        }
+
+        let mut hash = Fnv::new();
+        if variant.is_classic() {
+            hash.write(&canonical_values(&givens));
+        } else {
+            hash.write(&givens);
+            hash.write(describe(variant).as_bytes());
+        }
+        Self(hash.0)
+    }
+
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl FromStr for Fingerprint {
+    type Err = Error;
+
+    /// Parse the 16 hexadecimal digits of the fingerprint
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let error = || Error::ParseError { input: s.to_string(), reason: "expected 16 hexadecimal digits".to_string() };
+
+        if s.len() != 16 {
+            return Err(error());
+        }
+        u64::from_str_radix(s, 16).map(Self).map_err(|_| error())
+    }
+}
+
+/// The values of the smallest arrangement of the values of a classic board
+pub(crate) fn canonical_values(values: &[Value; Board::COUNT_VALUES]) -> [Value; Board::COUNT_VALUES] {
+    let transposed: [Value; Board::COUNT_VALUES] = std::array::from_fn(|cell_id| values[cell_id % 9 * 9 + cell_id / 9]);
+
+    let mut search = Search { grid: values, columns: [0; 9], best: [Value::MAX; Board::COUNT_VALUES] };
+    for grid in [values, &transposed] {
+        search.grid = grid;
+        for columns in column_orders() {
+            search.columns = columns;
+            search.extend(0, [false; 9], 0, [0; 10], 1);
+        }
+    }
+    search.best
+}
+
+/// Search for the smallest arrangement given an order of the columns, choosing the rows
+/// one by one and abandoning every choice which makes the arrangement larger than the best one
+struct Search<'a> {
+    grid: &'a [Value; Board::COUNT_VALUES],
+    columns: [usize; 9],
+    /// The smallest arrangement so far. Its values after a row which was improved
+    /// are `Value::MAX` until the search below that row arrives there.
+    best: [Value; Board::COUNT_VALUES],
+}
+
+impl Search<'_> {
+    /// Choose the row at `depth`, given the rows chosen so far, the band of the last one
+    /// and the labels of the values which occurred
+    fn extend(&mut self, depth: usize, used: [bool; 9], band: usize, labels: [Value; 10], next_label: Value) {
+        if depth == Board::COUNT_ROWS {
+            return;
+        }
+        // NOTE: the rows of a band stay together, so a new band starts every third row
+        let candidates: Vec<usize> = match depth % 3 {
+            0 => (0..9).filter(|row| !used[*row]).collect(),
+            _ => (3 * band..3 * band + 3).filter(|row| !used[*row]).collect(),
+        };
+
+        for row in candidates {
+            let (mut labels, mut next_label) = (labels, next_label);
+            let line: [Value; 9] = std::array::from_fn(|i| {
+                let value = self.grid[row * 9 + self.columns[i]] as usize;
+                if value != 0 && labels[value] == 0 {
+                    labels[value] = next_label;
+                    next_label += 1;
+                }
+                labels[value]
+            });
+            let best_line = &mut self.best[depth * 9..depth * 9 + 9];
+            if line[..] > best_line[..] {
+                continue;
+            }
+            if line[..] < best_line[..] {
+                best_line.copy_from_slice(&line);
+                self.best[depth * 9 + 9..].fill(Value::MAX);
+            }
+            let mut used = used;
+            used[row] = true;
+            self.extend(depth + 1, used, row / 3, labels, next_label);
+        }
+    }
+}
+
+/// The 1296 orders of the columns which keep the columns of a stack together
+fn column_orders() -> Vec<[usize; 9]> {
+    const PERMUTATIONS: [[usize; 3]; 6] = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+
+    let mut orders = Vec::with_capacity(6 * 6 * 6 * 6);
+    for stacks in PERMUTATIONS {
+        for first in PERMUTATIONS {
+            for second in PERMUTATIONS {
+                for third in PERMUTATIONS {
+                    let within = [first, second, third];
+                    orders.push(std::array::from_fn(|i| 3 * stacks[i / 3] + within[i / 3][i % 3]));
+                }
+            }
+        }
+    }
+    orders
+}
+
+/// The rules of the variant as text, distinguishing all variants
+fn describe(variant: &Variant) -> String {
+    let regions: String = (0..Board::COUNT_VALUES).map(|cell_id| char::from(b'1' + variant.regions().region_of(cell_id) as u8)).collect();
+    let flags = [
+        variant.is_latin_square(), variant.has_diagonals(), variant.has_windows(),
+        variant.has_anti_knight(), variant.has_anti_king(), variant.has_non_consecutive(),
+    ];
+    let flags: String = flags.iter().map(|flag| if *flag { '1' } else { '0' }).collect();
+    let thermometers: Vec<String> = variant.thermometers().iter().map(|thermometer| thermometer.to_string()).collect();
+    let arrows: Vec<String> = variant.arrows().iter().map(|arrow| arrow.to_string()).collect();
+    format!("{regions};{flags};{};{}", thermometers.join(","), arrows.join(","))
+}
+
+/// The 64-bit FNV-1a hash
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "..75......6..2..4....3.65.22......8...8.3.1...9......79.32.7....7..9..5......89..";
+
+    /// The board with the values at the cells given by `source` for every cell and the labels swapped
+    fn transformed(source: impl Fn(usize) -> usize, swap: (Value, Value)) -> Board {
+        let board: Board = PUZZLE.parse().unwrap();
+        let values: [Value; Board::COUNT_VALUES] = std::array::from_fn(|cell_id| match board[source(cell_id)].value() {
+            value if value == swap.0 => swap.1,
+            value if value == swap.1 => swap.0,
+            value => value,
+        });
+        Board::from_flattened_values(&values)
+    }
+
+    fn fingerprint(board: &Board) -> Fingerprint {
+        Fingerprint::of(board, &Variant::classic())
+    }
+
+    #[test]
+    fn equivalent_puzzles_agree() {
+        let expected = fingerprint(&PUZZLE.parse().unwrap());
+        let swapped_rows = |cell_id: usize| match cell_id / 9 {
+            0 => cell_id + 9,
+            1 => cell_id - 9,
+            _ => cell_id,
+        };
+        let transposed = |cell_id: usize| cell_id % 9 * 9 + cell_id / 9;
+        let swapped_stacks = |cell_id: usize| cell_id / 9 * 9 + (cell_id % 9 + 3) % 9;
+        assert_eq!(fingerprint(&transformed(swapped_rows, (1, 2))), expected);
+        assert_eq!(fingerprint(&transformed(transposed, (3, 9))), expected);
+        assert_eq!(fingerprint(&transformed(swapped_stacks, (1, 1))), expected);
+    }
+
+    #[test]
+    fn other_puzzles_differ() {
+        let expected = fingerprint(&PUZZLE.parse().unwrap());
+        let swapped_columns_of_stacks = |cell_id: usize| match cell_id % 9 {
+            2 => cell_id + 1,
+            3 => cell_id - 1,
+            _ => cell_id,
+        };
+        assert_ne!(fingerprint(&transformed(swapped_columns_of_stacks, (1, 1))), expected);
+        assert_ne!(fingerprint(&PUZZLE.replacen('7', ".", 1).parse().unwrap()), expected);
+        assert_ne!(Fingerprint::of(&PUZZLE.parse().unwrap(), &Variant::classic().with_diagonals()), expected);
+    }
+
+    #[test]
+    fn entries_are_ignored() {
+        let mut board: Board = PUZZLE.parse().unwrap();
+        let expected = fingerprint(&board);
+        board.try_set(0, 1).unwrap();
+        assert_eq!(fingerprint(&board), expected);
+    }
+
+    #[test]
+    fn text_round_trip() {
+        let fingerprint = fingerprint(&PUZZLE.parse().unwrap());
+        let text = fingerprint.to_string();
+        assert_eq!(text.len(), 16);
+        assert_eq!(text.parse::<Fingerprint>().unwrap(), fingerprint);
+        assert!(text[1..].parse::<Fingerprint>().is_err());
+        assert!("x".repeat(16).parse::<Fingerprint>().is_err());
+    }
+}
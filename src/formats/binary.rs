@@ -0,0 +1,227 @@
+//! A compact binary format for embedding puzzles in other programs or sending
+//! them over the network: a header of 2 bytes, the value of every cell in
+//! 4 bits, and optional sections for the givens and the pencil marks.
+//!
+//! | bytes | content |
+//! |-------|---------|
+//! | 1     | `MAGIC` |
+//! | 1     | flags: `HAS_ENTRIES` and `HAS_NOTES` |
+//! | 41    | 81 values in reading order, the first cell in the high 4 bits, 0 for unassigned |
+//! | 11    | with `HAS_ENTRIES`: bit `i` set if cell `i` is a given |
+//! | 92    | with `HAS_NOTES`: bit `9 * i + v - 1` set if `v` is a pencil mark of cell `i` |
+//!
+//! Bits count from the lowest bit of the first byte. Without `HAS_ENTRIES` all
+//! assigned cells are givens, so a puzzle takes 43 bytes.
+
+use crate::board::{Board, Cell, Value};
+use crate::collection::PuzzleCollection;
+use crate::error::Error;
+use crate::strategies::cell_name;
+
+/// First byte of every board
+const MAGIC: u8 = b'S';
+/// Flag of the section of the givens, written if some assigned cell is an entry
+const HAS_ENTRIES: u8 = 1;
+/// Flag of the section of the pencil marks, written if some cell has notes
+const HAS_NOTES: u8 = 2;
+
+/// Number of bytes of the values of the cells
+const COUNT_VALUE_BYTES: usize = Board::COUNT_VALUES.div_ceil(2);
+/// Number of bytes of the bits of the givens
+const COUNT_GIVEN_BYTES: usize = Board::COUNT_VALUES.div_ceil(8);
+/// Number of bytes of the bits of the pencil marks
+const COUNT_NOTE_BYTES: usize = (Board::COUNT_VALUES * 9).div_ceil(8);
+
+impl Board {
+    /// The board in the binary format: 43 bytes for a puzzle, more with entries or notes.
+    /// Invalid values above 9 are written as unassigned cells.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let has_entries = (0..Self::COUNT_VALUES).any(|cell_id| self[cell_id].value() != 0 && !self.is_given(cell_id));
+        let has_notes = (0..Self::COUNT_VALUES).any(|cell_id| !self.notes(cell_id).is_empty());
+        let mut flags = 0;
+        if has_entries {
+            flags |= HAS_ENTRIES;
+        }
+        if has_notes {
+            flags |= HAS_NOTES;
+        }
+
+        let mut bytes = vec![MAGIC, flags];
+        // NOTE: the low 4 bits of the last byte follow the last cell and stay 0
+        let values: Vec<Value> = (0..Self::COUNT_VALUES)
+            .map(|cell_id| match self[cell_id].value() {
+                value @ 1..=9 => value,
+                _ => 0,
+            })
+            .chain([0])
+            .collect();
+        bytes.extend(values.chunks(2).map(|pair| pair[0] << 4 | pair[1]));
+        if has_entries {
+            let mut givens = [0; COUNT_GIVEN_BYTES];
+            for cell_id in self.givens() {
+                set_bit(&mut givens, cell_id);
+            }
+            bytes.extend(givens);
+        }
+        if has_notes {
+            let mut notes = [0; COUNT_NOTE_BYTES];
+            for cell_id in 0..Self::COUNT_VALUES {
+                for value in self.notes(cell_id).iter() {
+                    set_bit(&mut notes, 9 * cell_id + value as usize - 1);
+                }
+            }
+            bytes.extend(notes);
+        }
+        bytes
+    }
+
+    /// Read a board written by `Board::to_bytes`. Fails with ``Error::ParseError``
+    /// if the bytes do not start with a board, e.g. for a wrong first byte, unknown
+    /// flags, missing bytes, values above 9 or givens of unassigned cells.
+    /// Bytes after the board are ignored.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Self::read_bytes(bytes).map(|(board, _)| board)
+    }
+
+    /// Read the board at the start of the bytes and return it with the number of its bytes
+    fn read_bytes(bytes: &[u8]) -> Result<(Self, usize), Error> {
+        let error = |reason: String| Error::ParseError { input: format!("{bytes:02x?}"), reason };
+
+        let (magic, flags) = match bytes {
+            [magic, flags, ..] => (*magic, *flags),
+            _ => return Err(error(format!("expected a header of 2 bytes but got {}", bytes.len()))),
+        };
+        if magic != MAGIC {
+            return Err(error(format!("expected the first byte {MAGIC:#04x} but got {magic:#04x}")));
+        }
+        if flags & !(HAS_ENTRIES | HAS_NOTES) != 0 {
+            return Err(error(format!("unknown flags {flags:#04x}")));
+        }
+        let count_givens = if flags & HAS_ENTRIES != 0 { COUNT_GIVEN_BYTES } else { 0 };
+        let count_notes = if flags & HAS_NOTES != 0 { COUNT_NOTE_BYTES } else { 0 };
+        let count_bytes = 2 + COUNT_VALUE_BYTES + count_givens + count_notes;
+        if bytes.len() < count_bytes {
+            return Err(error(format!("expected {count_bytes} bytes but got {}", bytes.len())));
+        }
+
+        let (values, rest) = bytes[2..].split_at(COUNT_VALUE_BYTES);
+        let (givens, rest) = rest.split_at(count_givens);
+        let notes = &rest[..count_notes];
+        let mut cells = [0; Self::COUNT_VALUES];
+        for (cell_id, value) in cells.iter_mut().enumerate() {
+            *value = match cell_id % 2 {
+                0 => values[cell_id / 2] >> 4,
+                _ => values[cell_id / 2] & 0x0f,
+            };
+            if *value > 9 {
+                return Err(error(format!("unexpected value {value} of cell {}", cell_name(cell_id))));
+            }
+        }
+
+        // NOTE: without the section of the givens every assigned cell is a given
+        let mut given_values = cells;
+        if !givens.is_empty() {
+            for cell_id in 0..Self::COUNT_VALUES {
+                match (bit(givens, cell_id), cells[cell_id]) {
+                    (true, 0) => return Err(error(format!("given cell {} is unassigned", cell_name(cell_id)))),
+                    (true, _) => {},
+                    (false, _) => given_values[cell_id] = 0,
+                }
+            }
+        }
+        let mut board = Self::from_flattened_values(&given_values);
+        for cell_id in (0..Self::COUNT_VALUES).filter(|cell_id| given_values[*cell_id] != cells[*cell_id]) {
+            board[cell_id] = Cell::new(cells[cell_id]);
+        }
+        if !notes.is_empty() {
+            for cell_id in 0..Self::COUNT_VALUES {
+                for value in (1..=9).filter(|value: &Value| bit(notes, 9 * cell_id + *value as usize - 1)) {
+                    board.add_note(cell_id, value);
+                }
+            }
+        }
+        Ok((board, count_bytes))
+    }
+}
+
+impl PuzzleCollection {
+    /// The puzzles in the binary format of `Board::to_bytes`, one after the other.
+    /// The name and the level of the collection are left out.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.boards().flat_map(Board::to_bytes).collect()
+    }
+
+    /// Read the boards written by `PuzzleCollection::to_bytes`. Fails with the
+    /// ``Error::ParseError`` of the first malformed board, whose reason names its position.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut collection = Self::new();
+        let mut rest = bytes;
+        while !rest.is_empty() {
+            let (board, count_bytes) = Board::read_bytes(rest).map_err(|error| match error {
+                Error::ParseError { input, reason } => Error::ParseError { input, reason: format!("{reason} in puzzle {}", collection.len() + 1) },
+                error => error,
+            })?;
+            collection.push(board);
+            rest = &rest[count_bytes..];
+        }
+        Ok(collection)
+    }
+}
+
+/// Is bit `i` of the bytes set, counting from the lowest bit of the first byte?
+fn bit(bytes: &[u8], i: usize) -> bool {
+    bytes[i / 8] & 1 << (i % 8) != 0
+}
+
+fn set_bit(bytes: &mut [u8], i: usize) {
+    bytes[i / 8] |= 1 << (i % 8);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "..75......6..2..4....3.65.22......8...8.3.1...9......79.32.7....7..9..5......89..";
+
+    #[test]
+    fn puzzles_take_43_bytes() {
+        let board: Board = PUZZLE.parse().unwrap();
+        let bytes = board.to_bytes();
+        assert_eq!((bytes.len(), bytes[0], bytes[1], bytes[3]), (43, MAGIC, 0, 0x75));
+        let read = Board::from_bytes(&bytes).unwrap();
+        assert_eq!(read.values(), board.values());
+        assert_eq!(read.givens(), board.givens());
+    }
+
+    #[test]
+    fn entries_and_notes_round_trip() {
+        let mut board: Board = PUZZLE.parse().unwrap();
+        board.try_set(0, 1).unwrap();
+        board.add_note(1, 3);
+        board.add_note(1, 8);
+        let bytes = board.to_bytes();
+        assert_eq!((bytes.len(), bytes[1]), (43 + COUNT_GIVEN_BYTES + COUNT_NOTE_BYTES, HAS_ENTRIES | HAS_NOTES));
+        let read = Board::from_bytes(&bytes).unwrap();
+        assert_eq!(read.values(), board.values());
+        assert_eq!(read.givens(), board.givens());
+        assert_eq!(read.notes(1).iter().collect::<Vec<Value>>(), [3, 8]);
+    }
+
+    #[test]
+    fn malformed_bytes_fail() {
+        let bytes = PUZZLE.parse::<Board>().unwrap().to_bytes();
+        assert!(Board::from_bytes(&bytes[..42]).is_err());
+        assert!(Board::from_bytes(&[[b'T'].as_slice(), &bytes[1..]].concat()).is_err());
+        assert!(Board::from_bytes(&[&bytes[..2], &[0xa0], &bytes[3..]].concat()).is_err());
+        assert!(Board::from_bytes(&[&bytes[..1], &[4], &bytes[2..]].concat()).is_err());
+    }
+
+    #[test]
+    fn collection_round_trip() {
+        let collection: PuzzleCollection = [PUZZLE, &PUZZLE.replace('9', ".")].iter().map(|line| line.parse::<Board>().unwrap()).collect();
+        let parsed = PuzzleCollection::from_bytes(&collection.to_bytes()).unwrap();
+        assert_eq!(parsed.boards().map(Board::values).collect::<Vec<_>>(), collection.boards().map(Board::values).collect::<Vec<_>>());
+        let Err(Error::ParseError { reason, .. }) = PuzzleCollection::from_bytes(&collection.to_bytes()[..80]) else { panic!() };
+        assert!(reason.ends_with("in puzzle 2"), "{reason}");
+    }
+}
@@ -0,0 +1,146 @@
+//! Comma-separated values as exported by spreadsheets: a board is 9 rows of
+//! 9 fields, a collection one puzzle per row. Blanks are empty fields or `0`.
+
+use crate::board::{Board, Value};
+use crate::collection::PuzzleCollection;
+use crate::error::Error;
+
+impl Board {
+    /// Parse 9 rows of 9 comma-separated values. Fields may be quoted and surrounded
+    /// by whitespace. Blank lines are skipped. All assigned cells become givens.
+    pub fn from_csv(s: &str) -> Result<Self, Error> {
+        let error = |reason: String| Error::ParseError { input: s.to_string(), reason };
+
+        let mut values = vec![];
+        let mut count_rows = 0;
+        for (line_id, line) in s.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+            let row = parse_fields(line).map_err(|reason| error(format!("{reason} in line {}", line_id + 1)))?;
+            if row.len() != Self::COUNT_COLUMNS {
+                return Err(error(format!("expected {} fields in line {} but got {}", Self::COUNT_COLUMNS, line_id + 1, row.len())));
+            }
+            count_rows += 1;
+            values.extend(row);
+        }
+        match values.try_into() {
+            Ok(values) => Ok(Self::from_flattened_values(&values)),
+            Err(_) => Err(error(format!("expected {} rows but got {count_rows}", Self::COUNT_ROWS))),
+        }
+    }
+
+    /// The board as 9 rows of 9 comma-separated values with empty fields for unassigned cells
+    pub fn to_csv(&self) -> String {
+        self.rows().map(|(_, row)| csv_row(row.iter().map(|cell| cell.value())) + "\n").collect()
+    }
+}
+
+impl PuzzleCollection {
+    /// Parse one puzzle per row: either 81 comma-separated values or a first field in
+    /// the one-line format of 81 cells, whose other fields (e.g. a solution) are ignored.
+    /// A first row without any digit is a header and skipped, as are blank lines.
+    pub fn from_csv(s: &str) -> Result<Self, Error> {
+        let error = |reason: String| Error::ParseError { input: s.to_string(), reason };
+
+        let mut lines = s.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()).peekable();
+        lines.next_if(|(_, line)| !line.contains(|symbol: char| symbol.is_ascii_digit()));
+        lines
+            .map(|(line_id, line)| {
+                let first = line.split(',').next().map(unquote).unwrap_or_default();
+                let values = match first.chars().count() == Board::COUNT_VALUES {
+                    true => first.parse::<Board>().map(|board| board.values().to_vec()).map_err(|parse_error| match parse_error {
+                        Error::ParseError { reason, .. } => reason,
+                        parse_error => parse_error.to_string(),
+                    }),
+                    false => parse_fields(line),
+                };
+                let values: Vec<Value> = values.map_err(|reason| error(format!("{reason} in line {}", line_id + 1)))?;
+                match values.try_into() {
+                    Ok(values) => Ok(Board::from_flattened_values(&values)),
+                    Err(values) => Err(error(format!("expected {} fields in line {} but got {}", Board::COUNT_VALUES, line_id + 1, values.len()))),
+                }
+            })
+            .collect()
+    }
+
+    /// The puzzles as rows of 81 comma-separated values with empty fields for unassigned cells
+    pub fn to_csv(&self) -> String {
+        self.boards().map(|board| csv_row(board.values().into_iter()) + "\n").collect()
+    }
+}
+
+/// The values of the comma-separated fields of the line, 0 for empty fields
+fn parse_fields(line: &str) -> Result<Vec<Value>, String> {
+    line.split(',')
+        .map(unquote)
+        .map(|field| match field {
+            "" | "0" | "." => Ok(0),
+            _ => field.parse::<Value>().ok().filter(|value| (1..=9).contains(value)).ok_or_else(|| format!("unexpected field {field:?}")),
+        })
+        .collect()
+}
+
+/// The field without surrounding whitespace and quotes
+fn unquote(field: &str) -> &str {
+    let field = field.trim();
+    field.strip_prefix('"').and_then(|field| field.strip_suffix('"')).unwrap_or(field).trim()
+}
+
+/// The values as comma-separated fields, empty for unassigned cells
+fn csv_row(values: impl Iterator<Item = Value>) -> String {
+    values
+        .map(|value| match value {
+            1..=9 => value.to_string(),
+            _ => String::new(),
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "..75......6..2..4....3.65.22......8...8.3.1...9......79.32.7....7..9..5......89..";
+
+    #[test]
+    fn board_round_trip() {
+        let board: Board = PUZZLE.parse().unwrap();
+        let csv = board.to_csv();
+        assert_eq!(csv.lines().next(), Some(",,7,5,,,,,"));
+        assert_eq!(Board::from_csv(&csv).unwrap().values(), board.values());
+    }
+
+    #[test]
+    fn board_accepts_quotes_and_zeros() {
+        let rows: Vec<String> = PUZZLE.as_bytes().chunks(9)
+            .map(|row| row.iter().map(|symbol| format!(" \"{}\" ", if *symbol == b'.' { '0' } else { *symbol as char })).collect::<Vec<_>>().join(","))
+            .collect();
+        let csv = rows.join("\n\n");
+        assert_eq!(Board::from_csv(&csv).unwrap().values(), PUZZLE.parse::<Board>().unwrap().values());
+    }
+
+    #[test]
+    fn board_rejects_malformed_rows() {
+        let csv = PUZZLE.parse::<Board>().unwrap().to_csv();
+        assert!(Board::from_csv(&csv.replacen(",7,", ",x,", 1)).is_err());
+        assert!(Board::from_csv(&csv.replacen(",7,", ",7,,", 1)).is_err());
+        assert!(Board::from_csv(&csv[..csv.len() - 18]).is_err());
+    }
+
+    #[test]
+    fn collection_round_trip() {
+        let collection: PuzzleCollection = [PUZZLE, &PUZZLE.replace('9', ".")].iter().map(|line| line.parse::<Board>().unwrap()).collect();
+        let csv = collection.to_csv();
+        assert_eq!(csv.lines().count(), 2);
+        let parsed = PuzzleCollection::from_csv(&csv).unwrap();
+        assert_eq!(parsed.boards().map(Board::values).collect::<Vec<_>>(), collection.boards().map(Board::values).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn collection_skips_the_header_and_other_fields() {
+        let csv = format!("puzzle,solution\n\n{PUZZLE},{}\n", "1".repeat(81));
+        let parsed = PuzzleCollection::from_csv(&csv).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed.boards().next().unwrap().values(), PUZZLE.parse::<Board>().unwrap().values());
+        assert!(PuzzleCollection::from_csv(&format!("{},1\n", &PUZZLE[1..])).is_err());
+    }
+}
@@ -0,0 +1,295 @@
+//! The links of the puzzle editor f-puzzles, which SudokuPad opens as well:
+//! a JSON description of the puzzle compressed with lz-string, e.g.
+//! `https://www.f-puzzles.com/?load=N4IgzglgXgpiBcB...` or
+//! `https://sudokupad.app/fpuzzlesN4IgzglgXgpiBcB...`.
+//! The rules of a `Variant` map to the grid regions, the diagonals, the
+//! windows as extra regions, the chess and non-consecutive flags, the
+//! thermometers and the arrows of f-puzzles. A `Puzzle` keeps the title and the author.
+
+use crate::board::{Board, Cell};
+use crate::coord::Coord;
+use crate::error::Error;
+use crate::formats::lz_string;
+use crate::json::Json;
+use crate::puzzle::Puzzle;
+use crate::strategies::cell_name;
+use crate::sudoku::Sudoku;
+use crate::topology::BLOCK_OF;
+use crate::variant::{Arrow, Regions, Thermometer, Variant};
+
+/// Link to f-puzzles opening a puzzle given as compressed data
+const FPUZZLES_URL: &str = "https://www.f-puzzles.com/?load=";
+
+/// Members of the f-puzzles JSON which do not affect the rules, e.g. decorations
+const IGNORED_MEMBERS: [&str; 12] = [
+    "size", "grid", "title", "author", "ruleset", "solution", "highlightConflicts", "disabledlogic",
+    "truecandidatesoptions", "text", "line", "rectangle",
+];
+
+impl Sudoku {
+    /// Open the puzzle of an f-puzzles or SudokuPad link like `Puzzle::from_fpuzzles_url`
+    pub fn from_fpuzzles_url(url: &str) -> Result<Self, Error> {
+        Puzzle::from_fpuzzles_url(url).map(|puzzle| puzzle.to_sudoku())
+    }
+
+    /// The f-puzzles link of the game with the givens and the entries of the board
+    /// like `Puzzle::to_fpuzzles_url`. Constraints added with `with_constraint` are left out.
+    pub fn to_fpuzzles_url(&self) -> Result<String, Error> {
+        Puzzle::from(self).to_fpuzzles_url()
+    }
+}
+
+impl Puzzle {
+    /// Open the puzzle of an f-puzzles or SudokuPad link, or of the compressed data of
+    /// such a link, with its title and author. Cells with `given` values become givens,
+    /// others with values entries. Fails with ``Error::ParseError`` unless the puzzle
+    /// is a 9×9 grid whose rules are all expressible as a `Variant`, e.g. for a single
+    /// diagonal or a killer cage.
+    pub fn from_fpuzzles_url(url: &str) -> Result<Self, Error> {
+        let error = |reason: String| Error::ParseError { input: url.to_string(), reason };
+
+        let data = match (url.find("load="), url.rfind("fpuzzles")) {
+            (Some(start), _) => &url[start + "load=".len()..],
+            (None, Some(start)) => &url[start + "fpuzzles".len()..],
+            (None, None) => url,
+        };
+        let data = data.split('&').next().unwrap_or_default().trim().replace("%2B", "+").replace("%2F", "/").replace("%3D", "=");
+        let json = lz_string::decompress_from_base64(&data).and_then(|text| Json::parse(&text))
+            .map_err(|reason| error(format!("invalid puzzle data: {reason}")))?;
+
+        let unsupported = |member: &str| error(format!("f-puzzles constraint {member:?} is not supported"));
+        if json.get("size").and_then(Json::as_usize) != Some(Board::COUNT_ROWS) {
+            return Err(error("expected a puzzle of size 9".to_string()));
+        }
+        let mut variant = Variant::classic();
+        let rows = json.get("grid").and_then(Json::as_array).filter(|rows| rows.len() == Board::COUNT_ROWS)
+            .ok_or_else(|| error("expected a grid of 9 rows".to_string()))?;
+        let mut givens = [0; Board::COUNT_VALUES];
+        let mut entries = vec![];
+        let mut region_of = BLOCK_OF;
+        let mut has_regions = false;
+        for (row, cells) in rows.iter().enumerate() {
+            let cells = cells.as_array().filter(|cells| cells.len() == Board::COUNT_COLUMNS)
+                .ok_or_else(|| error(format!("expected 9 cells in row {}", row + 1)))?;
+            for (col, cell) in cells.iter().enumerate() {
+                let cell_id = row * Board::COUNT_COLUMNS + col;
+                if let Some(region) = cell.get("region").filter(|region| **region != Json::Null) {
+                    region_of[cell_id] = region.as_usize().ok_or_else(|| error(format!("invalid region of cell {}", cell_name(cell_id))))?;
+                    has_regions = true;
+                }
+                let value = match cell.get("value") {
+                    None | Some(Json::Null) => continue,
+                    Some(value) => value.as_usize().filter(|value| (1..=9).contains(value))
+                        .ok_or_else(|| error(format!("invalid value of cell {}", cell_name(cell_id))))?,
+                };
+                match cell.get("given").and_then(Json::as_bool).unwrap_or(false) {
+                    true => givens[cell_id] = value as u8,
+                    false => entries.push((cell_id, value as u8)),
+                }
+            }
+        }
+        if has_regions {
+            variant = variant.with_regions(Regions::new(region_of)?);
+        }
+
+        let Json::Object(members) = &json else { unreachable!("the size is a member") };
+        let diagonals = (json.get("diagonal+").is_some_and(Json::is_set), json.get("diagonal-").is_some_and(Json::is_set));
+        for (member, value) in members.iter().filter(|(member, value)| !IGNORED_MEMBERS.contains(&member.as_str()) && value.is_set()) {
+            variant = match member.as_str() {
+                "diagonal+" | "diagonal-" => variant,
+                "antiknight" => variant.with_anti_knight(),
+                "antiking" => variant.with_anti_king(),
+                "nonconsecutive" => variant.with_non_consecutive(),
+                "extraregion" if windows(value) => variant.with_windows(),
+                "thermometer" => {
+                    for line in lines(value).map_err(&error)? {
+                        variant = variant.with_thermometer(Thermometer::new(line)?);
+                    }
+                    variant
+                },
+                "arrow" => {
+                    for arrow in value.as_array().unwrap_or_default() {
+                        let circles = cells(arrow.get("cells").unwrap_or(&Json::Null)).map_err(&error)?;
+                        for line in lines(arrow).map_err(&error)? {
+                            if circles.len() != 1 || line.first() != circles.first() {
+                                return Err(error("expected arrows with one circle cell at the start of their line".to_string()));
+                            }
+                            variant = variant.with_arrow(Arrow::new(line)?);
+                        }
+                    }
+                    variant
+                },
+                _ => return Err(unsupported(member)),
+            };
+        }
+        variant = match diagonals {
+            (true, true) => variant.with_diagonals(),
+            (false, false) => variant,
+            _ => return Err(error("a single diagonal is not supported".to_string())),
+        };
+
+        let mut board = Board::from_flattened_values(&givens);
+        for (cell_id, value) in entries {
+            board[cell_id] = Cell::new(value);
+        }
+        let mut puzzle = Puzzle::new(board).with_variant(variant);
+        if let Some(title) = json.get("title").and_then(Json::as_str).filter(|title| !title.is_empty()) {
+            puzzle = puzzle.with_title(title);
+        }
+        if let Some(author) = json.get("author").and_then(Json::as_str).filter(|author| !author.is_empty()) {
+            puzzle = puzzle.with_author(author);
+        }
+        Ok(puzzle)
+    }
+
+    /// The f-puzzles link of the puzzle with its title, its author and the givens
+    /// and the entries of the board. Fails with ``Error::LayoutError`` for Latin squares,
+    /// which f-puzzles cannot express.
+    pub fn to_fpuzzles_url(&self) -> Result<String, Error> {
+        let variant = self.variant();
+        if variant.is_latin_square() {
+            return Err(Error::LayoutError { reason: "f-puzzles has no Latin squares".to_string() });
+        }
+        let board = self.board();
+        let rows = (0..Board::COUNT_ROWS)
+            .map(|row| Json::Array((0..Board::COUNT_COLUMNS).map(|col| {
+                let cell_id = row * Board::COUNT_COLUMNS + col;
+                let mut members = vec![];
+                if (1..=9).contains(&board[cell_id].value()) {
+                    members.push(("value".to_string(), Json::from(board[cell_id].value() as usize)));
+                    if board.is_given(cell_id) {
+                        members.push(("given".to_string(), Json::from(true)));
+                    }
+                }
+                if !variant.regions().is_classic() {
+                    members.push(("region".to_string(), Json::from(variant.regions().region_of(cell_id))));
+                }
+                Json::Object(members)
+            }).collect()))
+            .collect();
+
+        let mut members = vec![("size".to_string(), Json::from(Board::COUNT_ROWS)), ("grid".to_string(), Json::Array(rows))];
+        if let Some(title) = self.title() {
+            members.push(("title".to_string(), Json::from(title)));
+        }
+        if let Some(author) = self.author() {
+            members.push(("author".to_string(), Json::from(author)));
+        }
+        let mut flag = |member: &str, set: bool| {
+            if set {
+                members.push((member.to_string(), Json::from(true)));
+            }
+        };
+        flag("diagonal+", variant.has_diagonals());
+        flag("diagonal-", variant.has_diagonals());
+        flag("antiknight", variant.has_anti_knight());
+        flag("antiking", variant.has_anti_king());
+        flag("nonconsecutive", variant.has_non_consecutive());
+        if variant.has_windows() {
+            let windows = WINDOW_ORIGINS.iter()
+                .map(|(top, left)| Json::Object(vec![("cells".to_string(), cells_json((0..9).map(|i| (top + i / 3) * 9 + left + i % 3)))]))
+                .collect();
+            members.push(("extraregion".to_string(), Json::Array(windows)));
+        }
+        if !variant.thermometers().is_empty() {
+            let thermometers = variant.thermometers().iter()
+                .map(|thermometer| Json::Object(vec![("lines".to_string(), Json::Array(vec![cells_json(thermometer.cells().iter().copied())]))]))
+                .collect();
+            members.push(("thermometer".to_string(), Json::Array(thermometers)));
+        }
+        if !variant.arrows().is_empty() {
+            let arrows = variant.arrows().iter()
+                .map(|arrow| Json::Object(vec![
+                    ("lines".to_string(), Json::Array(vec![cells_json(arrow.cells().iter().copied())])),
+                    ("cells".to_string(), cells_json([arrow.circle()].into_iter())),
+                ]))
+                .collect();
+            members.push(("arrow".to_string(), Json::Array(arrows)));
+        }
+        Ok(format!("{FPUZZLES_URL}{}", lz_string::compress_to_base64(&Json::Object(members).to_string())))
+    }
+}
+
+/// Rows and columns of the top-left cells of the windows of Hypersudoku
+const WINDOW_ORIGINS: [(usize, usize); 4] = [(1, 1), (1, 5), (5, 1), (5, 5)];
+
+/// Are the extra regions exactly the windows of Hypersudoku?
+fn windows(extra_regions: &Json) -> bool {
+    let mut regions: Vec<Vec<usize>> = match extra_regions.as_array() {
+        Some(regions) => regions.iter().filter_map(|region| cells(region.get("cells")?).ok()).collect(),
+        None => return false,
+    };
+    regions.iter_mut().for_each(|cells| cells.sort_unstable());
+    regions.sort();
+    let expected: Vec<Vec<usize>> = WINDOW_ORIGINS.iter().map(|(top, left)| (0..9).map(|i| (top + i / 3) * 9 + left + i % 3).collect()).collect();
+    extra_regions.as_array().map(<[Json]>::len) == Some(regions.len()) && regions == expected
+}
+
+/// The cells of every line of a shape like `{"lines": [["R1C1", "R2C2"]]}`
+fn lines(shape: &Json) -> Result<Vec<Vec<usize>>, String> {
+    match shape {
+        Json::Array(shapes) => Ok(shapes.iter().map(lines).collect::<Result<Vec<_>, String>>()?.concat()),
+        _ => shape.get("lines").and_then(Json::as_array).ok_or("expected the lines of a shape")?.iter().map(cells).collect(),
+    }
+}
+
+/// The IDs of the cells of an array of names like `R1C1`
+fn cells(names: &Json) -> Result<Vec<usize>, String> {
+    names.as_array().ok_or("expected an array of cells")?.iter()
+        .map(|name| name.as_str().and_then(|name| name.parse::<Coord>().ok()).map(|coord| coord.cell_id()).ok_or_else(|| format!("invalid cell {name}")))
+        .collect()
+}
+
+/// The names of the cells like `R1C1` as an array
+fn cells_json(cell_ids: impl Iterator<Item = usize>) -> Json {
+    Json::Array(cell_ids.map(|cell_id| Json::from(cell_name(cell_id).to_uppercase().as_str())).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "..75......6..2..4....3.65.22......8...8.3.1...9......79.32.7....7..9..5......89..";
+
+    /// The compressed data of a link to an f-puzzles description
+    fn data_of(json: &str) -> String {
+        lz_string::compress_to_base64(json)
+    }
+
+    #[test]
+    fn link_round_trip() {
+        let variant = Variant::classic().with_windows().with_anti_knight().with_arrow(Arrow::new(vec![0, 1, 2]).unwrap());
+        let mut board: Board = PUZZLE.parse().unwrap();
+        board.try_set(0, 1).unwrap();
+        let puzzle = Puzzle::new(board).with_title("Daily").with_author("Jane").with_variant(variant);
+        let url = puzzle.to_fpuzzles_url().unwrap();
+        assert!(url.starts_with(FPUZZLES_URL));
+        let parsed = Puzzle::from_fpuzzles_url(&url).unwrap();
+        assert_eq!((parsed.title(), parsed.author()), (Some("Daily"), Some("Jane")));
+        assert_eq!(parsed.board().values(), puzzle.board().values());
+        assert_eq!(parsed.board().givens(), puzzle.board().givens());
+        let parsed_variant = parsed.variant();
+        assert!(parsed_variant.has_windows() && parsed_variant.has_anti_knight() && !parsed_variant.has_diagonals());
+        assert_eq!(parsed_variant.arrows()[0].cells(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn sudokupad_links_and_plain_data() {
+        let grid = ["[{},{},{},{},{},{},{},{},{}]"; 9].join(",");
+        let data = data_of(&format!("{{\"size\":9,\"grid\":[{grid}],\"diagonal+\":true,\"diagonal-\":true}}"));
+        for url in [format!("https://sudokupad.app/fpuzzles{data}"), data.clone(), format!("{FPUZZLES_URL}{}&x=1", data.replace('+', "%2B"))] {
+            assert!(Puzzle::from_fpuzzles_url(&url).unwrap().variant().has_diagonals(), "{url}");
+        }
+    }
+
+    #[test]
+    fn unsupported_rules_fail() {
+        let grid = ["[{},{},{},{},{},{},{},{},{}]"; 9].join(",");
+        for rules in ["\"killercage\":[{\"cells\":[\"R1C1\"]}]", "\"diagonal+\":true"] {
+            let data = data_of(&format!("{{\"size\":9,\"grid\":[{grid}],{rules}}}"));
+            assert!(matches!(Puzzle::from_fpuzzles_url(&data), Err(Error::ParseError { .. })), "{rules}");
+        }
+        assert!(Puzzle::from_fpuzzles_url(&data_of(&format!("{{\"size\":6,\"grid\":[{grid}]}}"))).is_err());
+        assert!(Puzzle::new(Board::default()).with_variant(Variant::classic().with_latin_square()).to_fpuzzles_url().is_err());
+    }
+}
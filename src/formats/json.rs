@@ -0,0 +1,281 @@
+//! A JSON format keeping everything a `Puzzle` knows, for exchanging puzzles
+//! with web applications. A puzzle is an object like
+//!
+//! ```text
+//! {"cells": "53..7....6..195...", "title": "Daily", "author": "Jane Doe",
+//!  "source": "Gazette", "date": "2024-05-01", "difficulty": "hard",
+//!  "variant": {"diagonals": true, "thermometers": ["r1c1 r1c2 r1c3"]}}
+//! ```
+//!
+//! with the 81 cells in the one-line format and only the information which is
+//! known. `givens` lists the IDs of the givens if some assigned cell is an entry.
+//! `variant` has the flags `latin_square`, `diagonals`, `windows`, `anti_knight`,
+//! `anti_king` and `non_consecutive`, the `regions` as 81 digits, and the cells
+//! of the `thermometers` and the `arrows`. A collection is an object with an
+//! optional `name` and `level` and its `puzzles` in an array.
+//!
+//! A solved puzzle may carry the `stats` of the search which solved it: the
+//! `nodes`, `backtracks`, `propagations` and `max_depth` and the `elapsed_ms`.
+//! They are written only, reading a puzzle ignores them.
+
+use crate::board::Board;
+use crate::collection::PuzzleCollection;
+use crate::error::Error;
+use crate::json::Json;
+use crate::puzzle::Puzzle;
+use crate::solver::SolveStats;
+use crate::variant::{Arrow, Regions, Thermometer, Variant};
+
+impl Puzzle {
+    /// Parse a puzzle in the JSON format. Fails with ``Error::ParseError`` for
+    /// malformed JSON, members of the wrong type or cells which `Board::from_str` rejects.
+    pub fn from_json(s: &str) -> Result<Self, Error> {
+        let json = Json::parse(s).map_err(|reason| Error::ParseError { input: s.to_string(), reason })?;
+        Self::from_json_value(&json).map_err(|reason| Error::ParseError { input: s.to_string(), reason })
+    }
+
+    /// The puzzle in the JSON format
+    pub fn to_json(&self) -> String {
+        self.to_json_value().to_string()
+    }
+
+    /// The puzzle in the JSON format with the statistics of the search as member `stats`
+    pub fn to_json_with_stats(&self, stats: &SolveStats) -> String {
+        let mut json = self.to_json_value();
+        if let Json::Object(members) = &mut json {
+            members.push(("stats".to_string(), stats_to_json(stats)));
+        }
+        json.to_string()
+    }
+
+    fn from_json_value(json: &Json) -> Result<Self, String> {
+        let text = |member: &str| match json.get(member) {
+            None | Some(Json::Null) => Ok(None),
+            Some(value) => value.as_str().map(Some).ok_or_else(|| format!("expected a string as {member}")),
+        };
+        let cells = text("cells")?.ok_or("expected the cells of the puzzle")?;
+        let mut board: Board = cells.parse().map_err(|error: Error| error.to_string())?;
+        if let Some(givens) = json.get("givens").filter(|givens| **givens != Json::Null) {
+            let givens = givens.as_array().ok_or("expected an array as givens")?.iter()
+                .map(|cell_id| cell_id.as_usize().filter(|cell_id| *cell_id < Board::COUNT_VALUES && board[*cell_id].value() != 0))
+                .collect::<Option<Vec<usize>>>()
+                .ok_or("expected the IDs of assigned cells as givens")?;
+            // NOTE: the cells which are no givens become entries
+            let values = board.values();
+            let mut given_values = [0; Board::COUNT_VALUES];
+            for cell_id in givens {
+                given_values[cell_id] = values[cell_id];
+            }
+            board = Board::from_flattened_values(&given_values).with_values(&values);
+        }
+
+        let mut puzzle = Puzzle::new(board);
+        if let Some(title) = text("title")? {
+            puzzle = puzzle.with_title(title);
+        }
+        if let Some(author) = text("author")? {
+            puzzle = puzzle.with_author(author);
+        }
+        if let Some(source) = text("source")? {
+            puzzle = puzzle.with_source(source);
+        }
+        if let Some(date) = text("date")? {
+            puzzle = puzzle.with_date(date);
+        }
+        if let Some(difficulty) = text("difficulty")? {
+            puzzle = puzzle.with_difficulty(difficulty);
+        }
+        if let Some(variant) = json.get("variant").filter(|variant| **variant != Json::Null) {
+            puzzle = puzzle.with_variant(variant_from_json(variant)?);
+        }
+        Ok(puzzle)
+    }
+
+    fn to_json_value(&self) -> Json {
+        let board = self.board();
+        let mut members = vec![("cells".to_string(), Json::from(board.to_line_string('.').as_str()))];
+        if (0..Board::COUNT_VALUES).any(|cell_id| board[cell_id].value() != 0 && !board.is_given(cell_id)) {
+            members.push(("givens".to_string(), Json::Array(board.givens().into_iter().map(Json::from).collect())));
+        }
+        let texts = [("title", self.title()), ("author", self.author()), ("source", self.source()), ("date", self.date()), ("difficulty", self.difficulty())];
+        for (member, value) in texts {
+            if let Some(value) = value {
+                members.push((member.to_string(), Json::from(value)));
+            }
+        }
+        if !self.variant().is_classic() {
+            members.push(("variant".to_string(), variant_to_json(self.variant())));
+        }
+        Json::Object(members)
+    }
+}
+
+impl PuzzleCollection {
+    /// Parse a collection in the JSON format. A plain array of puzzles is read as well,
+    /// and puzzles may be strings in the one-line format. Fails with the
+    /// ``Error::ParseError`` of the first malformed puzzle, whose reason names its position.
+    pub fn from_json(s: &str) -> Result<Self, Error> {
+        let error = |reason: String| Error::ParseError { input: s.to_string(), reason };
+
+        let json = Json::parse(s).map_err(error)?;
+        let (puzzles, mut collection) = match &json {
+            Json::Array(puzzles) => (puzzles.as_slice(), PuzzleCollection::new()),
+            _ => {
+                let puzzles = json.get("puzzles").and_then(Json::as_array).ok_or_else(|| error("expected an array of puzzles".to_string()))?;
+                let mut collection = PuzzleCollection::new();
+                if let Some(name) = json.get("name").and_then(Json::as_str) {
+                    collection = collection.with_name(name);
+                }
+                if let Some(level) = json.get("level").and_then(Json::as_str) {
+                    collection = collection.with_level(level);
+                }
+                (puzzles, collection)
+            },
+        };
+        for (i, puzzle) in puzzles.iter().enumerate() {
+            let puzzle = match puzzle {
+                Json::String(cells) => cells.parse::<Board>().map(Puzzle::new).map_err(|parse_error| parse_error.to_string()),
+                _ => Puzzle::from_json_value(puzzle),
+            };
+            collection.push(puzzle.map_err(|reason| error(format!("{reason} in puzzle {}", i + 1)))?);
+        }
+        Ok(collection)
+    }
+
+    /// The collection in the JSON format, one puzzle per line
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{");
+        if let Some(name) = self.name() {
+            out.push_str(&format!("\"name\":{},", Json::from(name)));
+        }
+        if let Some(level) = self.level() {
+            out.push_str(&format!("\"level\":{},", Json::from(level)));
+        }
+        let puzzles: Vec<String> = self.iter().map(|puzzle| puzzle.to_json_value().to_string()).collect();
+        out.push_str(&format!("\"puzzles\":[\n{}\n]}}\n", puzzles.join(",\n")));
+        out
+    }
+}
+
+fn stats_to_json(stats: &SolveStats) -> Json {
+    Json::Object(vec![
+        ("nodes".to_string(), Json::Number(stats.nodes as f64)),
+        ("backtracks".to_string(), Json::Number(stats.backtracks as f64)),
+        ("propagations".to_string(), Json::Number(stats.propagations as f64)),
+        ("max_depth".to_string(), Json::from(stats.max_depth)),
+        ("elapsed_ms".to_string(), Json::Number(stats.elapsed.as_secs_f64() * 1000.0)),
+    ])
+}
+
+fn variant_to_json(variant: &Variant) -> Json {
+    let mut members = vec![];
+    if !variant.regions().is_classic() {
+        let regions: String = (0..Board::COUNT_VALUES).map(|cell_id| char::from(b'1' + variant.regions().region_of(cell_id) as u8)).collect();
+        members.push(("regions".to_string(), Json::from(regions.as_str())));
+    }
+    let flags = [
+        ("latin_square", variant.is_latin_square()), ("diagonals", variant.has_diagonals()), ("windows", variant.has_windows()),
+        ("anti_knight", variant.has_anti_knight()), ("anti_king", variant.has_anti_king()), ("non_consecutive", variant.has_non_consecutive()),
+    ];
+    for (member, _) in flags.iter().filter(|(_, set)| *set) {
+        members.push((member.to_string(), Json::from(true)));
+    }
+    if !variant.thermometers().is_empty() {
+        members.push(("thermometers".to_string(), Json::Array(variant.thermometers().iter().map(|thermometer| Json::from(thermometer.to_string().as_str())).collect())));
+    }
+    if !variant.arrows().is_empty() {
+        members.push(("arrows".to_string(), Json::Array(variant.arrows().iter().map(|arrow| Json::from(arrow.to_string().as_str())).collect())));
+    }
+    Json::Object(members)
+}
+
+fn variant_from_json(json: &Json) -> Result<Variant, String> {
+    let flag = |member: &str| match json.get(member) {
+        None | Some(Json::Null) => Ok(false),
+        Some(value) => value.as_bool().ok_or_else(|| format!("expected a boolean as {member} of the variant")),
+    };
+    let shapes = |member: &str| match json.get(member) {
+        None | Some(Json::Null) => Ok(vec![]),
+        Some(value) => value.as_array().and_then(|shapes| shapes.iter().map(Json::as_str).collect::<Option<Vec<&str>>>())
+            .ok_or_else(|| format!("expected an array of strings as {member} of the variant")),
+    };
+
+    let mut variant = Variant::classic();
+    if let Some(regions) = json.get("regions").filter(|regions| **regions != Json::Null) {
+        let regions = regions.as_str().ok_or("expected a string as regions of the variant")?;
+        variant = variant.with_regions(regions.parse::<Regions>().map_err(|error| error.to_string())?);
+    }
+    if flag("latin_square")? {
+        variant = variant.with_latin_square();
+    }
+    if flag("diagonals")? {
+        variant = variant.with_diagonals();
+    }
+    if flag("windows")? {
+        variant = variant.with_windows();
+    }
+    if flag("anti_knight")? {
+        variant = variant.with_anti_knight();
+    }
+    if flag("anti_king")? {
+        variant = variant.with_anti_king();
+    }
+    if flag("non_consecutive")? {
+        variant = variant.with_non_consecutive();
+    }
+    for thermometer in shapes("thermometers")? {
+        variant = variant.with_thermometer(thermometer.parse::<Thermometer>().map_err(|error| error.to_string())?);
+    }
+    for arrow in shapes("arrows")? {
+        variant = variant.with_arrow(arrow.parse::<Arrow>().map_err(|error| error.to_string())?);
+    }
+    Ok(variant)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "..75......6..2..4....3.65.22......8...8.3.1...9......79.32.7....7..9..5......89..";
+
+    #[test]
+    fn puzzle_round_trip() {
+        let variant = Variant::classic().with_diagonals().with_thermometer(Thermometer::new(vec![0, 1, 10]).unwrap());
+        let mut board: Board = PUZZLE.parse().unwrap();
+        board.try_set(0, 1).unwrap();
+        let puzzle = Puzzle::new(board).with_title("Daily").with_author("Jane \"J\" Doe").with_variant(variant);
+        let parsed = Puzzle::from_json(&puzzle.to_json()).unwrap();
+        assert_eq!((parsed.title(), parsed.author(), parsed.date()), (Some("Daily"), Some("Jane \"J\" Doe"), None));
+        assert_eq!(parsed.board().values(), puzzle.board().values());
+        assert_eq!(parsed.board().givens(), puzzle.board().givens());
+        assert!(parsed.variant().has_diagonals() && !parsed.variant().has_windows());
+        assert_eq!(parsed.variant().thermometers()[0].cells(), [0, 1, 10]);
+    }
+
+    #[test]
+    fn stats_are_written_only() {
+        let puzzle = Puzzle::new(PUZZLE.parse().unwrap());
+        let json = puzzle.to_json_with_stats(&SolveStats { nodes: 12, ..SolveStats::default() });
+        assert!(json.contains("\"stats\":{\"nodes\":12,"), "{json}");
+        assert_eq!(Puzzle::from_json(&json).unwrap().board().values(), puzzle.board().values());
+    }
+
+    #[test]
+    fn collection_round_trip() {
+        let mut collection = PuzzleCollection::new().with_name("Daily");
+        collection.push(PUZZLE.parse::<Board>().unwrap());
+        collection.push(Puzzle::new(PUZZLE.replace('9', ".").parse().unwrap()).with_difficulty("hard"));
+        let parsed = PuzzleCollection::from_json(&collection.to_json()).unwrap();
+        assert_eq!((parsed.name(), parsed.len()), (Some("Daily"), 2));
+        assert_eq!(parsed.get(1).and_then(Puzzle::difficulty), Some("hard"));
+        assert_eq!(PuzzleCollection::from_json(&format!("[\"{PUZZLE}\"]")).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn malformed_puzzles_fail() {
+        assert!(Puzzle::from_json("{\"title\":\"no cells\"}").is_err());
+        assert!(Puzzle::from_json(&format!("{{\"cells\":\"{PUZZLE}\",\"title\":1}}")).is_err());
+        let Err(Error::ParseError { reason, .. }) = PuzzleCollection::from_json(&format!("[\"{PUZZLE}\",\"{}\"]", &PUZZLE[1..])) else { panic!() };
+        assert!(reason.ends_with("in puzzle 2"), "{reason}");
+    }
+}
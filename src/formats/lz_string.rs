@@ -0,0 +1,222 @@
+//! The LZ-based compression of the JavaScript library lz-string in its URL-safe
+//! base64 flavor (`compressToBase64`), which f-puzzles uses for its links.
+//! The algorithm works on UTF-16 code units like JavaScript strings.
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+/// The alphabet of the base64 output, the value of a character being its position
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Bits written into characters of 6 bits each, the first bit being the highest one
+struct BitWriter {
+    out: String,
+    value: u8,
+    position: usize,
+}
+
+impl BitWriter {
+    /// Write the lowest `count` bits of the value, the lowest bit first
+    fn write(&mut self, mut value: usize, count: usize) {
+        for _ in 0..count {
+            self.value = (self.value << 1) | (value & 1) as u8;
+            value >>= 1;
+            if self.position == 5 {
+                self.out.push(BASE64[self.value as usize] as char);
+                self.position = 0;
+                self.value = 0;
+            } else {
+                self.position += 1;
+            }
+        }
+    }
+
+    /// Pad the last character with zero bits and the output with `=` to a multiple of 4
+    fn finish(mut self) -> String {
+        loop {
+            self.value <<= 1;
+            if self.position == 5 {
+                self.out.push(BASE64[self.value as usize] as char);
+                break;
+            }
+            self.position += 1;
+        }
+        while !self.out.len().is_multiple_of(4) {
+            self.out.push('=');
+        }
+        self.out
+    }
+}
+
+/// Compress the text like `LZString.compressToBase64`
+pub(crate) fn compress_to_base64(text: &str) -> String {
+    let units: Vec<u16> = text.encode_utf16().collect();
+    let mut dictionary: HashMap<Vec<u16>, usize> = HashMap::new();
+    // NOTE: single units which are in the dictionary but whose literal is not written yet
+    let mut to_create: HashSet<u16> = HashSet::new();
+    let mut writer = BitWriter { out: String::new(), value: 0, position: 0 };
+    let (mut enlarge_in, mut dictionary_size, mut count_bits) = (2usize, 3usize, 2usize);
+    let mut word: Vec<u16> = vec![];
+
+    // NOTE: write the code of `word`, as a literal the first time a unit occurs
+    let emit = |word: &[u16], dictionary: &HashMap<Vec<u16>, usize>, to_create: &mut HashSet<u16>, enlarge_in: &mut usize, count_bits: &mut usize, writer: &mut BitWriter| {
+        if word.len() == 1 && to_create.remove(&word[0]) {
+            match word[0] < 256 {
+                true => {
+                    writer.write(0, *count_bits);
+                    writer.write(word[0] as usize, 8);
+                },
+                false => {
+                    writer.write(1, *count_bits);
+                    writer.write(word[0] as usize, 16);
+                },
+            }
+            *enlarge_in -= 1;
+            if *enlarge_in == 0 {
+                *enlarge_in = 1 << *count_bits;
+                *count_bits += 1;
+            }
+        } else {
+            writer.write(dictionary[word], *count_bits);
+        }
+        *enlarge_in -= 1;
+        if *enlarge_in == 0 {
+            *enlarge_in = 1 << *count_bits;
+            *count_bits += 1;
+        }
+    };
+
+    for unit in units {
+        if let Entry::Vacant(entry) = dictionary.entry(vec![unit]) {
+            entry.insert(dictionary_size);
+            dictionary_size += 1;
+            to_create.insert(unit);
+        }
+        let mut extended = word.clone();
+        extended.push(unit);
+        if dictionary.contains_key(&extended) {
+            word = extended;
+        } else {
+            emit(&word, &dictionary, &mut to_create, &mut enlarge_in, &mut count_bits, &mut writer);
+            dictionary.insert(extended, dictionary_size);
+            dictionary_size += 1;
+            word = vec![unit];
+        }
+    }
+    if !word.is_empty() {
+        emit(&word, &dictionary, &mut to_create, &mut enlarge_in, &mut count_bits, &mut writer);
+    }
+    writer.write(2, count_bits);
+    writer.finish()
+}
+
+/// Bits read from characters of 6 bits each, the first bit being the highest one
+struct BitReader {
+    values: Vec<u8>,
+    index: usize,
+    position: u8,
+}
+
+impl BitReader {
+    /// Read `count` bits into a value, the lowest bit first. Bits past the end are 0.
+    fn read(&mut self, count: usize) -> usize {
+        let mut bits = 0;
+        for power in 0..count {
+            let value = self.values.get(self.index).copied().unwrap_or(0);
+            if value & self.position != 0 {
+                bits |= 1 << power;
+            }
+            self.position >>= 1;
+            if self.position == 0 {
+                self.position = 32;
+                self.index += 1;
+            }
+        }
+        bits
+    }
+}
+
+/// Decompress the output of `LZString.compressToBase64`. Fails with the reason
+/// on characters outside of the base64 alphabet or a corrupt stream.
+pub(crate) fn decompress_from_base64(data: &str) -> Result<String, String> {
+    let values = data.trim_end_matches('=').chars()
+        .map(|symbol| BASE64.iter().position(|other| *other as char == symbol).map(|value| value as u8).ok_or_else(|| format!("unexpected character {symbol:?}")))
+        .collect::<Result<Vec<u8>, String>>()?;
+    let count_values = values.len();
+    let mut reader = BitReader { values, index: 0, position: 32 };
+
+    let literal = |reader: &mut BitReader, code: usize| match code {
+        0 => Ok(vec![reader.read(8) as u16]),
+        1 => Ok(vec![reader.read(16) as u16]),
+        _ => Err("corrupt data".to_string()),
+    };
+
+    // NOTE: codes 0 to 2 stand for a literal of 8 bits, of 16 bits and the end of the stream
+    let mut dictionary: Vec<Vec<u16>> = vec![vec![]; 3];
+    let (mut enlarge_in, mut count_bits) = (4usize, 3usize);
+    let first = match reader.read(2) {
+        2 => return Ok(String::new()),
+        code => literal(&mut reader, code)?,
+    };
+    dictionary.push(first.clone());
+    let mut word = first.clone();
+    let mut result = first;
+
+    loop {
+        if reader.index >= count_values {
+            return Err("unexpected end of the data".to_string());
+        }
+        let mut code = reader.read(count_bits);
+        match code {
+            0 | 1 => {
+                let entry = literal(&mut reader, code)?;
+                dictionary.push(entry);
+                code = dictionary.len() - 1;
+                enlarge_in -= 1;
+            },
+            2 => return Ok(String::from_utf16_lossy(&result)),
+            _ => {},
+        }
+        if enlarge_in == 0 {
+            enlarge_in = 1 << count_bits;
+            count_bits += 1;
+        }
+
+        let entry = match dictionary.get(code) {
+            Some(entry) => entry.clone(),
+            None if code == dictionary.len() => [word.clone(), vec![word[0]]].concat(),
+            None => return Err("corrupt data".to_string()),
+        };
+        result.extend(&entry);
+        dictionary.push([word, vec![entry[0]]].concat());
+        enlarge_in -= 1;
+        word = entry;
+        if enlarge_in == 0 {
+            enlarge_in = 1 << count_bits;
+            count_bits += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let long = "{\"grid\":[".to_string() + &"{\"value\":1,\"given\":true},{},".repeat(60) + "]}";
+        for text in ["", "a", "abababababab", "Sudoku ★ für alle", long.as_str()] {
+            let data = compress_to_base64(text);
+            assert!(data.chars().all(|symbol| BASE64.contains(&(symbol as u8)) || symbol == '='), "{data}");
+            assert_eq!(decompress_from_base64(&data).as_deref(), Ok(text));
+        }
+        assert!(compress_to_base64(&long).len() < long.len() / 4);
+    }
+
+    #[test]
+    fn corrupt_data_fails() {
+        let data = compress_to_base64("abcdefghijklmnopqrstuvwxyz");
+        assert!(decompress_from_base64(&data[..data.len() / 2]).is_err());
+        assert!(decompress_from_base64("N4Ig!").is_err());
+    }
+}
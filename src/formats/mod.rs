@@ -0,0 +1,17 @@
+//! Text formats of puzzle files used by other Sudoku programs, and a compact binary one.
+//!
+//! Every format adds a pair of methods to `Board`, one parsing a file
+//! and one writing it, e.g. `Board::from_sdk` and `Board::to_sdk`.
+//! Formats of several puzzles add them to `PuzzleCollection` instead.
+//! Formats keeping information about a puzzle, like its author or the rules of
+//! its variant, add them to `Puzzle` as well, e.g. `Puzzle::from_sdk`.
+
+mod binary;
+mod csv;
+mod fpuzzles;
+mod json;
+mod lz_string;
+mod opensudoku;
+mod sdk;
+mod sdm;
+mod ss;
@@ -0,0 +1,137 @@
+//! The XML format of the puzzle packs of OpenSudoku for Android (`.opensudoku`).
+//! A pack has a name, a level and the puzzles as `<game>` elements whose `data`
+//! attribute holds the 81 cells with `0` for blanks, e.g.
+//!
+//! ```text
+//! <opensudoku>
+//!   <name>Easy puzzles</name>
+//!   <level>easy</level>
+//!   <game data="530070000600195000..." />
+//! </opensudoku>
+//! ```
+//!
+//! The format of newer versions with the games of a pack inside a
+//! `<folder name="...">` element is read as well.
+
+use crate::board::Board;
+use crate::collection::PuzzleCollection;
+use crate::error::Error;
+
+impl PuzzleCollection {
+    /// Parse an OpenSudoku pack with its name and level. Fails with ``Error::ParseError``
+    /// unless there is an `<opensudoku>` element and every game has 81 cells of `data`.
+    // ASSUME: the packs are well-formed XML, so a simple scan for the elements suffices
+    pub fn from_opensudoku(s: &str) -> Result<Self, Error> {
+        let error = |reason: String| Error::ParseError { input: s.to_string(), reason };
+
+        if !s.contains("<opensudoku") {
+            return Err(error("expected an <opensudoku> element".to_string()));
+        }
+        let mut collection = PuzzleCollection::new();
+        let folder_name = start_tags(s, "folder").next().and_then(|folder| attribute(folder, "name"));
+        if let Some(name) = element_text(s, "name").or(folder_name) {
+            collection = collection.with_name(name);
+        }
+        if let Some(level) = element_text(s, "level") {
+            collection = collection.with_level(level);
+        }
+        for (i, game) in start_tags(s, "game").enumerate() {
+            let data = attribute(game, "data").ok_or_else(|| error(format!("game {} has no data attribute", i + 1)))?;
+            let board = data.parse::<Board>().map_err(|parse_error| match parse_error {
+                Error::ParseError { reason, .. } => error(format!("{reason} in the data of game {}", i + 1)),
+                parse_error => parse_error,
+            })?;
+            collection.push(board);
+        }
+        Ok(collection)
+    }
+
+    /// The collection as an OpenSudoku pack with `0` for unassigned cells
+    pub fn to_opensudoku(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opensudoku>\n");
+        if let Some(name) = self.name() {
+            out.push_str(&format!("  <name>{}</name>\n", escape(name)));
+        }
+        if let Some(level) = self.level() {
+            out.push_str(&format!("  <level>{}</level>\n", escape(level)));
+        }
+        for board in self.boards() {
+            out.push_str(&format!("  <game data=\"{}\" />\n", board.to_line_string('0')));
+        }
+        out.push_str("</opensudoku>\n");
+        out
+    }
+}
+
+/// The contents of the start tags of the elements named `tag`, i.e. the attributes
+fn start_tags<'a>(xml: &'a str, tag: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    xml.match_indices('<').filter_map(move |(start, _)| {
+        let rest = xml[start + 1..].strip_prefix(tag)?;
+        if !rest.starts_with(|symbol: char| symbol.is_whitespace() || symbol == '/' || symbol == '>') {
+            return None;
+        }
+        rest.find('>').map(|end| &rest[..end])
+    })
+}
+
+/// The unescaped value of the attribute in the contents of a start tag
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    tag.match_indices(name)
+        .filter(|(start, _)| tag[..*start].ends_with(char::is_whitespace))
+        .find_map(|(start, _)| {
+            let rest = tag[start + name.len()..].trim_start().strip_prefix('=')?.trim_start();
+            let quote = rest.chars().next().filter(|quote| *quote == '"' || *quote == '\'')?;
+            let value = &rest[1..];
+            value.find(quote).map(|end| unescape(&value[..end]))
+        })
+}
+
+/// The unescaped text of the first element named `tag`
+fn element_text(xml: &str, tag: &str) -> Option<String> {
+    let start = xml.find(&format!("<{tag}>"))? + tag.len() + 2;
+    let end = xml[start..].find(&format!("</{tag}>"))?;
+    Some(unescape(xml[start..start + end].trim()))
+}
+
+/// Replace the characters XML reserves by their entities
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Replace the predefined entities of XML by their characters
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"").replace("&apos;", "'").replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "..75......6..2..4....3.65.22......8...8.3.1...9......79.32.7....7..9..5......89..";
+
+    #[test]
+    fn round_trip() {
+        let mut collection = PuzzleCollection::new().with_name("Hard & harder").with_level("hard");
+        collection.push(PUZZLE.parse::<Board>().unwrap());
+        let xml = collection.to_opensudoku();
+        assert!(xml.contains("<name>Hard &amp; harder</name>"));
+        let parsed = PuzzleCollection::from_opensudoku(&xml).unwrap();
+        assert_eq!((parsed.name(), parsed.level(), parsed.len()), (Some("Hard & harder"), Some("hard"), 1));
+        assert_eq!(parsed.boards().next().unwrap().values(), collection.boards().next().unwrap().values());
+    }
+
+    #[test]
+    fn folders_of_newer_versions() {
+        let xml = format!("<opensudoku version=\"2\"><folder name=\"Saved\"><game created=\"0\" data='{}'/></folder></opensudoku>", PUZZLE.replace('.', "0"));
+        let parsed = PuzzleCollection::from_opensudoku(&xml).unwrap();
+        assert_eq!((parsed.name(), parsed.len()), (Some("Saved"), 1));
+    }
+
+    #[test]
+    fn malformed_packs_fail() {
+        assert!(PuzzleCollection::from_opensudoku("<games></games>").is_err());
+        assert!(PuzzleCollection::from_opensudoku("<opensudoku><game /></opensudoku>").is_err());
+        let Err(Error::ParseError { reason, .. }) = PuzzleCollection::from_opensudoku(&format!("<opensudoku><game data=\"{}\"/></opensudoku>", &PUZZLE[1..])) else { panic!() };
+        assert!(reason.ends_with("in the data of game 1"), "{reason}");
+    }
+}
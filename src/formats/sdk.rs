@@ -0,0 +1,109 @@
+//! The `.sdk` format of SadMan Sudoku: optional header lines starting with `#`
+//! (e.g. `#A` for the author, kept by `Puzzle::from_sdk`), then 9 lines of 9 cells
+//! with `.` for blanks.
+//! Saved games may follow the grid of a `[Puzzle]` section with a `[State]` section.
+
+use crate::board::Board;
+use crate::error::Error;
+use crate::puzzle::Puzzle;
+
+impl Board {
+    /// Parse the puzzle of a `.sdk` file. Header lines starting with `#`,
+    /// the `[Puzzle]` line and everything from the next section on are skipped.
+    /// The grid is read like `Board::parse_grid`. All assigned cells become givens.
+    pub fn from_sdk(s: &str) -> Result<Self, Error> {
+        let mut lines = s.lines().map(str::trim).filter(|line| !line.starts_with('#')).peekable();
+        lines.next_if(|line| line.eq_ignore_ascii_case("[Puzzle]"));
+        let grid = lines.take_while(|line| !line.starts_with('[')).collect::<Vec<&str>>().join("\n");
+        Self::parse_grid(&grid).map_err(|error| match error {
+            Error::ParseError { reason, .. } => Error::ParseError { input: s.to_string(), reason },
+            error => error,
+        })
+    }
+
+    /// The board in the `.sdk` format: 9 lines of 9 cells with `.` for unassigned cells
+    pub fn to_sdk(&self) -> String {
+        self.rows()
+            .map(|(_, row)| row.iter().map(|cell| match cell.value() {
+                value @ 1..=9 => (b'0' + value) as char,
+                _ => '.',
+            }).collect::<String>() + "\n")
+            .collect()
+    }
+}
+
+impl Puzzle {
+    /// Parse the puzzle of a `.sdk` file like `Board::from_sdk` together with the
+    /// information of its header lines: `#D` (the description) as the title, `#A` the
+    /// author, `#S` the source, `#B` the date and `#L` the level as the difficulty.
+    pub fn from_sdk(s: &str) -> Result<Self, Error> {
+        let mut puzzle = Self::new(Board::from_sdk(s)?);
+        for line in s.lines().map(str::trim).take_while(|line| !line.starts_with("[State")) {
+            let Some(header) = line.strip_prefix('#') else { continue };
+            let mut symbols = header.chars();
+            let (tag, value) = (symbols.next(), symbols.as_str().trim());
+            if value.is_empty() {
+                continue;
+            }
+            puzzle = match tag {
+                Some('D') => puzzle.with_title(value),
+                Some('A') => puzzle.with_author(value),
+                Some('S') => puzzle.with_source(value),
+                Some('B') => puzzle.with_date(value),
+                Some('L') => puzzle.with_difficulty(value),
+                _ => puzzle,
+            };
+        }
+        Ok(puzzle)
+    }
+
+    /// The puzzle in the `.sdk` format like `Board::to_sdk`, preceded by a header line
+    /// for each of the title, the author, the source, the date and the difficulty it has
+    pub fn to_sdk(&self) -> String {
+        let headers = [('D', self.title()), ('A', self.author()), ('S', self.source()), ('B', self.date()), ('L', self.difficulty())];
+        let mut out: String = headers.iter()
+            .filter_map(|(tag, value)| value.map(|value| format!("#{tag}{}\n", value.replace('\n', " "))))
+            .collect();
+        out.push_str(&self.board().to_sdk());
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "..75......6..2..4....3.65.22......8...8.3.1...9......79.32.7....7..9..5......89..";
+
+    #[test]
+    fn board_round_trip() {
+        let board: Board = PUZZLE.parse().unwrap();
+        let sdk = board.to_sdk();
+        assert_eq!(sdk.lines().next(), Some("..75....."));
+        assert_eq!(Board::from_sdk(&sdk).unwrap().values(), board.values());
+    }
+
+    #[test]
+    fn saved_games_keep_the_puzzle_section() {
+        let board: Board = PUZZLE.parse().unwrap();
+        let solved = PUZZLE.replacen('.', "1", 1).parse::<Board>().unwrap();
+        let saved = format!("#Asomeone\n[Puzzle]\n{}[State]\n{}", board.to_sdk(), solved.to_sdk());
+        assert_eq!(Board::from_sdk(&saved).unwrap().values(), board.values());
+    }
+
+    #[test]
+    fn puzzle_round_trip_with_headers() {
+        let puzzle = Puzzle::new(PUZZLE.parse().unwrap()).with_title("Escargot").with_author("someone").with_difficulty("hard");
+        let sdk = puzzle.to_sdk();
+        assert!(sdk.starts_with("#DEscargot\n#Asomeone\n#Lhard\n"));
+        let parsed = Puzzle::from_sdk(&sdk).unwrap();
+        assert_eq!((parsed.title(), parsed.author(), parsed.source(), parsed.difficulty()), (Some("Escargot"), Some("someone"), None, Some("hard")));
+        assert_eq!(parsed.board().values(), puzzle.board().values());
+    }
+
+    #[test]
+    fn malformed_grids_fail() {
+        let sdk = PUZZLE.parse::<Board>().unwrap().to_sdk();
+        assert!(matches!(Board::from_sdk(&sdk[..sdk.len() - 10]), Err(Error::ParseError { .. })));
+    }
+}
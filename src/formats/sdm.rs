@@ -0,0 +1,49 @@
+//! The `.sdm` format of puzzle collections: one puzzle per line in the
+//! one-line format of 81 cells, e.g. the classic benchmark files.
+
+use crate::board::Board;
+use crate::collection::PuzzleCollection;
+use crate::error::Error;
+
+impl PuzzleCollection {
+    /// Parse the puzzles of an `.sdm` file, one per line like `Board::from_str`.
+    /// Blank lines are skipped. Fails with the ``Error::ParseError`` of the first
+    /// malformed line, whose reason names the line number.
+    pub fn from_sdm(s: &str) -> Result<Self, Error> {
+        s.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(line_id, line)| line.parse::<Board>().map_err(|error| match error {
+                Error::ParseError { input, reason } => Error::ParseError { input, reason: format!("{reason} in line {}", line_id + 1) },
+                error => error,
+            }))
+            .collect()
+    }
+
+    /// The puzzles in the `.sdm` format, one line of 81 cells per puzzle with `0` for unassigned cells
+    pub fn to_sdm(&self) -> String {
+        self.boards().map(|board| board.to_line_string('0') + "\n").collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "..75......6..2..4....3.65.22......8...8.3.1...9......79.32.7....7..9..5......89..";
+
+    #[test]
+    fn round_trip() {
+        let collection: PuzzleCollection = [PUZZLE, &PUZZLE.replace('9', ".")].iter().map(|line| line.parse::<Board>().unwrap()).collect();
+        let sdm = collection.to_sdm();
+        assert_eq!(sdm.lines().next(), Some(PUZZLE.replace('.', "0").as_str()));
+        let parsed = PuzzleCollection::from_sdm(&format!("\n{sdm}\n")).unwrap();
+        assert_eq!(parsed.boards().map(Board::values).collect::<Vec<_>>(), collection.boards().map(Board::values).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn the_error_names_the_line() {
+        let Err(Error::ParseError { reason, .. }) = PuzzleCollection::from_sdm(&format!("{PUZZLE}\n{}\n", &PUZZLE[1..])) else { panic!() };
+        assert!(reason.ends_with("in line 2"), "{reason}");
+    }
+}
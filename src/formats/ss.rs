@@ -0,0 +1,81 @@
+//! The `.ss` format of Simple Sudoku: 9 rows of cells with `|` between the
+//! blocks and `.` or `X` for blanks, the blocks of rows separated by dashes.
+//! Saved files may frame the grid with `*-----------*` lines.
+
+use crate::board::{Board, Value};
+use crate::error::Error;
+
+impl Board {
+    /// Parse the puzzle of an `.ss` file. Besides `.` and `X`, blanks may be `0`.
+    /// Lines holding only separators (`|`, `-`, `+`, `*`) are skipped.
+    /// All assigned cells become givens.
+    pub fn from_ss(s: &str) -> Result<Self, Error> {
+        let grid: String = s.chars()
+            .map(|symbol| match symbol {
+                'X' | 'x' => '.',
+                '*' => ' ',
+                _ => symbol,
+            })
+            .collect();
+        Self::parse_grid(&grid).map_err(|error| match error {
+            Error::ParseError { reason, .. } => Error::ParseError { input: s.to_string(), reason },
+            error => error,
+        })
+    }
+
+    /// The board in the `.ss` format with `.` for unassigned cells, e.g.
+    ///
+    /// ```text
+    /// 53.|.7.|...
+    /// 6..|195|...
+    /// .98|...|.6.
+    /// -----------
+    /// ```
+    pub fn to_ss(&self) -> String {
+        let symbol = |value: Value| match value {
+            1..=9 => (b'0' + value) as char,
+            _ => '.',
+        };
+
+        let mut out = String::new();
+        for (row_id, row) in self.rows() {
+            if row_id > 0 && row_id % 3 == 0 {
+                out.push_str(&"-".repeat(11));
+                out.push('\n');
+            }
+            let blocks: Vec<String> = row.cells().chunks(3).map(|block| block.iter().map(|cell| symbol(cell.value())).collect()).collect();
+            out.push_str(&blocks.join("|"));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "..75......6..2..4....3.65.22......8...8.3.1...9......79.32.7....7..9..5......89..";
+
+    #[test]
+    fn round_trip() {
+        let board: Board = PUZZLE.parse().unwrap();
+        let ss = board.to_ss();
+        assert_eq!(ss.lines().take(4).collect::<Vec<_>>(), ["..7|5..|...", ".6.|.2.|.4.", "...|3.6|5.2", "-----------"]);
+        assert_eq!(Board::from_ss(&ss).unwrap().values(), board.values());
+    }
+
+    #[test]
+    fn framed_grids_with_x_for_blanks() {
+        let board: Board = PUZZLE.parse().unwrap();
+        let framed = format!("*-----------*\n{}*-----------*\n", board.to_ss().replace('.', "X"));
+        assert_eq!(Board::from_ss(&framed).unwrap().values(), board.values());
+    }
+
+    #[test]
+    fn malformed_grids_fail() {
+        let ss = PUZZLE.parse::<Board>().unwrap().to_ss();
+        assert!(Board::from_ss(&ss.replacen('7', "A", 1)).is_err());
+        assert!(Board::from_ss(&ss[..ss.len() - 12]).is_err());
+    }
+}
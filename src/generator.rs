@@ -0,0 +1,165 @@
+//! Generating new classic puzzles with a unique solution.
+//!
+//! The generator fills an empty board with a random solution and then removes
+//! givens in random order as long as the solution stays unique. For a requested
+//! difficulty, it also keeps every given whose removal would make the puzzle
+//! harder, and starts over with a new solution if the puzzle ends up easier.
+
+use crate::board::Board;
+use crate::difficulty::Difficulty;
+use crate::error::Error;
+use crate::puzzle::Puzzle;
+use crate::rng::Rng;
+use crate::solver::{SolveConfig, SolveOutcome};
+use crate::sudoku::Sudoku;
+
+/// What to generate, as used by `Generator::new`
+#[derive(Clone,Copy,Debug)]
+pub struct GeneratorConfig {
+    /// The grade of the puzzles, any grade if `None`.
+    /// Pathological puzzles cannot be generated.
+    pub difficulty: Option<Difficulty>,
+    /// The same seed always generates the same puzzles
+    pub seed: u64,
+    /// Keep the givens symmetric under a rotation by 180 degrees, as most newspapers do
+    pub symmetric: bool,
+    /// Give up on a puzzle after this many solutions which yield none of the difficulty
+    pub max_attempts: usize,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self { difficulty: None, seed: 0, symmetric: true, max_attempts: 1000 }
+    }
+}
+
+/// Source of new puzzles, one after the other
+#[derive(Clone,Debug)]
+pub struct Generator {
+    config: GeneratorConfig,
+    rng: Rng,
+}
+
+impl Generator {
+    pub fn new(config: GeneratorConfig) -> Self {
+        Self { config, rng: Rng::new(config.seed) }
+    }
+
+    /// The next puzzle, graded with its difficulty. Fails with ``Error::GenerateError``
+    /// for pathological puzzles or if every attempt misses the difficulty.
+    pub fn next_puzzle(&mut self) -> Result<Puzzle, Error> {
+        self.next_puzzle_observed(&mut |_| {})
+    }
+
+    /// The next puzzle like `next_puzzle`, passing the number of every attempt,
+    /// counted from 1, to `observe` before making it
+    pub fn next_puzzle_observed(&mut self, observe: &mut dyn FnMut(usize)) -> Result<Puzzle, Error> {
+        if self.config.difficulty == Some(Difficulty::Pathological) {
+            return Err(Error::GenerateError { reason: "pathological puzzles are not generated".to_string() });
+        }
+        for attempt in 1..=self.config.max_attempts.max(1) {
+            observe(attempt);
+            let solution = self.solution();
+            let board = self.dig(&solution);
+            let difficulty = Difficulty::of(&board);
+            if self.config.difficulty.is_none_or(|target| target == difficulty) {
+                return Ok(Puzzle::new(board).with_difficulty(difficulty.name()));
+            }
+        }
+        let difficulty = self.config.difficulty.map_or(String::new(), |difficulty| format!("{difficulty} "));
+        Err(Error::GenerateError { reason: format!("no {difficulty}puzzle within {} attempts", self.config.max_attempts.max(1)) })
+    }
+
+    /// A random solution grid
+    fn solution(&mut self) -> Board {
+        let config = SolveConfig { seed: Some(self.rng.next_u64()), ..SolveConfig::default() };
+        match Sudoku::default().solve_with(&config) {
+            SolveOutcome::Solved(solution) => solution,
+            // ASSUME: the empty board always has a solution
+            _ => unreachable!("the empty board has no solution"),
+        }
+    }
+
+    /// Remove givens of the solution in random order while the puzzle stays
+    /// unique and, for an easier difficulty than expert, no harder than it
+    fn dig(&mut self, solution: &Board) -> Board {
+        let mut values = solution.values();
+        let mut cell_ids: Vec<usize> = match self.config.symmetric {
+            true => (0..=Board::COUNT_VALUES / 2).collect(),
+            false => (0..Board::COUNT_VALUES).collect(),
+        };
+        self.rng.shuffle(&mut cell_ids);
+
+        let solver = self.config.difficulty.filter(|difficulty| *difficulty < Difficulty::Expert).map(|difficulty| difficulty.solver());
+        for cell_id in cell_ids {
+            let removed = match self.config.symmetric {
+                true => vec![cell_id, Board::COUNT_VALUES - 1 - cell_id],
+                false => vec![cell_id],
+            };
+            let kept = values;
+            for cell_id in removed.iter() {
+                values[*cell_id] = 0;
+            }
+            let board = Board::from_flattened_values(&values);
+            let mut sudoku = Sudoku::default();
+            sudoku.init_board(&board);
+            if !sudoku.has_unique_solution() || solver.is_some_and(|solver| !solver.can_solve(&board)) {
+                values = kept;
+            }
+        }
+        Board::from_flattened_values(&values)
+    }
+}
+
+impl Iterator for Generator {
+    type Item = Result<Puzzle, Error>;
+
+    /// The next puzzle like `next_puzzle`, without end
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_puzzle())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn puzzles_are_unique_and_symmetric() {
+        let puzzle = Generator::new(GeneratorConfig { seed: 1, ..GeneratorConfig::default() }).next_puzzle().unwrap();
+        let board = puzzle.board();
+        assert!(puzzle.to_sudoku().has_unique_solution());
+        assert!((0..Board::COUNT_VALUES).all(|cell_id| board.is_given(cell_id) == board.is_given(Board::COUNT_VALUES - 1 - cell_id)));
+        assert_eq!(puzzle.difficulty(), Some(Difficulty::of(board).name()));
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_puzzles() {
+        let config = GeneratorConfig { seed: 2, symmetric: false, ..GeneratorConfig::default() };
+        let values = |generator: Generator| generator.take(2).map(|puzzle| puzzle.unwrap().board().values()).collect::<Vec<_>>();
+        let first = values(Generator::new(config));
+        assert_eq!(first, values(Generator::new(config)));
+        assert_ne!(first[0], first[1]);
+        assert_ne!(first, values(Generator::new(GeneratorConfig { seed: 3, ..config })));
+    }
+
+    #[test]
+    fn the_difficulty_is_met() {
+        let config = GeneratorConfig { difficulty: Some(Difficulty::Easy), seed: 4, ..GeneratorConfig::default() };
+        let puzzle = Generator::new(config).next_puzzle().unwrap();
+        assert_eq!(Difficulty::of(puzzle.board()), Difficulty::Easy);
+    }
+
+    #[test]
+    fn pathological_puzzles_are_not_generated() {
+        let config = GeneratorConfig { difficulty: Some(Difficulty::Pathological), ..GeneratorConfig::default() };
+        assert!(matches!(Generator::new(config).next_puzzle(), Err(Error::GenerateError { .. })));
+    }
+
+    #[test]
+    fn attempts_are_observed() {
+        let mut attempts = vec![];
+        Generator::new(GeneratorConfig::default()).next_puzzle_observed(&mut |attempt| attempts.push(attempt)).unwrap();
+        assert_eq!(attempts, [1]);
+    }
+}
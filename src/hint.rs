@@ -0,0 +1,50 @@
+use std::fmt;
+
+use crate::board::Value;
+use crate::strategies::{cell_name, Deduction, Technique};
+
+/// The next logical step of a game as suggested by `Sudoku::next_hint`
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct Hint {
+    pub technique: Technique,
+    /// The cells forming the pattern of the technique, worth highlighting
+    pub cells: Vec<usize>,
+    /// Candidates ruled out by the technique as `(cell_id, value)`
+    pub eliminations: Vec<(usize, Value)>,
+    /// The value which can be placed as `(cell_id, value)`, if any
+    pub placement: Option<(usize, Value)>,
+    /// Why the step is valid, in words
+    pub explanation: String,
+}
+
+impl Hint {
+    /// Human-readable name of the technique
+    pub fn technique_name(&self) -> &'static str {
+        self.technique.name()
+    }
+}
+
+impl From<Deduction> for Hint {
+    fn from(deduction: Deduction) -> Self {
+        Self {
+            technique: deduction.technique,
+            cells: deduction.cells,
+            eliminations: deduction.eliminations,
+            placement: deduction.placements.first().copied(),
+            explanation: deduction.explanation,
+        }
+    }
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.technique, self.explanation)?;
+        if let Some((cell_id, value)) = self.placement {
+            write!(f, " => {}={}", cell_name(cell_id), value)?;
+        } else if !self.eliminations.is_empty() {
+            let eliminations = self.eliminations.iter().map(|(cell_id, value)| format!("{}<>{}", cell_name(*cell_id), value));
+            write!(f, " => {}", eliminations.collect::<Vec<String>>().join(", "))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,315 @@
+//! A minimal JSON document model with a parser and a compact writer,
+//! enough for the file formats exchanging JSON with other programs.
+
+use std::fmt;
+
+/// A JSON value. Objects keep the order of their members.
+#[derive(Clone,Debug,PartialEq)]
+pub(crate) enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Parse a JSON document. On failure returns the reason with the byte offset.
+    pub(crate) fn parse(s: &str) -> Result<Json, String> {
+        let mut parser = Parser { s, position: 0 };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        match parser.position == s.len() {
+            true => Ok(value),
+            false => Err(parser.error("unexpected characters after the document")),
+        }
+    }
+
+    /// The member of an object with the key, `None` for other values
+    pub(crate) fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(members) => members.iter().find(|(name, _)| name == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_bool(&self) -> Option<bool> {
+        match self {
+            Json::Bool(flag) => Some(*flag),
+            _ => None,
+        }
+    }
+
+    /// The number if it is a non-negative integer
+    pub(crate) fn as_usize(&self) -> Option<usize> {
+        match self {
+            Json::Number(number) if *number >= 0.0 && number.fract() == 0.0 => Some(*number as usize),
+            _ => None,
+        }
+    }
+
+    /// Does the value carry information, i.e. is it neither `null`, `false`, empty nor zero?
+    pub(crate) fn is_set(&self) -> bool {
+        match self {
+            Json::Null => false,
+            Json::Bool(flag) => *flag,
+            Json::Number(number) => *number != 0.0,
+            Json::String(text) => !text.is_empty(),
+            Json::Array(values) => !values.is_empty(),
+            Json::Object(members) => !members.is_empty(),
+        }
+    }
+}
+
+impl From<&str> for Json {
+    fn from(text: &str) -> Self {
+        Json::String(text.to_string())
+    }
+}
+
+impl From<usize> for Json {
+    fn from(number: usize) -> Self {
+        Json::Number(number as f64)
+    }
+}
+
+impl From<bool> for Json {
+    fn from(flag: bool) -> Self {
+        Json::Bool(flag)
+    }
+}
+
+impl fmt::Display for Json {
+    /// The compact representation without whitespace
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(flag) => write!(f, "{flag}"),
+            Json::Number(number) if number.is_finite() => write!(f, "{number}"),
+            Json::Number(_) => write!(f, "null"),
+            Json::String(text) => write_string(f, text),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
+            },
+            Json::Object(members) => {
+                write!(f, "{{")?;
+                for (i, (name, value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, name)?;
+                    write!(f, ":{value}")?;
+                }
+                write!(f, "}}")
+            },
+        }
+    }
+}
+
+/// Write the text as a JSON string with quotes and escapes
+fn write_string(f: &mut fmt::Formatter, text: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for symbol in text.chars() {
+        match symbol {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            _ if symbol.is_control() => write!(f, "\\u{:04x}", symbol as u32)?,
+            _ => write!(f, "{symbol}")?,
+        }
+    }
+    write!(f, "\"")
+}
+
+/// Recursive descent over the bytes of a document
+struct Parser<'a> {
+    s: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, reason: &str) -> String {
+        format!("{reason} at offset {}", self.position)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.s.as_bytes().get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.position += 1;
+        }
+    }
+
+    /// Consume the literal if the document continues with it
+    fn eat(&mut self, literal: &str) -> bool {
+        let found = self.s[self.position..].starts_with(literal);
+        if found {
+            self.position += literal.len();
+        }
+        found
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => self.string().map(Json::String),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            _ if self.eat("true") => Ok(Json::Bool(true)),
+            _ if self.eat("false") => Ok(Json::Bool(false)),
+            _ if self.eat("null") => Ok(Json::Null),
+            None => Err(self.error("unexpected end of the document")),
+            Some(_) => Err(self.error("expected a value")),
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, String> {
+        self.position += 1;
+        let mut members = vec![];
+        self.skip_whitespace();
+        if self.eat("}") {
+            return Ok(Json::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(b'"') {
+                return Err(self.error("expected the name of a member"));
+            }
+            let name = self.string()?;
+            self.skip_whitespace();
+            if !self.eat(":") {
+                return Err(self.error("expected ':'"));
+            }
+            members.push((name, self.value()?));
+            self.skip_whitespace();
+            if self.eat("}") {
+                return Ok(Json::Object(members));
+            }
+            if !self.eat(",") {
+                return Err(self.error("expected ',' or '}'"));
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, String> {
+        self.position += 1;
+        let mut values = vec![];
+        self.skip_whitespace();
+        if self.eat("]") {
+            return Ok(Json::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            if self.eat("]") {
+                return Ok(Json::Array(values));
+            }
+            if !self.eat(",") {
+                return Err(self.error("expected ',' or ']'"));
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.position += 1;
+        let mut text = String::new();
+        // NOTE: UTF-16 code units of escapes, combined once the escapes end
+        let mut units: Vec<u16> = vec![];
+        loop {
+            let symbol = self.s[self.position..].chars().next().ok_or_else(|| self.error("unterminated string"))?;
+            if symbol != '\\' && !units.is_empty() {
+                text.push_str(&String::from_utf16_lossy(&units));
+                units.clear();
+            }
+            self.position += symbol.len_utf8();
+            match symbol {
+                '"' => return Ok(text),
+                '\\' => {
+                    let escaped = self.peek().ok_or_else(|| self.error("unterminated string"))?;
+                    self.position += 1;
+                    let unescaped = match escaped {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let digits = self.s.get(self.position..self.position + 4).ok_or_else(|| self.error("incomplete escape"))?;
+                            let unit = u16::from_str_radix(digits, 16).map_err(|_| self.error("invalid escape"))?;
+                            self.position += 4;
+                            units.push(unit);
+                            continue;
+                        },
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    if !units.is_empty() {
+                        text.push_str(&String::from_utf16_lossy(&units));
+                        units.clear();
+                    }
+                    text.push(unescaped);
+                },
+                _ => text.push(symbol),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, String> {
+        let start = self.position;
+        while matches!(self.peek(), Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')) {
+            self.position += 1;
+        }
+        self.s[start..self.position].parse().map(Json::Number).map_err(|_| self.error("invalid number"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_write_round_trip() {
+        let text = r#"{"name":"a \"quoted\"\nline","values":[1,2.5,-3],"flags":[true,false,null],"empty":{}}"#;
+        let json = Json::parse(text).unwrap();
+        assert_eq!(json.get("name").and_then(Json::as_str), Some("a \"quoted\"\nline"));
+        assert_eq!(json.get("values").and_then(Json::as_array).map(<[Json]>::len), Some(3));
+        assert_eq!(json.to_string(), text);
+        assert_eq!(Json::parse(" [ 1 , \"\\u00e9\" ] ").unwrap(), Json::Array(vec![Json::from(1), Json::from("é")]));
+    }
+
+    #[test]
+    fn malformed_documents_fail() {
+        for text in ["", "{", "[1,]", "{\"a\" 1}", "tru", "\"open", "1 2"] {
+            assert!(Json::parse(text).is_err(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! A Sudoku library providing a board representation,
+//! constraint verification, move enumeration, constraint propagation and solvers.
+//!
+//! With the `serde` feature, `Board`, `Sudoku`, `Variant`, `Move`, `Coord` and
+//! `SolveTrace` implement `Serialize` and `Deserialize`.
+//! The `examples` feature adds the `corpus` of example puzzles.
+
+mod board;
+mod candidate_set;
+mod collection;
+mod coord;
+mod difficulty;
+mod error;
+mod fingerprint;
+mod hint;
+mod json;
+mod moves;
+mod propagation;
+mod puzzle;
+mod rating;
+mod region;
+mod rng;
+mod scramble;
+mod search;
+mod sudoku;
+mod symbols;
+mod topology;
+
+pub mod constraint;
+#[cfg(feature = "examples")]
+pub mod corpus;
+pub mod export;
+pub mod formats;
+pub mod generator;
+pub mod samurai;
+pub mod sized;
+pub mod solver;
+pub mod strategies;
+pub mod variant;
+
+pub use board::{Board, Cell, CellChange, Slice, Value};
+pub use candidate_set::CandidateSet;
+pub use collection::PuzzleCollection;
+pub use constraint::Constraint;
+pub use coord::Coord;
+pub use difficulty::Difficulty;
+pub use error::Error;
+pub use fingerprint::Fingerprint;
+pub use hint::Hint;
+pub use moves::Move;
+pub use propagation::CandidateBoard;
+pub use puzzle::Puzzle;
+pub use rating::Rating;
+pub use samurai::SamuraiPuzzle;
+pub use sized::{BoardSpec, SizedBoard};
+pub use region::{BlockMut, BlockRef, ColumnMut, ColumnRef, RegionCells, RegionMut, RegionRef, RowMut, RowRef};
+pub use sudoku::Sudoku;
+pub use symbols::SymbolSet;
+pub use variant::{Arrow, Regions, Thermometer, Variant};
@@ -0,0 +1,71 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::board::Value;
+use crate::coord::Coord;
+use crate::error::Error;
+
+/// Placement of a value in a cell
+#[derive(Clone,Copy,Debug,PartialEq,Eq,PartialOrd,Ord,Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Move {
+    pub cell: Coord,
+    pub value: Value,
+}
+
+impl Move {
+    pub fn new(cell: Coord, value: Value) -> Self {
+        Self { cell, value }
+    }
+
+    /// The ID of the cell this move places its value in
+    pub fn cell_id(&self) -> usize {
+        self.cell.cell_id()
+    }
+}
+
+impl fmt::Display for Move {
+    /// Standard notation, e.g. `r4c5=7`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}={}", self.cell, self.value)
+    }
+}
+
+impl FromStr for Move {
+    type Err = Error;
+
+    /// Parse the standard notation `r4c5=7`, where `r4c5=0` clears the cell
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let error = |reason: &str| Error::ParseError { input: s.to_string(), reason: reason.to_string() };
+
+        let (cell, value) = s.split_once('=').ok_or_else(|| error("expected a move like r4c5=7"))?;
+        let cell: Coord = cell.parse()?;
+        let value: Value = value.trim().parse().map_err(|_| error("expected a value"))?;
+        if value > 9 {
+            return Err(error("expected a value from 0 to 9"));
+        }
+
+        Ok(Self { cell, value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        for notation in ["r1c1=1", "r4c5=7", "r9c9=9", "r4c5=0"] {
+            let placement: Move = notation.parse().unwrap();
+            assert_eq!(placement.to_string(), notation);
+        }
+        assert_eq!("R4C5=7".parse::<Move>().unwrap(), Move::new(Coord::new(3, 4), 7));
+    }
+
+    #[test]
+    fn parse_rejects_malformed_moves() {
+        for notation in ["r4c5", "r4c5=10", "r4c5=x", "r0c5=7", "r4c10=7"] {
+            assert!(notation.parse::<Move>().is_err(), "{notation}");
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use crate::board::{Board, Value};
+use crate::candidate_set::CandidateSet;
+use crate::error::Error;
+use crate::solver::trace::TraceEvent;
+use crate::topology::{HOUSES, PEERS};
+
+/// A change of a `CandidateBoard` recorded so that it can be undone
+#[derive(Clone,Copy,Debug)]
+enum Change {
+    /// A value was placed in the cell which had the given candidates before
+    Placement { cell_id: usize, candidates: CandidateSet },
+    Elimination { cell_id: usize, value: Value },
+}
+
+/// A `Board` together with the remaining candidate values of every unassigned cell.
+///
+/// Assignments through `CandidateBoard` keep the candidates consistent,
+/// i.e. a placed value is eliminated from all cells sharing a house with it.
+/// Any state which provably cannot be completed is reported as ``Error::Unsolvable``.
+///
+/// Every change is recorded on an undo stack, so a search can work on
+/// a single instance and return to an earlier `checkpoint` via `undo`.
+#[derive(Clone,Debug)]
+pub struct CandidateBoard {
+    board: Board,
+    candidates: [CandidateSet; Board::COUNT_VALUES],
+    changes: Vec<Change>,
+}
+
+impl CandidateBoard {
+    /// Determine the candidates of every unassigned cell of the given board
+    pub fn new(board: &Board) -> Self {
+        let mut candidates = [CandidateSet::empty(); Board::COUNT_VALUES];
+        for cell_id in board.unassigned() {
+            candidates[cell_id] = board.legal_values(cell_id);
+        }
+
+        Self { board: board.clone(), candidates, changes: vec![] }
+    }
+
+    /// The board with all assignments made so far
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// The remaining candidate values of the cell, empty for assigned cells
+    pub fn candidates(&self, cell_id: usize) -> CandidateSet {
+        self.candidates[cell_id]
+    }
+
+    /// Is every cell of the board assigned?
+    pub fn is_solved(&self) -> bool {
+        self.board.unassigned().is_empty()
+    }
+
+    /// Is there an unassigned cell without any candidate left?
+    pub fn is_contradictory(&self) -> bool {
+        self.board.unassigned().into_iter().any(|cell_id| self.candidates[cell_id].is_empty())
+    }
+
+    /// Place `value` in the cell and eliminate it from the candidates of all its peers.
+    /// Fails with ``Error::Unsolvable`` if some unassigned peer runs out of candidates.
+    pub fn assign(&mut self, cell_id: usize, value: Value) -> Result<(), Error> {
+        self.assign_observed(cell_id, value, &mut |_| {})
+    }
+
+    /// Like `assign`, but report every eliminated candidate to `observe`
+    pub(crate) fn assign_observed(&mut self, cell_id: usize, value: Value, observe: &mut dyn FnMut(TraceEvent)) -> Result<(), Error> {
+        self.board.place(cell_id, value);
+        self.changes.push(Change::Placement { cell_id, candidates: self.candidates[cell_id] });
+        self.candidates[cell_id] = CandidateSet::empty();
+
+        for peer_id in PEERS[cell_id] {
+            if self.candidates[peer_id].remove(value) {
+                self.changes.push(Change::Elimination { cell_id: peer_id, value });
+                observe(TraceEvent::elimination(peer_id, value));
+            }
+            if self.board[peer_id].value() == 0 && self.candidates[peer_id].is_empty() {
+                return Err(Error::Unsolvable);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove `value` from the candidates of the cell and return whether it was a candidate.
+    /// Fails with ``Error::Unsolvable`` if the cell runs out of candidates.
+    pub fn eliminate(&mut self, cell_id: usize, value: Value) -> Result<bool, Error> {
+        if !self.candidates[cell_id].remove(value) {
+            return Ok(false);
+        }
+        self.changes.push(Change::Elimination { cell_id, value });
+        if self.candidates[cell_id].is_empty() {
+            return Err(Error::Unsolvable);
+        }
+        Ok(true)
+    }
+
+    /// The current position on the undo stack to return to with `undo`
+    pub(crate) fn checkpoint(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Revert all placements and eliminations made since `checkpoint`
+    pub(crate) fn undo(&mut self, checkpoint: usize) {
+        while self.changes.len() > checkpoint {
+            match self.changes.pop() {
+                Some(Change::Placement { cell_id, candidates }) => {
+                    self.board.unplace(cell_id);
+                    self.candidates[cell_id] = candidates;
+                },
+                Some(Change::Elimination { cell_id, value }) => {
+                    self.candidates[cell_id].insert(value);
+                },
+                None => break,
+            }
+        }
+    }
+
+    /// Apply naked singles (a cell with only one candidate) and hidden singles
+    /// (a value with only one possible cell in a house) until a fixpoint is reached.
+    /// Returns the number of placements made or ``Error::Unsolvable``
+    /// if a contradiction was derived.
+    pub fn propagate(&mut self) -> Result<usize, Error> {
+        self.propagate_observed(&mut |_| {})
+    }
+
+    /// Like `propagate`, but report every placement and elimination to `observe`
+    pub(crate) fn propagate_observed(&mut self, observe: &mut dyn FnMut(TraceEvent)) -> Result<usize, Error> {
+        let mut placements = 0;
+
+        loop {
+            let mut progress = false;
+
+            for cell_id in 0..Board::COUNT_VALUES {
+                if self.board[cell_id].value() != 0 {
+                    continue;
+                }
+                if self.candidates[cell_id].is_empty() {
+                    return Err(Error::Unsolvable);
+                }
+                if let Some(value) = self.candidates[cell_id].single_value() {
+                    observe(TraceEvent::placement(cell_id, value));
+                    self.assign_observed(cell_id, value, observe)?;
+                    placements += 1;
+                    progress = true;
+                }
+            }
+
+            for cells in HOUSES {
+                for value in 1..=9 {
+                    if cells.iter().any(|cell_id| self.board[*cell_id].value() == value) {
+                        continue;
+                    }
+
+                    let mut positions = cells.iter().filter(|cell_id| self.candidates[**cell_id].contains(value));
+                    match (positions.next(), positions.next()) {
+                        (None, _) => return Err(Error::Unsolvable),
+                        (Some(cell_id), None) => {
+                            observe(TraceEvent::placement(*cell_id, value));
+                            self.assign_observed(*cell_id, value, observe)?;
+                            placements += 1;
+                            progress = true;
+                        },
+                        _ => {},
+                    }
+                }
+            }
+
+            if !progress {
+                return Ok(placements);
+            }
+        }
+    }
+}
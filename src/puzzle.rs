@@ -0,0 +1,135 @@
+use crate::board::Board;
+use crate::fingerprint::Fingerprint;
+use crate::sudoku::Sudoku;
+use crate::variant::Variant;
+
+/// A puzzle together with what is known about it beyond its values, as kept by
+/// file formats like `.sdk` and f-puzzles links: a title, the author, the source,
+/// the date, the difficulty and the rules of the variant it is played by.
+#[derive(Clone,Debug,Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Puzzle {
+    board: Board,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    title: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    author: Option<String>,
+    /// Where the puzzle was published, e.g. the name of a newspaper or a website
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    source: Option<String>,
+    /// The date of publication as given by the source, e.g. `2024-05-01`
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    date: Option<String>,
+    /// The difficulty as described by the author, e.g. `hard`
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    difficulty: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    variant: Variant,
+}
+
+impl Puzzle {
+    /// The classic puzzle of the board without any information about it
+    pub fn new(board: Board) -> Self {
+        Self { board, ..Self::default() }
+    }
+
+    /// This puzzle with the title
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// This puzzle with the author
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// This puzzle with the source it was published in
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// This puzzle with the date of publication
+    pub fn with_date(mut self, date: impl Into<String>) -> Self {
+        self.date = Some(date.into());
+        self
+    }
+
+    /// This puzzle with the difficulty described by the author
+    pub fn with_difficulty(mut self, difficulty: impl Into<String>) -> Self {
+        self.difficulty = Some(difficulty.into());
+        self
+    }
+
+    /// This puzzle played by the rules of `variant`
+    pub fn with_variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// This puzzle with another board, e.g. the state of a game played on it
+    pub fn with_board(mut self, board: Board) -> Self {
+        self.board = board;
+        self
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    pub fn date(&self) -> Option<&str> {
+        self.date.as_deref()
+    }
+
+    pub fn difficulty(&self) -> Option<&str> {
+        self.difficulty.as_deref()
+    }
+
+    /// The rules of this puzzle
+    pub fn variant(&self) -> &Variant {
+        &self.variant
+    }
+
+    /// The stable ID of this puzzle, the same for all puzzles which are equivalent
+    /// by relabeling the values, permuting rows and columns within bands and stacks,
+    /// permuting bands and stacks and transposing. Only the givens count.
+    /// Variants other than the classic one are only equal if they agree exactly.
+    pub fn fingerprint(&self) -> Fingerprint {
+        Fingerprint::of(&self.board, &self.variant)
+    }
+
+    /// A new game of this puzzle by its rules, starting from its board
+    pub fn to_sudoku(&self) -> Sudoku {
+        let mut sudoku = Sudoku::default().with_variant(self.variant.clone());
+        sudoku.init_board(&self.board);
+        sudoku
+    }
+}
+
+impl From<Board> for Puzzle {
+    fn from(board: Board) -> Self {
+        Self::new(board)
+    }
+}
+
+impl From<&Sudoku> for Puzzle {
+    /// The current board of the game with its rules.
+    /// Constraints added with `Sudoku::with_constraint` are left out.
+    fn from(sudoku: &Sudoku) -> Self {
+        Self::new(sudoku.board().clone()).with_variant(sudoku.variant().clone())
+    }
+}
@@ -0,0 +1,164 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::board::Board;
+use crate::difficulty::Difficulty;
+use crate::propagation::CandidateBoard;
+use crate::solver::SolveConfig;
+use crate::strategies::{self, Technique};
+use crate::sudoku::Sudoku;
+
+/// How hard a classic puzzle is for a human solver who never guesses,
+/// found by applying the simplest applicable technique until it is solved
+#[derive(Clone,Debug,PartialEq,Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rating {
+    pub difficulty: Difficulty,
+    /// The most advanced technique which was applied, `None` if none was
+    pub hardest: Option<Technique>,
+    /// How often each technique was applied, from the simplest to the most advanced
+    pub steps: Vec<(Technique, usize)>,
+    /// Did the techniques solve the puzzle completely?
+    pub solved: bool,
+    /// The sum of the weights of all steps, plus `GUESS_SCORE` if guessing is needed
+    pub score: u32,
+}
+
+impl Rating {
+    /// The score added for puzzles which the techniques do not solve
+    pub const GUESS_SCORE: u32 = 1000;
+
+    /// Rate the board. Techniques assuming a unique solution are only applied if it has one.
+    pub fn of(board: &Board) -> Self {
+        // ASSUME: without a deadline the rating always finishes
+        Self::rate(board, None).expect("rating without a deadline")
+    }
+
+    /// Rate the board like `of`, but give up with `None` once the timeout has passed
+    pub fn of_within(board: &Board, timeout: Duration) -> Option<Self> {
+        Self::rate(board, Some(Instant::now() + timeout))
+    }
+
+    fn rate(board: &Board, deadline: Option<Instant>) -> Option<Self> {
+        let mut sudoku = Sudoku::default();
+        sudoku.init_board(board);
+        let config = SolveConfig { timeout: deadline.map(|deadline| deadline.saturating_duration_since(Instant::now())), ..SolveConfig::default() };
+        let unique = sudoku.count_solutions_with(2, &config)? == 1;
+
+        let strategies: Vec<_> = strategies::all().into_iter().filter(|strategy| unique || !strategy.requires_uniqueness()).collect();
+        let mut grid = CandidateBoard::new(board);
+        let mut counts = [0; Technique::ALL.len()];
+        let solved = loop {
+            if grid.is_contradictory() {
+                break false;
+            }
+            if grid.is_solved() {
+                break true;
+            }
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return None;
+            }
+            let Some(deduction) = strategies::next_deduction(&grid, &strategies) else { break false };
+            if deduction.apply(&mut grid).is_err() {
+                break false;
+            }
+            counts[deduction.technique as usize] += 1;
+        };
+
+        let steps: Vec<(Technique, usize)> = Technique::ALL.into_iter().zip(counts).filter(|(_, count)| *count > 0).collect();
+        let hardest = steps.iter().map(|(technique, _)| *technique).max();
+        let mut score = steps.iter().map(|(technique, count)| weight(*technique) * *count as u32).sum();
+        if !solved {
+            score += Self::GUESS_SCORE;
+        }
+        let difficulty = match (unique, solved) {
+            (false, _) => Difficulty::Pathological,
+            (true, false) => Difficulty::Expert,
+            (true, true) => hardest.map_or(Difficulty::Easy, Difficulty::of_technique),
+        };
+        Some(Self { difficulty, hardest, steps, solved, score })
+    }
+
+    /// The total number of steps
+    pub fn step_count(&self) -> usize {
+        self.steps.iter().map(|(_, count)| count).sum()
+    }
+}
+
+impl fmt::Display for Rating {
+    /// E.g. `hard (score 312, hardest technique X-Wing, 64 steps)`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (score {}", self.difficulty, self.score)?;
+        if let Some(hardest) = self.hardest {
+            write!(f, ", hardest technique {hardest}")?;
+        }
+        write!(f, ", {} steps", self.step_count())?;
+        if !self.solved {
+            write!(f, ", guessing needed")?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// How much a step of the technique adds to the score, growing with how hard it is to spot
+fn weight(technique: Technique) -> u32 {
+    match technique {
+        Technique::NakedSingle => 1,
+        Technique::HiddenSingle => 2,
+        Technique::PointingCandidates | Technique::BoxLineReduction => 5,
+        Technique::NakedPair => 10,
+        Technique::HiddenPair => 12,
+        Technique::NakedTriple => 15,
+        Technique::HiddenTriple => 18,
+        Technique::NakedQuad => 25,
+        Technique::HiddenQuad => 30,
+        Technique::XWing => 30,
+        Technique::XyWing => 35,
+        Technique::Swordfish | Technique::XyzWing | Technique::WWing => 40,
+        Technique::UniqueRectangleType1 | Technique::BugPlusOne => 40,
+        Technique::SimpleColoring | Technique::UniqueRectangleType2 => 45,
+        Technique::Jellyfish => 50,
+        Technique::FinnedFish => 55,
+        Technique::AlsXz => 70,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "..75......6..2..4....3.65.22......8...8.3.1...9......79.32.7....7..9..5......89..";
+    const SOLUTION: &str = "127584639365921748489376512231769485758432196694815327943257861876193254512648973";
+
+    #[test]
+    fn singles_rate_easy() {
+        let cells: String = SOLUTION.chars().enumerate().map(|(cell_id, symbol)| if cell_id % 10 == 0 { '.' } else { symbol }).collect();
+        let rating = Rating::of(&cells.parse().unwrap());
+        assert_eq!((rating.difficulty, rating.solved, rating.step_count()), (Difficulty::Easy, true, 9));
+        assert_eq!(rating.steps, [(Technique::NakedSingle, 9)]);
+        assert_eq!(rating.score, 9);
+        assert_eq!(rating.to_string(), "easy (score 9, hardest technique Naked Single, 9 steps)");
+    }
+
+    #[test]
+    fn hard_puzzles_need_more_techniques() {
+        let rating = Rating::of(&PUZZLE.parse().unwrap());
+        assert!(rating.difficulty > Difficulty::Easy);
+        assert!(rating.hardest > Some(Technique::HiddenSingle));
+        assert_eq!(rating.score >= Rating::GUESS_SCORE, !rating.solved);
+        assert!(rating.steps.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn several_solutions_are_pathological() {
+        let rating = Rating::of(&Board::default());
+        assert_eq!((rating.difficulty, rating.solved, rating.hardest), (Difficulty::Pathological, false, None));
+        assert_eq!(rating.score, Rating::GUESS_SCORE);
+        assert!(rating.to_string().ends_with("guessing needed)"));
+    }
+
+    #[test]
+    fn no_time_gives_no_rating() {
+        assert_eq!(Rating::of_within(&PUZZLE.parse().unwrap(), Duration::ZERO), None);
+    }
+}
@@ -0,0 +1,206 @@
+//! Borrowed views on the rows, columns and blocks of a board.
+//!
+//! Unlike a `Slice`, a `RegionRef` does not copy the 9 cells. It reads them
+//! from the board whenever they are accessed, so taking a view is free.
+//! A `RegionMut` additionally writes whole regions at once.
+
+use std::ops::Index;
+
+use crate::board::{Board, Cell, Slice, Value};
+use crate::candidate_set::CandidateSet;
+use crate::coord::Coord;
+use crate::error::Error;
+use crate::topology::HOUSES;
+
+/// A row, column or block of a board without copying its cells
+#[derive(Clone,Copy,Debug)]
+pub struct RegionRef<'a> {
+    board: &'a Board,
+    cell_ids: &'static [usize; 9],
+}
+
+/// A row of a board without copying its cells
+pub type RowRef<'a> = RegionRef<'a>;
+/// A column of a board without copying its cells
+pub type ColumnRef<'a> = RegionRef<'a>;
+/// A block of a board without copying its cells
+pub type BlockRef<'a> = RegionRef<'a>;
+
+impl<'a> RegionRef<'a> {
+    /// The view of a house given an identifier from 0 to 26 (see `topology::HOUSES`)
+    pub(crate) fn new(board: &'a Board, house_id: usize) -> Self {
+        Self { board, cell_ids: &HOUSES[house_id] }
+    }
+
+    /// The IDs of the cells in this region in order
+    pub fn cell_ids(&self) -> &'static [usize; 9] {
+        self.cell_ids
+    }
+
+    /// Iterate over the cells of this region in order
+    pub fn iter(&self) -> RegionCells<'a> {
+        RegionCells { board: self.board, cell_ids: self.cell_ids.iter() }
+    }
+
+    /// Does this region contain the provided `Value`?
+    pub fn has(&self, value: Value) -> bool {
+        self.iter().any(|cell| cell.value() == value)
+    }
+
+    /// Does a cell other than the one at `index` contain the provided `Value`?
+    pub(crate) fn has_besides(&self, value: Value, index: usize) -> bool {
+        self.iter().enumerate().any(|(i, cell)| i != index && cell.value() == value)
+    }
+
+    /// Are the admissible Sudoku values inside the cells unique?
+    pub fn has_unique_sudoku_values(&self) -> bool {
+        let mut seen = CandidateSet::empty();
+        // NOTE: consider only admissible values
+        self.iter().all(|cell| !(1..=9).contains(&cell.value()) || seen.insert(cell.value()))
+    }
+
+    /// Which Sudoku values are used in this region?
+    pub fn sudoku_values(&self) -> CandidateSet {
+        self.iter().map(|cell| cell.value()).collect()
+    }
+
+    /// Which Sudoku values are unused in this region?
+    pub fn unused_sudoku_values(&self) -> CandidateSet {
+        !self.sudoku_values()
+    }
+
+    /// Copy the cells of this region into a `Slice`
+    pub fn to_slice(&self) -> Slice {
+        let mut slice = Slice::default();
+        for (i, cell) in self.iter().enumerate() {
+            slice.set(i, cell);
+        }
+        slice
+    }
+}
+
+impl Index<usize> for RegionRef<'_> {
+    type Output = Cell;
+
+    fn index(&self, index: usize) -> &Cell {
+        &self.board[self.cell_ids[index]]
+    }
+}
+
+impl<'a> IntoIterator for RegionRef<'a> {
+    type Item = Cell;
+    type IntoIter = RegionCells<'a>;
+
+    fn into_iter(self) -> RegionCells<'a> {
+        self.iter()
+    }
+}
+
+/// Iterator over the cells of a `RegionRef`
+#[derive(Clone,Debug)]
+pub struct RegionCells<'a> {
+    board: &'a Board,
+    cell_ids: std::slice::Iter<'static, usize>,
+}
+
+impl Iterator for RegionCells<'_> {
+    type Item = Cell;
+
+    fn next(&mut self) -> Option<Cell> {
+        self.cell_ids.next().map(|cell_id| self.board[*cell_id])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cell_ids.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for RegionCells<'_> {
+    fn next_back(&mut self) -> Option<Cell> {
+        self.cell_ids.next_back().map(|cell_id| self.board[*cell_id])
+    }
+}
+
+impl ExactSizeIterator for RegionCells<'_> {}
+
+/// A row, column or block of a board which can be written to
+#[derive(Debug)]
+pub struct RegionMut<'a> {
+    board: &'a mut Board,
+    cell_ids: &'static [usize; 9],
+}
+
+/// A row of a board which can be written to
+pub type RowMut<'a> = RegionMut<'a>;
+/// A column of a board which can be written to
+pub type ColumnMut<'a> = RegionMut<'a>;
+/// A block of a board which can be written to
+pub type BlockMut<'a> = RegionMut<'a>;
+
+impl<'a> RegionMut<'a> {
+    /// The mutable view of a house given an identifier from 0 to 26 (see `topology::HOUSES`)
+    pub(crate) fn new(board: &'a mut Board, house_id: usize) -> Self {
+        Self { board, cell_ids: &HOUSES[house_id] }
+    }
+
+    /// The IDs of the cells in this region in order
+    pub fn cell_ids(&self) -> &'static [usize; 9] {
+        self.cell_ids
+    }
+
+    /// Read-only view of this region
+    pub fn as_ref(&self) -> RegionRef<'_> {
+        RegionRef { board: self.board, cell_ids: self.cell_ids }
+    }
+
+    /// Iterate over the cells of this region in order
+    pub fn iter(&self) -> RegionCells<'_> {
+        self.as_ref().iter()
+    }
+
+    /// Store `value` in the cell at `index` of this region (0 clears the cell).
+    /// Fails like `Board::try_set` and leaves the board unchanged.
+    pub fn set(&mut self, index: usize, value: Value) -> Result<(), Error> {
+        if index >= self.cell_ids.len() {
+            return Err(Error::IdError { admissible: 0..self.cell_ids.len(), actual: index });
+        }
+        self.board.try_set(self.cell_ids[index], value)
+    }
+
+    /// Store the values in the cells of this region in order (0 clears a cell).
+    /// Either all values are written or, if a value lies outside of `0..=9` or
+    /// a cell holds a given, none is and the corresponding error is returned.
+    /// Like `Board::try_set`, this does not check the Sudoku constraints.
+    pub fn fill(&mut self, values: &[Value; 9]) -> Result<(), Error> {
+        for (cell_id, value) in self.cell_ids.iter().zip(values) {
+            if *value > 9 {
+                return Err(Error::ValueError { value: *value, expected: "0 (unassigned) or 1..=9".to_string() });
+            }
+            if self.board.is_given(*cell_id) {
+                return Err(Error::GivenError { cell: Coord::from_cell_id(*cell_id) });
+            }
+        }
+
+        for (cell_id, value) in self.cell_ids.iter().zip(values) {
+            self.board.place(*cell_id, *value);
+        }
+        Ok(())
+    }
+
+    /// Clear all cells of this region which don't hold a given
+    pub fn clear(&mut self) {
+        for cell_id in self.cell_ids.iter() {
+            if !self.board.is_given(*cell_id) {
+                self.board.place(*cell_id, 0);
+            }
+        }
+    }
+}
+
+impl Index<usize> for RegionMut<'_> {
+    type Output = Cell;
+
+    fn index(&self, index: usize) -> &Cell {
+        &self.board[self.cell_ids[index]]
+    }
+}
@@ -0,0 +1,43 @@
+//! A small seedable pseudo-random number generator (SplitMix64).
+//!
+//! It is not suitable for cryptography, but fast, dependency-free and
+//! reproducible: the same seed always yields the same sequence.
+
+/// Pseudo-random number generator given a 64-bit seed
+#[derive(Clone,Debug)]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// The next 64 pseudo-random bits
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A pseudo-random number in `0..bound`. `bound` must not be zero.
+    pub(crate) fn below(&mut self, bound: usize) -> usize {
+        // NOTE: multiply-shift maps the 64 bits onto the range without a modulo
+        ((self.next_u64() as u128 * bound as u128) >> 64) as usize
+    }
+
+    /// Shuffle the items uniformly (Fisher-Yates)
+    pub(crate) fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+
+    /// A pseudo-randomly chosen item or `None` if there is none
+    pub(crate) fn choose<T: Copy>(&mut self, items: &[T]) -> Option<T> {
+        if items.is_empty() { None } else { Some(items[self.below(items.len())]) }
+    }
+}
@@ -0,0 +1,320 @@
+//! Samurai Sudoku: five classic grids overlapping at their corner blocks.
+//!
+//! The grids lie on a 21×21 layout. The center grid shares each of its corner
+//! blocks with the opposite corner block of one of the four outer grids. Every
+//! cell of the layout is stored once, so a value placed in an overlap belongs
+//! to both grids at the same time. Verifying and solving consider the rows,
+//! columns and blocks of all five grids together with the generic search in `search`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::board::{Board, Value};
+use crate::candidate_set::CandidateSet;
+use crate::coord::Coord;
+use crate::error::Error;
+use crate::search::{Search, Topology};
+
+/// Names of the grids in the order of `SamuraiPuzzle::GRID_ORIGINS`
+const GRID_NAMES: [&str; SamuraiPuzzle::COUNT_GRIDS] = ["top-left", "top-right", "center", "bottom-left", "bottom-right"];
+
+/// Five 9×9 grids overlapping at corner blocks. Cells are addressed by their
+/// zero-based row and column on the 21×21 layout or by a grid and a row and
+/// column within it. The cells store the values (0 means unassigned).
+#[derive(Clone,Debug,PartialEq,Eq,Hash)]
+pub struct SamuraiPuzzle {
+    /// The values of all cells of the layout in reading order, 0 outside of the grids
+    cells: Vec<Value>,
+}
+
+impl SamuraiPuzzle {
+    /// Number of rows and columns of the layout
+    pub const SIZE: usize = 21;
+    pub const COUNT_GRIDS: usize = 5;
+    /// Number of cells in the grids, counting the shared ones once
+    pub const COUNT_CELLS: usize = Self::COUNT_GRIDS * Board::COUNT_VALUES - 4 * 9;
+    /// Row and column of the top-left cell of every grid on the layout:
+    /// top-left, top-right, center, bottom-left and bottom-right
+    pub const GRID_ORIGINS: [(usize, usize); Self::COUNT_GRIDS] = [(0, 0), (0, 12), (6, 6), (12, 0), (12, 12)];
+
+    /// The puzzle with all cells unassigned
+    pub fn new() -> Self {
+        Self { cells: vec![0; Self::SIZE * Self::SIZE] }
+    }
+
+    /// Does the cell of the layout belong to one of the grids?
+    pub fn contains(row: usize, col: usize) -> bool {
+        !Self::grids_of(row, col).is_empty()
+    }
+
+    /// The grids containing the cell of the layout: two for the cells of shared blocks,
+    /// one for the other cells of the grids and none for the cells between the grids
+    pub fn grids_of(row: usize, col: usize) -> Vec<usize> {
+        (0..Self::COUNT_GRIDS)
+            .filter(|grid| {
+                let (top, left) = Self::GRID_ORIGINS[*grid];
+                (top..top + Board::COUNT_ROWS).contains(&row) && (left..left + Board::COUNT_COLUMNS).contains(&col)
+            })
+            .collect()
+    }
+
+    /// The value of the cell of the layout (0 means unassigned).
+    /// Panics unless the cell belongs to one of the grids.
+    pub fn get(&self, row: usize, col: usize) -> Value {
+        assert!(Self::contains(row, col), "cell (row {row}, column {col}) is outside of the grids");
+        self.cells[row * Self::SIZE + col]
+    }
+
+    /// Store `value` in the cell of the layout (0 clears the cell) after checking
+    /// the cell and the value. Fails with ``Error::LayoutError`` for cells outside
+    /// of the grids or ``Error::ValueError``. This does not check the Sudoku constraints.
+    pub fn try_set(&mut self, row: usize, col: usize, value: Value) -> Result<(), Error> {
+        if !Self::contains(row, col) {
+            return Err(Error::LayoutError { reason: format!("cell {} lies outside of the grids", Coord::new(row, col)) });
+        }
+        if value > 9 {
+            return Err(Error::ValueError { value, expected: "0 (unassigned) or 1..=9".to_string() });
+        }
+        self.cells[row * Self::SIZE + col] = value;
+        Ok(())
+    }
+
+    /// Store `value` in the cell at the row and column within the grid, which
+    /// also changes the other grid if the cell lies in a shared block.
+    /// Fails like `try_set` and with ``Error::IdError`` for positions outside of the grid.
+    pub fn try_set_in_grid(&mut self, grid: usize, row: usize, col: usize, value: Value) -> Result<(), Error> {
+        if grid >= Self::COUNT_GRIDS {
+            return Err(Error::IdError { admissible: 0..Self::COUNT_GRIDS, actual: grid });
+        }
+        if row >= Board::COUNT_ROWS || col >= Board::COUNT_COLUMNS {
+            return Err(Error::IdError { admissible: 0..Board::COUNT_ROWS, actual: row.max(col) });
+        }
+        let (top, left) = Self::GRID_ORIGINS[grid];
+        self.try_set(top + row, left + col, value)
+    }
+
+    /// The values of the grid as a classic board. All assigned cells become givens.
+    /// Panics unless `grid` is below `COUNT_GRIDS`.
+    pub fn grid(&self, grid: usize) -> Board {
+        let (top, left) = Self::GRID_ORIGINS[grid];
+        let mut values = [0; Board::COUNT_VALUES];
+        for (cell_id, value) in values.iter_mut().enumerate() {
+            *value = self.cells[(top + cell_id / 9) * Self::SIZE + left + cell_id % 9];
+        }
+        Board::from_flattened_values(&values)
+    }
+
+    /// Is every cell of the grids assigned?
+    pub fn is_complete(&self) -> bool {
+        layout_ids().iter().all(|layout_id| self.cells[*layout_id] != 0)
+    }
+
+    /// The values which can be placed in the unassigned cell of the layout without
+    /// repeating a value of a row, column or block of the grids containing it.
+    /// Empty for assigned cells and cells outside of the grids.
+    pub fn legal_values(&self, row: usize, col: usize) -> CandidateSet {
+        if !Self::contains(row, col) || self.cells[row * Self::SIZE + col] != 0 {
+            return CandidateSet::empty();
+        }
+        let used = houses().into_iter()
+            .map(|(_, house)| house)
+            .filter(|house| house.contains(&(row * Self::SIZE + col)))
+            .flatten()
+            .map(|layout_id| self.cells[layout_id])
+            .collect::<CandidateSet>();
+        !used
+    }
+
+    /// Does the puzzle satisfy the Sudoku constraints of all grids? If not, returns
+    /// a ``Error::ConflictError`` naming the first house with a repeated value,
+    /// e.g. `row 3 of the top-left grid`, and the cells as positions on the layout.
+    pub fn verify(&self) -> Result<(), Error> {
+        for (region, house) in houses() {
+            for (i, layout_id) in house.iter().enumerate() {
+                let value = self.cells[*layout_id];
+                let repeated: Vec<usize> = house[i..].iter().copied().filter(|other| self.cells[*other] == value).collect();
+                if value != 0 && repeated.len() > 1 {
+                    let cells = repeated.into_iter().map(|layout_id| Coord::new(layout_id / Self::SIZE, layout_id % Self::SIZE)).collect();
+                    return Err(Error::ConflictError { region, value, cells });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Solve all grids at once by depth-first backtracking on the cell with the fewest
+    /// candidates. Returns ``Error::Unsolvable`` if there is no solution and the error
+    /// of `verify` if the puzzle already violates a constraint.
+    pub fn solve(&self) -> Result<SamuraiPuzzle, Error> {
+        self.verify()?;
+        let layout_ids = layout_ids();
+        let mut solution = None;
+        Search::new(&topology(), &self.search_cells()).run(&mut |cells| {
+            let mut solved = self.clone();
+            for (layout_id, value) in layout_ids.iter().zip(cells) {
+                solved.cells[*layout_id] = *value;
+            }
+            solution = Some(solved);
+            false
+        });
+        solution.ok_or(Error::Unsolvable)
+    }
+
+    /// Count the solutions of the puzzle, but stop as soon as `limit` solutions are found.
+    /// A puzzle which already violates a constraint has no solutions.
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        let mut count = 0;
+        if limit == 0 || self.verify().is_err() {
+            return count;
+        }
+        Search::new(&topology(), &self.search_cells()).run(&mut |_| {
+            count += 1;
+            count < limit
+        });
+        count
+    }
+
+    /// Does the puzzle have exactly one solution?
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == 1
+    }
+
+    /// The values of the cells of the grids in the order of `layout_ids`
+    fn search_cells(&self) -> Vec<Value> {
+        layout_ids().into_iter().map(|layout_id| self.cells[layout_id]).collect()
+    }
+}
+
+impl Default for SamuraiPuzzle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Positions of the cells of the grids on the layout (`row * SIZE + col`) in reading order
+fn layout_ids() -> Vec<usize> {
+    let size = SamuraiPuzzle::SIZE;
+    (0..size * size).filter(|layout_id| SamuraiPuzzle::contains(layout_id / size, layout_id % size)).collect()
+}
+
+/// The rows, columns and blocks of all grids with their descriptions as positions on the layout.
+/// Each shared block occurs twice, once for each of its grids.
+fn houses() -> Vec<(String, Vec<usize>)> {
+    let mut houses = vec![];
+    for (grid, (top, left)) in SamuraiPuzzle::GRID_ORIGINS.iter().enumerate() {
+        let layout_id = |row: usize, col: usize| (top + row) * SamuraiPuzzle::SIZE + left + col;
+        let name = GRID_NAMES[grid];
+        for i in 0..9 {
+            houses.push((format!("row {} of the {name} grid", i + 1), (0..9).map(|col| layout_id(i, col)).collect()));
+            houses.push((format!("column {} of the {name} grid", i + 1), (0..9).map(|row| layout_id(row, i)).collect()));
+            houses.push((format!("block {} of the {name} grid", i + 1), (0..9).map(|j| layout_id(i / 3 * 3 + j / 3, i % 3 * 3 + j % 3)).collect()));
+        }
+    }
+    houses
+}
+
+/// The houses of `houses` for the generic search, in which the cells are numbered as in `layout_ids`
+fn topology() -> Topology {
+    let layout_ids = layout_ids();
+    let search_id = |layout_id: &usize| layout_ids.binary_search(layout_id).unwrap();
+    let houses = houses().into_iter().map(|(_, house)| house.iter().map(search_id).collect()).collect();
+    Topology::with_cells(layout_ids.len(), Board::COUNT_ROWS, houses)
+}
+
+impl FromStr for SamuraiPuzzle {
+    type Err = Error;
+
+    /// Parse the 369 cells of the grids in reading order of the layout. Values are
+    /// digits, blanks are `.` or `0`, and whitespace is ignored. So both the layout
+    /// of `Display` with spaces between the grids and a single line of 369 symbols work.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let error = |reason: String| Error::ParseError { input: s.to_string(), reason };
+
+        let mut values = vec![];
+        for symbol in s.chars().filter(|symbol| !symbol.is_whitespace()) {
+            match symbol {
+                '.' | '0' => values.push(0),
+                '1'..='9' => values.push(symbol as Value - b'0'),
+                _ => return Err(error(format!("unexpected character {symbol:?}"))),
+            }
+        }
+        if values.len() != Self::COUNT_CELLS {
+            return Err(error(format!("expected {} cells but got {}", Self::COUNT_CELLS, values.len())));
+        }
+
+        let mut puzzle = Self::new();
+        for (layout_id, value) in layout_ids().into_iter().zip(values) {
+            puzzle.cells[layout_id] = value;
+        }
+        Ok(puzzle)
+    }
+}
+
+impl fmt::Display for SamuraiPuzzle {
+    /// The 21 rows of the layout with `.` for unassigned cells and spaces between the grids
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in 0..Self::SIZE {
+            let line: String = (0..Self::SIZE)
+                .map(|col| match self.cells[row * Self::SIZE + col] {
+                    _ if !Self::contains(row, col) => ' ',
+                    0 => '.',
+                    value => (b'0' + value) as char,
+                })
+                .collect();
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sized::SizedBoard;
+
+    #[test]
+    fn the_layout() {
+        assert_eq!(layout_ids().len(), SamuraiPuzzle::COUNT_CELLS);
+        assert_eq!(SamuraiPuzzle::grids_of(7, 7), [0, 2]);
+        assert_eq!(SamuraiPuzzle::grids_of(10, 10), [2]);
+        assert!(!SamuraiPuzzle::contains(0, 10));
+        assert!(!SamuraiPuzzle::contains(10, 0));
+    }
+
+    #[test]
+    fn shared_blocks_belong_to_both_grids() {
+        let mut puzzle = SamuraiPuzzle::new();
+        puzzle.try_set_in_grid(0, 8, 8, 5).unwrap();
+        assert_eq!(puzzle.get(8, 8), 5);
+        assert_eq!(puzzle.grid(2)[(2, 2)].value(), 5);
+        assert!(!puzzle.legal_values(8, 14).contains(5));
+        assert!(!puzzle.legal_values(2, 8).contains(5));
+        assert!(puzzle.legal_values(2, 14).contains(5));
+        assert!(matches!(puzzle.try_set(0, 10, 1), Err(Error::LayoutError { .. })));
+        assert!(matches!(puzzle.try_set_in_grid(5, 0, 0, 1), Err(Error::IdError { .. })));
+        assert!(matches!(puzzle.try_set(0, 0, 10), Err(Error::ValueError { .. })));
+    }
+
+    #[test]
+    fn conflicts_across_grids() {
+        let mut puzzle = SamuraiPuzzle::new();
+        puzzle.try_set(8, 6, 4).unwrap();
+        puzzle.try_set(8, 14, 4).unwrap();
+        let Err(Error::ConflictError { region, value: 4, .. }) = puzzle.verify() else { panic!() };
+        assert_eq!(region, "row 3 of the center grid");
+        assert_eq!(puzzle.count_solutions(1), 0);
+    }
+
+    #[test]
+    fn solve_and_round_trip() {
+        let solution = SamuraiPuzzle::new().solve().unwrap();
+        assert!(solution.is_complete());
+        solution.verify().unwrap();
+        assert!((0..SamuraiPuzzle::COUNT_GRIDS).all(|grid| SizedBoard::from(&solution.grid(grid)).verify().is_ok()));
+        assert!(solution.has_unique_solution());
+        assert_eq!(solution.to_string().parse::<SamuraiPuzzle>().unwrap(), solution);
+        let line: String = solution.to_string().split_whitespace().collect();
+        assert_eq!(line.parse::<SamuraiPuzzle>().unwrap(), solution);
+        assert!(line[1..].parse::<SamuraiPuzzle>().is_err());
+    }
+}
@@ -0,0 +1,80 @@
+//! Scrambling a classic puzzle into an equivalent one which looks different:
+//! the values are relabeled, the rows within a band, the bands, the columns
+//! within a stack and the stacks are permuted, and the board may be transposed.
+//! The scrambled puzzle has the same `Fingerprint` and the same solving path.
+
+use crate::board::{Board, Value};
+use crate::rng::Rng;
+
+impl Board {
+    /// A pseudo-random equivalent of the board under the classic rules, the same for
+    /// the same seed. Givens stay givens and entries stay entries, but notes are dropped.
+    pub fn scrambled(&self, seed: u64) -> Board {
+        let mut rng = Rng::new(seed);
+        let mut labels: [Value; 10] = std::array::from_fn(|value| value as Value);
+        rng.shuffle(&mut labels[1..]);
+        let rows = line_order(&mut rng);
+        let columns = line_order(&mut rng);
+        let transposed = rng.below(2) == 1;
+
+        let (mut values, mut givens) = ([0; Board::COUNT_VALUES], [0; Board::COUNT_VALUES]);
+        for cell_id in 0..Board::COUNT_VALUES {
+            let (row, col) = match transposed {
+                true => (cell_id % 9, cell_id / 9),
+                false => (cell_id / 9, cell_id % 9),
+            };
+            let source_id = rows[row] * 9 + columns[col];
+            values[cell_id] = labels[self[source_id].value() as usize];
+            if self.is_given(source_id) {
+                givens[cell_id] = values[cell_id];
+            }
+        }
+        Board::from_flattened_values(&givens).with_values(&values)
+    }
+}
+
+/// A random order of the 9 rows or columns which keeps those of a band or stack together
+fn line_order(rng: &mut Rng) -> [usize; 9] {
+    let mut groups = [0, 1, 2];
+    rng.shuffle(&mut groups);
+    let mut within = [[0, 1, 2]; 3];
+    for lines in within.iter_mut() {
+        rng.shuffle(lines);
+    }
+    std::array::from_fn(|i| 3 * groups[i / 3] + within[i / 3][i % 3])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::Board;
+    use crate::puzzle::Puzzle;
+
+    const PUZZLE: &str = "..75......6..2..4....3.65.22......8...8.3.1...9......79.32.7....7..9..5......89..";
+
+    #[test]
+    fn scrambled_puzzles_are_equivalent() {
+        let board: Board = PUZZLE.parse().unwrap();
+        let expected = Puzzle::new(board.clone()).fingerprint();
+        for seed in 0..5 {
+            let scrambled = board.scrambled(seed);
+            assert_eq!(Puzzle::new(scrambled.clone()).fingerprint(), expected, "{seed}");
+            assert_eq!(scrambled.givens().len(), board.givens().len());
+        }
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_board() {
+        let board: Board = PUZZLE.parse().unwrap();
+        assert_eq!(board.scrambled(7).values(), board.scrambled(7).values());
+        assert_ne!(board.scrambled(7).values(), board.scrambled(8).values());
+    }
+
+    #[test]
+    fn entries_stay_entries() {
+        let mut board: Board = PUZZLE.parse().unwrap();
+        board.try_set(0, 1).unwrap();
+        let scrambled = board.scrambled(3);
+        assert_eq!((0..Board::COUNT_VALUES).filter(|cell_id| scrambled[*cell_id].value() != 0).count(), board.givens().len() + 1);
+        assert_eq!(scrambled.givens().len(), board.givens().len());
+    }
+}
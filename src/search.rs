@@ -0,0 +1,395 @@
+//! Backtracking search on boards with any number of values and any houses.
+//!
+//! Unlike the solvers in `solver`, which rely on the fixed topology of the
+//! classic board, this search takes the houses (sets of cells holding
+//! distinct values) at runtime. It propagates naked and hidden singles after
+//! every guess and branches on the cell with the fewest candidates.
+//! Besides houses, a topology may relate cells which must not hold
+//! consecutive values and cells along thermometers holding increasing values.
+//! Given an `Rng`, the search tries the candidates of a cell in random order.
+
+use std::time::Instant;
+
+use crate::board::Value;
+use crate::rng::Rng;
+use crate::solver::progress::ProgressTracker;
+
+/// The cells and houses a search operates on
+#[derive(Clone,Debug)]
+pub(crate) struct Topology {
+    /// Number of values, i.e. values are `1..=size`
+    pub(crate) size: usize,
+    /// Sets of cells which must hold distinct values and, as far as they have
+    /// `size` cells, all values
+    pub(crate) houses: Vec<Vec<usize>>,
+    /// For every cell the cells which must hold a different value in increasing order
+    pub(crate) peers: Vec<Vec<usize>>,
+    /// For every cell the cells which must not hold a value one less or one more
+    pub(crate) non_consecutive: Vec<Vec<usize>>,
+    /// Sequences of cells, starting at the bulb, which must hold strictly increasing values
+    pub(crate) thermometers: Vec<Vec<usize>>,
+    /// For every cell the thermometers through it with the position of the cell on them
+    on_thermometers: Vec<Vec<(usize, usize)>>,
+}
+
+impl Topology {
+    /// The topology of `size * size` cells whose peers are the cells sharing a house
+    pub(crate) fn from_houses(size: usize, houses: Vec<Vec<usize>>) -> Self {
+        Self::with_cells(size * size, size, houses)
+    }
+
+    /// Like `from_houses`, but for any number of cells, e.g. the cells of overlapping grids
+    pub(crate) fn with_cells(count_cells: usize, size: usize, houses: Vec<Vec<usize>>) -> Self {
+        let mut peers = vec![vec![]; count_cells];
+        for house in houses.iter() {
+            for cell_id in house.iter().copied() {
+                peers[cell_id].extend(house.iter().copied().filter(|peer_id| *peer_id != cell_id));
+            }
+        }
+        for cell_peers in peers.iter_mut() {
+            cell_peers.sort_unstable();
+            cell_peers.dedup();
+        }
+        let non_consecutive = vec![vec![]; count_cells];
+        Self { size, houses, peers, non_consecutive, thermometers: vec![], on_thermometers: vec![vec![]; count_cells] }
+    }
+
+    /// Make the cells peers of `cell_id`, which must then hold a different value
+    pub(crate) fn add_peers(&mut self, cell_id: usize, peers: impl IntoIterator<Item = usize>) {
+        self.peers[cell_id].extend(peers.into_iter().filter(|peer_id| *peer_id != cell_id));
+        self.peers[cell_id].sort_unstable();
+        self.peers[cell_id].dedup();
+    }
+
+    /// Make the cells hold values which are not consecutive to the value of `cell_id`
+    pub(crate) fn add_non_consecutive(&mut self, cell_id: usize, cells: impl IntoIterator<Item = usize>) {
+        self.non_consecutive[cell_id].extend(cells.into_iter().filter(|other_id| *other_id != cell_id));
+        self.non_consecutive[cell_id].sort_unstable();
+        self.non_consecutive[cell_id].dedup();
+    }
+
+    /// Make the cells, starting at the bulb, hold strictly increasing values
+    pub(crate) fn add_thermometer(&mut self, cells: Vec<usize>) {
+        for (position, cell_id) in cells.iter().enumerate() {
+            self.on_thermometers[*cell_id].push((self.thermometers.len(), position));
+        }
+        self.thermometers.push(cells);
+    }
+
+    /// The values the cell can hold given its positions on thermometers
+    fn thermometer_bounds(&self, cell_id: usize) -> u32 {
+        self.on_thermometers[cell_id].iter().fold(self.all_values(), |values, (thermometer_id, position)| {
+            let length = self.thermometers[*thermometer_id].len() as isize;
+            let position = *position as isize;
+            values & values_between(position + 1, self.size as isize - (length - 1 - position))
+        })
+    }
+
+    /// Bit `v` is set for every value `v`
+    fn all_values(&self) -> u32 {
+        ((1u32 << self.size) - 1) << 1
+    }
+}
+
+/// Backtracking search propagating naked and hidden singles after every guess
+pub(crate) struct Search<'a> {
+    topology: &'a Topology,
+    cells: Vec<Value>,
+    /// Bit `v` is set if value `v` does not occur among the peers of an unassigned cell
+    candidates: Vec<u32>,
+    /// Candidates of cells as they were before eliminations, to undo them
+    trail: Vec<(usize, u32)>,
+    /// Cells assigned by the search in order, to undo the assignments
+    assigned: Vec<usize>,
+    /// Number of search tree nodes visited so far
+    pub(crate) nodes: u64,
+    /// Number of guesses undone so far
+    pub(crate) backtracks: u64,
+    /// Number of values placed by propagating singles so far
+    pub(crate) propagations: u64,
+    /// Number of guesses leading to the current node
+    depth: usize,
+    pub(crate) max_depth: usize,
+    pub(crate) node_limit: Option<u64>,
+    pub(crate) deadline: Option<Instant>,
+    /// Set once the search stopped because the node limit or the deadline was exceeded
+    pub(crate) aborted: bool,
+    /// Rules beyond the topology: given the cells, remove candidates they forbid
+    /// (bit `v` stands for value `v`) or return `false` if the cells violate them
+    pub(crate) prune: Option<PruneHook<'a>>,
+    /// Try the candidates of a cell in pseudo-random order instead of increasing order
+    pub(crate) rng: Option<Rng>,
+    /// Reports the progress of the search every now and then
+    pub(crate) progress: Option<ProgressTracker<'a>>,
+    started: Instant,
+}
+
+/// A prune hook of a `Search`, see `Search::prune`
+pub(crate) type PruneHook<'a> = Box<dyn Fn(&[Value], &mut [u32]) -> bool + 'a>;
+
+impl<'a> Search<'a> {
+    /// Prepare the search on the cells (0 means unassigned).
+    /// The assigned cells must not violate any house.
+    pub(crate) fn new(topology: &'a Topology, cells: &[Value]) -> Self {
+        let all = topology.all_values();
+        let candidates = (0..cells.len())
+            .map(|cell_id| match cells[cell_id] {
+                0 => {
+                    let candidates = topology.peers[cell_id].iter().fold(all, |candidates, peer_id| candidates & !(1 << cells[*peer_id]));
+                    let candidates = topology.non_consecutive[cell_id].iter().fold(candidates, |candidates, other_id| candidates & !consecutive(cells[*other_id]));
+                    topology.on_thermometers[cell_id].iter().fold(candidates & topology.thermometer_bounds(cell_id), |candidates, (thermometer_id, position)| {
+                        let thermometer = &topology.thermometers[*thermometer_id];
+                        thermometer.iter().enumerate()
+                            .filter(|(_, other_id)| cells[**other_id] != 0)
+                            .fold(candidates, |candidates, (other_position, other_id)| candidates & ordered(cells[*other_id], other_position, *position))
+                    })
+                },
+                _ => 0,
+            })
+            .collect();
+        Self {
+            topology, cells: cells.to_vec(), candidates, trail: vec![], assigned: vec![],
+            nodes: 0, backtracks: 0, propagations: 0, depth: 0, max_depth: 0, node_limit: None, deadline: None, aborted: false, prune: None, rng: None, progress: None, started: Instant::now(),
+        }
+    }
+
+    /// The current values of all cells (0 means unassigned)
+    pub(crate) fn cells(&self) -> &[Value] {
+        &self.cells
+    }
+
+    /// The cells assigned by the search so far in order
+    pub(crate) fn assigned(&self) -> &[usize] {
+        &self.assigned
+    }
+
+    /// Place the value and remove it from the candidates of the peers, its
+    /// consecutive values from the candidates of the non-consecutive cells and
+    /// the values out of order from the candidates of cells on its thermometers.
+    /// Returns `false` if one of these cells is left without candidates.
+    fn assign(&mut self, cell_id: usize, value: Value) -> bool {
+        self.cells[cell_id] = value;
+        self.assigned.push(cell_id);
+        let topology = self.topology;
+        let mut consistent = true;
+        for peer_id in topology.peers[cell_id].iter().copied() {
+            consistent &= self.eliminate(peer_id, 1 << value);
+        }
+        for other_id in topology.non_consecutive[cell_id].iter().copied() {
+            consistent &= self.eliminate(other_id, consecutive(value));
+        }
+        for (thermometer_id, position) in topology.on_thermometers[cell_id].iter().copied() {
+            for (other_position, other_id) in topology.thermometers[thermometer_id].iter().copied().enumerate() {
+                consistent &= self.eliminate(other_id, !ordered(value, position, other_position));
+            }
+        }
+        consistent
+    }
+
+    /// Remove the values from the candidates of the cell if it is unassigned.
+    /// Returns `false` if the cell is left without candidates.
+    fn eliminate(&mut self, cell_id: usize, values: u32) -> bool {
+        if self.cells[cell_id] != 0 || self.candidates[cell_id] & values == 0 {
+            return true;
+        }
+        self.trail.push((cell_id, self.candidates[cell_id]));
+        self.candidates[cell_id] &= !values;
+        self.candidates[cell_id] != 0
+    }
+
+    /// Assign singles like `propagate_singles` and remove the candidates the
+    /// prune hook forbids until neither changes anything. The assignments are kept.
+    /// Returns `false` on a contradiction or if the cells violate the prune hook.
+    pub(crate) fn propagate(&mut self) -> bool {
+        loop {
+            if !self.propagate_singles() {
+                return false;
+            }
+            let Some(prune) = self.prune.as_ref() else {
+                return true;
+            };
+            let mut candidates = self.candidates.clone();
+            if !prune(&self.cells, &mut candidates) {
+                return false;
+            }
+
+            let mut changed = false;
+            for (cell_id, pruned) in candidates.into_iter().enumerate() {
+                let removed = self.candidates[cell_id] & !pruned;
+                if self.cells[cell_id] == 0 && removed != 0 {
+                    changed = true;
+                    if !self.eliminate(cell_id, removed) {
+                        return false;
+                    }
+                }
+            }
+            if !changed {
+                return true;
+            }
+        }
+    }
+
+    /// Assign cells with a single candidate and values with a single possible
+    /// cell in a house until there are none left. The assignments are kept.
+    /// Returns `false` if a cell or a value in a house is left without a place.
+    fn propagate_singles(&mut self) -> bool {
+        let all = self.topology.all_values();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for cell_id in 0..self.cells.len() {
+                let candidates = self.candidates[cell_id];
+                if self.cells[cell_id] == 0 && candidates.count_ones() == 1 {
+                    self.propagations += 1;
+                    if !self.assign(cell_id, candidates.trailing_zeros() as Value) {
+                        return false;
+                    }
+                    changed = true;
+                }
+            }
+
+            for house in self.topology.houses.iter() {
+                let (mut once, mut twice, mut placed) = (0u32, 0u32, 0u32);
+                for cell_id in house.iter().copied() {
+                    match self.cells[cell_id] {
+                        0 => {
+                            twice |= once & self.candidates[cell_id];
+                            once |= self.candidates[cell_id];
+                        },
+                        value => placed |= 1 << value,
+                    }
+                }
+                // NOTE: only houses with a cell for every value must hold every value
+                if house.len() < self.topology.size {
+                    continue;
+                }
+                if once | placed != all {
+                    return false;
+                }
+
+                let mut singles = once & !twice & !placed;
+                while singles != 0 {
+                    let value = singles.trailing_zeros() as Value;
+                    singles &= singles - 1;
+                    self.propagations += 1;
+                    let cell_id = house.iter().copied()
+                        .find(|cell_id| self.cells[*cell_id] == 0 && self.candidates[*cell_id] & (1 << value) != 0);
+                    // NOTE: an earlier single of this house may have taken the value's only cell
+                    match cell_id {
+                        Some(cell_id) if self.assign(cell_id, value) => changed = true,
+                        _ => return false,
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// The lengths of the trails, to undo everything done afterwards
+    fn checkpoint(&self) -> (usize, usize) {
+        (self.trail.len(), self.assigned.len())
+    }
+
+    /// Undo all eliminations and assignments made since the checkpoint
+    fn undo(&mut self, (trail_len, assigned_len): (usize, usize)) {
+        while self.trail.len() > trail_len {
+            let (cell_id, candidates) = self.trail.pop().unwrap();
+            self.candidates[cell_id] = candidates;
+        }
+        while self.assigned.len() > assigned_len {
+            let cell_id = self.assigned.pop().unwrap();
+            self.cells[cell_id] = 0;
+        }
+    }
+
+    /// Has the search exceeded its node limit or its deadline?
+    fn exceeds_budget(&mut self) -> bool {
+        let exceeded = self.node_limit.is_some_and(|limit| self.nodes > limit)
+            || self.deadline.is_some_and(|deadline| Instant::now() >= deadline);
+        self.aborted |= exceeded;
+        exceeded
+    }
+
+    /// Visit every solution until `visit` returns `false` or the budget is exceeded.
+    /// Returns `false` if the search was stopped early. The cells are left unchanged.
+    pub(crate) fn run(&mut self, visit: &mut dyn FnMut(&[Value]) -> bool) -> bool {
+        self.nodes += 1;
+        if self.exceeds_budget() {
+            return false;
+        }
+        if let Some(progress) = &mut self.progress {
+            progress.visit(self.nodes, self.started);
+        }
+        let checkpoint = self.checkpoint();
+        let go_on = !self.propagate() || self.branch(visit);
+        self.undo(checkpoint);
+        go_on
+    }
+
+    /// Guess every candidate of the cell with the fewest candidates and search on
+    fn branch(&mut self, visit: &mut dyn FnMut(&[Value]) -> bool) -> bool {
+        let cell_id = (0..self.cells.len())
+            .filter(|cell_id| self.cells[*cell_id] == 0)
+            .min_by_key(|cell_id| self.candidates[*cell_id].count_ones());
+        let cell_id = match cell_id {
+            Some(cell_id) => cell_id,
+            None => return visit(&self.cells),
+        };
+
+        let candidates = self.candidates[cell_id];
+        let mut values: Vec<Value> = (1..=self.topology.size as Value).filter(|value| candidates & (1 << value) != 0).collect();
+        if let Some(rng) = self.rng.as_mut() {
+            rng.shuffle(&mut values);
+        }
+        if let Some(progress) = &mut self.progress {
+            progress.descend(values.len());
+        }
+        for value in values {
+            let checkpoint = self.checkpoint();
+            self.depth += 1;
+            self.max_depth = self.max_depth.max(self.depth);
+            let go_on = !self.assign(cell_id, value) || self.run(visit);
+            self.depth -= 1;
+            self.undo(checkpoint);
+            if !go_on {
+                return false;
+            }
+            self.backtracks += 1;
+            if let Some(progress) = &mut self.progress {
+                progress.finish_branch();
+            }
+        }
+        if let Some(progress) = &mut self.progress {
+            progress.ascend();
+        }
+        true
+    }
+}
+
+/// Bits of the values one less and one more than `value`, none for unassigned cells
+fn consecutive(value: Value) -> u32 {
+    match value {
+        0 => 0,
+        value => (1 << (value + 1)) | (1 << (value - 1)),
+    }
+}
+
+/// Bits of the values from `low` to `high`, none if `low` exceeds `high`
+fn values_between(low: isize, high: isize) -> u32 {
+    let (low, high) = (low.max(1), high.min(31));
+    if low > high {
+        return 0;
+    }
+    ((1u64 << (high + 1)) - (1u64 << low)) as u32
+}
+
+/// Bits of the values which can be at `other_position` of a thermometer holding
+/// `value` at `position`: steps towards the tip increase the value by at least one
+fn ordered(value: Value, position: usize, other_position: usize) -> u32 {
+    let (value, steps) = (value as isize, other_position as isize - position as isize);
+    match steps {
+        0 => !0,
+        steps if steps > 0 => values_between(value + steps, 31),
+        steps => values_between(1, value + steps),
+    }
+}
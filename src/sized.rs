@@ -0,0 +1,427 @@
+//! Boards of other sizes than 9×9, e.g. 4×4 grids for kids or 16×16 and 25×25 "monster" puzzles.
+//!
+//! `Board` and the solvers in `solver` are tailored to the classic grid with
+//! compile-time lookup tables. A `SizedBoard` instead takes its dimensions from
+//! a runtime `BoardSpec` and uses one parser, renderer and solver for all sizes.
+//! Values larger than 9 are written as letters: `A` is 10, `B` is 11 and so on.
+//! Other alphabets, e.g. the hexadecimal digits `0`–`F`, are given as a `SymbolSet`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::board::{Board, Value};
+use crate::coord::Coord;
+use crate::error::Error;
+use crate::search::{Search, Topology};
+use crate::symbols::SymbolSet;
+
+/// Largest number of values (and so of rows and columns) a board may have
+pub const MAX_SIZE: usize = 25;
+
+/// The dimensions of a board given by the dimensions of its blocks.
+/// A board with blocks of `box_rows × box_cols` cells has `box_rows * box_cols`
+/// rows, columns and blocks, and as many values.
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Hash)]
+pub struct BoardSpec {
+    box_rows: usize,
+    box_cols: usize,
+}
+
+impl BoardSpec {
+    /// The classic 9×9 board with 3×3 blocks
+    pub const CLASSIC: BoardSpec = BoardSpec { box_rows: 3, box_cols: 3 };
+
+    /// A board with blocks of `box_rows × box_cols` cells, e.g. `new(2, 3)` for 6×6 boards.
+    /// Fails with ``Error::SizeError`` if the board would be empty or have more than `MAX_SIZE` values.
+    pub fn new(box_rows: usize, box_cols: usize) -> Result<Self, Error> {
+        if box_rows == 0 || box_cols == 0 || box_rows * box_cols > MAX_SIZE {
+            return Err(Error::SizeError { box_rows, box_cols });
+        }
+        Ok(Self { box_rows, box_cols })
+    }
+
+    /// A board with square blocks, e.g. `square(2)` for 4×4 or `square(4)` for 16×16 boards
+    pub fn square(box_size: usize) -> Result<Self, Error> {
+        Self::new(box_size, box_size)
+    }
+
+    /// A board with `size` values whose blocks are as square as possible,
+    /// e.g. 2×3 blocks for size 6. If `size` is prime, the blocks coincide with the rows.
+    pub fn for_size(size: usize) -> Result<Self, Error> {
+        let box_rows = (1..=size).take_while(|rows| rows * rows <= size).filter(|rows| size.is_multiple_of(*rows)).last().unwrap_or(0);
+        Self::new(box_rows, size.checked_div(box_rows).unwrap_or(0))
+    }
+
+    pub fn box_rows(&self) -> usize {
+        self.box_rows
+    }
+
+    pub fn box_cols(&self) -> usize {
+        self.box_cols
+    }
+
+    /// Number of values, which is also the number of rows, columns and blocks
+    pub fn size(&self) -> usize {
+        self.box_rows * self.box_cols
+    }
+
+    /// Number of cells of the board
+    pub fn count_cells(&self) -> usize {
+        self.size() * self.size()
+    }
+
+    /// Identifier of the block containing the cell in reading order (0 is at the top-left)
+    pub fn block_of(&self, cell_id: usize) -> usize {
+        let (row, col) = (cell_id / self.size(), cell_id % self.size());
+        (row / self.box_rows) * self.box_rows + col / self.box_cols
+    }
+
+    /// The cell IDs of every row, then of every column and then of every block
+    pub fn houses(&self) -> Vec<Vec<usize>> {
+        let size = self.size();
+        let rows = (0..size).map(|row| (0..size).map(|col| row * size + col).collect());
+        let columns = (0..size).map(|col| (0..size).map(|row| row * size + col).collect());
+        let blocks = (0..size).map(|block_id| (0..self.count_cells()).filter(|cell_id| self.block_of(*cell_id) == block_id).collect());
+        rows.chain(columns).chain(blocks).collect()
+    }
+
+    /// Description of a house as returned by `houses`, e.g. `row 3` or `block 12`
+    pub(crate) fn house_name(&self, house_id: usize) -> String {
+        let size = self.size();
+        match house_id / size {
+            0 => format!("row {}", house_id + 1),
+            1 => format!("column {}", house_id - size + 1),
+            _ => format!("block {}", house_id - 2 * size + 1),
+        }
+    }
+
+    /// The rows, columns and blocks as used by the search
+    fn topology(&self) -> Topology {
+        Topology::from_houses(self.size(), self.houses())
+    }
+}
+
+impl Default for BoardSpec {
+    fn default() -> Self {
+        Self::CLASSIC
+    }
+}
+
+impl fmt::Display for BoardSpec {
+    /// E.g. `16×16 with 4×4 blocks`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{0}×{0} with {1}×{2} blocks", self.size(), self.box_rows, self.box_cols)
+    }
+}
+
+/// A board of any size given by its `BoardSpec`.
+/// The cells store the values (0 means unassigned, 1..=size are Sudoku values).
+#[derive(Clone,Debug,PartialEq,Eq,Hash)]
+pub struct SizedBoard {
+    spec: BoardSpec,
+    cells: Vec<Value>,
+}
+
+impl SizedBoard {
+    /// The empty board of the spec
+    pub fn new(spec: BoardSpec) -> Self {
+        Self { spec, cells: vec![0; spec.count_cells()] }
+    }
+
+    /// The board holding the values in reading order. Fails with ``Error::ValueError``
+    /// if a value exceeds `spec.size()` and panics unless there are `spec.count_cells()` values.
+    pub fn from_values(spec: BoardSpec, values: &[Value]) -> Result<Self, Error> {
+        assert_eq!(values.len(), spec.count_cells(), "expected one value per cell of a {spec} board");
+        if let Some(value) = values.iter().find(|value| **value as usize > spec.size()) {
+            return Err(Error::ValueError { value: *value, expected: format!("0 (unassigned) or 1..={}", spec.size()) });
+        }
+        Ok(Self { spec, cells: values.to_vec() })
+    }
+
+    /// Parse the cells of a board with the given spec in reading order. Values are
+    /// digits or letters (`A` is 10), blanks are `.` or `0`. Whitespace and the
+    /// separators `|`, `-`, `+` as well as box-drawing characters are ignored.
+    pub fn parse(spec: BoardSpec, s: &str) -> Result<Self, Error> {
+        Self::parse_with(spec, s, &SymbolSet::alphanumeric(spec.size()))
+    }
+
+    /// Parse the cells like `parse`, but with the values written as symbols of the set,
+    /// e.g. `SymbolSet::hexadecimal()`. Separators which are symbols of the set are values.
+    pub fn parse_with(spec: BoardSpec, s: &str, symbols: &SymbolSet) -> Result<Self, Error> {
+        let error = |reason: String| Error::ParseError { input: s.to_string(), reason };
+
+        let mut cells = vec![];
+        for symbol in s.chars().filter(|symbol| !is_separator(*symbol) || symbols.symbols().contains(symbol)) {
+            match symbols.value_of(symbol) {
+                Some(value) if value as usize <= spec.size() => cells.push(value),
+                Some(_) => return Err(error(format!("value {symbol:?} exceeds the size {} of the board", spec.size()))),
+                None => return Err(error(format!("unexpected character {symbol:?} for the symbols {symbols}"))),
+            }
+        }
+        if cells.len() != spec.count_cells() {
+            return Err(error(format!("expected {} cells but got {}", spec.count_cells(), cells.len())));
+        }
+        Ok(Self { spec, cells })
+    }
+
+    /// String representation of the board like `Display`, but with the values written
+    /// as symbols of the set. The columns are as wide as the widest symbol.
+    pub fn to_string_with(&self, symbols: &SymbolSet) -> String {
+        let spec = self.spec;
+        let block_line = |left: &str, middle: &str, right: &str| {
+            format!("{}{}{}\n", left, vec!["─".repeat((symbols.width() + 2) * spec.box_cols); spec.box_rows].join(middle), right)
+        };
+
+        let mut out = block_line("┌", "┬", "┐");
+        for row in 0..self.size() {
+            if row > 0 && row % spec.box_rows == 0 {
+                out.push_str(&block_line("├", "┼", "┤"));
+            }
+            out.push('│');
+            for col in 0..self.size() {
+                out.push_str(&symbols.to_padded_string(self.get(row, col)));
+                if col % spec.box_cols == spec.box_cols - 1 {
+                    out.push('│');
+                }
+            }
+            out.push('\n');
+        }
+        out.push_str(&block_line("└", "┴", "┘"));
+        out
+    }
+
+    pub fn spec(&self) -> BoardSpec {
+        self.spec
+    }
+
+    /// Number of values, rows and columns
+    pub fn size(&self) -> usize {
+        self.spec.size()
+    }
+
+    /// The values of all cells in reading order (0 means unassigned)
+    pub fn values(&self) -> &[Value] {
+        &self.cells
+    }
+
+    /// The value of the cell at the zero-based row and column (0 means unassigned)
+    pub fn get(&self, row: usize, col: usize) -> Value {
+        assert!(row < self.size() && col < self.size(), "cell (row {row}, column {col}) is outside of the board");
+        self.cells[row * self.size() + col]
+    }
+
+    /// Store `value` in the cell (0 clears the cell) after checking the cell ID
+    /// and the value. Fails with ``Error::IdError`` or ``Error::ValueError``.
+    pub fn try_set(&mut self, cell_id: usize, value: Value) -> Result<(), Error> {
+        if cell_id >= self.cells.len() {
+            return Err(Error::IdError { admissible: 0..self.cells.len(), actual: cell_id });
+        }
+        if value as usize > self.size() {
+            return Err(Error::ValueError { value, expected: format!("0 (unassigned) or 1..={}", self.size()) });
+        }
+        self.cells[cell_id] = value;
+        Ok(())
+    }
+
+    /// IDs of the unassigned cells in increasing order
+    pub fn unassigned(&self) -> Vec<usize> {
+        (0..self.cells.len()).filter(|cell_id| self.cells[*cell_id] == 0).collect()
+    }
+
+    /// Is every cell assigned?
+    pub fn is_complete(&self) -> bool {
+        self.cells.iter().all(|value| *value != 0)
+    }
+
+    /// The values which can be placed in the unassigned cell without repeating
+    /// a value of its row, its column or its block. Empty for assigned cells.
+    pub fn legal_values(&self, cell_id: usize) -> Vec<Value> {
+        if self.cells[cell_id] != 0 {
+            return vec![];
+        }
+        let size = self.size();
+        (1..=size as Value)
+            .filter(|value| (0..self.cells.len()).all(|peer_id| {
+                let is_peer = peer_id / size == cell_id / size || peer_id % size == cell_id % size || self.spec.block_of(peer_id) == self.spec.block_of(cell_id);
+                !is_peer || self.cells[peer_id] != *value
+            }))
+            .collect()
+    }
+
+    /// Does the board satisfy all Sudoku constraints? If not, returns a
+    /// ``Error::ConflictError`` naming the first house with a repeated value.
+    pub fn verify(&self) -> Result<(), Error> {
+        for (house_id, house) in self.spec.houses().iter().enumerate() {
+            for (i, cell_id) in house.iter().enumerate() {
+                let value = self.cells[*cell_id];
+                let repeated: Vec<usize> = house[i..].iter().copied().filter(|other| self.cells[*other] == value).collect();
+                if value != 0 && repeated.len() > 1 {
+                    let cells = repeated.into_iter().map(|cell_id| Coord::new(cell_id / self.size(), cell_id % self.size())).collect();
+                    return Err(Error::ConflictError { region: self.spec.house_name(house_id), value, cells });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Solve the board by depth-first backtracking on the cell with the fewest candidates.
+    /// Returns ``Error::Unsolvable`` if there is no solution and the error of `verify`
+    /// if the board already violates a constraint.
+    pub fn solve(&self) -> Result<SizedBoard, Error> {
+        self.verify()?;
+        let mut solution = None;
+        Search::new(&self.spec.topology(), &self.cells).run(&mut |cells| {
+            solution = Some(Self { spec: self.spec, cells: cells.to_vec() });
+            false
+        });
+        solution.ok_or(Error::Unsolvable)
+    }
+
+    /// Count the solutions of the board, but stop as soon as `limit` solutions are found.
+    /// A board which already violates a constraint has no solutions.
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        let mut count = 0;
+        if limit == 0 || self.verify().is_err() {
+            return count;
+        }
+        Search::new(&self.spec.topology(), &self.cells).run(&mut |_| {
+            count += 1;
+            count < limit
+        });
+        count
+    }
+
+    /// Does the board have exactly one solution?
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == 1
+    }
+}
+
+/// Characters which only structure the textual representation of a board
+pub(crate) fn is_separator(symbol: char) -> bool {
+    symbol.is_whitespace() || "|-+│─┌┐└┘├┤┬┴┼".contains(symbol)
+}
+
+impl From<&Board> for SizedBoard {
+    fn from(board: &Board) -> Self {
+        Self { spec: BoardSpec::CLASSIC, cells: (0..Board::COUNT_VALUES).map(|cell_id| board[cell_id].value()).collect() }
+    }
+}
+
+impl TryFrom<&SizedBoard> for Board {
+    type Error = Error;
+
+    /// The classic board with the same values. All assigned cells become givens.
+    /// Fails with ``Error::SizeError`` unless the board has the classic spec.
+    fn try_from(board: &SizedBoard) -> Result<Self, Error> {
+        if board.spec != BoardSpec::CLASSIC {
+            return Err(Error::SizeError { box_rows: board.spec.box_rows, box_cols: board.spec.box_cols });
+        }
+        let mut values = [0; Board::COUNT_VALUES];
+        values.copy_from_slice(&board.cells);
+        Ok(Board::from_flattened_values(&values))
+    }
+}
+
+impl FromStr for SizedBoard {
+    type Err = Error;
+
+    /// Parse a board like `SizedBoard::parse` and take its size from the number of cells,
+    /// e.g. 16 cells for a 4×4 board. See `BoardSpec::for_size` for the shape of the blocks.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let count_cells = s.chars().filter(|symbol| !is_separator(*symbol)).count();
+        let size = (1..=MAX_SIZE).find(|size| size * size == count_cells)
+            .ok_or_else(|| Error::ParseError { input: s.to_string(), reason: format!("{count_cells} cells do not form a square board") })?;
+        Self::parse(BoardSpec::for_size(size)?, s)
+    }
+}
+
+impl fmt::Display for SizedBoard {
+    /// The board in box drawing with digits and letters (see `SymbolSet::alphanumeric`)
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string_with(&SymbolSet::alphanumeric(self.size())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A solved board of the square spec, shifting the values by a block row per row
+    fn pattern(box_size: usize) -> SizedBoard {
+        let spec = BoardSpec::square(box_size).unwrap();
+        let size = spec.size();
+        let values: Vec<Value> = (0..spec.count_cells())
+            .map(|cell_id| ((box_size * (cell_id / size % box_size) + cell_id / size / box_size + cell_id % size) % size + 1) as Value)
+            .collect();
+        SizedBoard::from_values(spec, &values).unwrap()
+    }
+
+    #[test]
+    fn specs() {
+        assert_eq!((BoardSpec::for_size(6).unwrap().box_rows(), BoardSpec::for_size(6).unwrap().box_cols()), (2, 3));
+        assert_eq!(BoardSpec::for_size(7).unwrap().box_rows(), 1);
+        assert_eq!(BoardSpec::for_size(9).unwrap(), BoardSpec::CLASSIC);
+        assert!(BoardSpec::new(6, 5).is_err());
+        assert!(BoardSpec::new(0, 3).is_err());
+    }
+
+    #[test]
+    fn solve_4x4() {
+        let board: SizedBoard = "1...\n..12\n2...\n..2.".parse().unwrap();
+        assert_eq!(board.spec(), BoardSpec::square(2).unwrap());
+        let solution = board.solve().unwrap();
+        assert!(solution.is_complete());
+        solution.verify().unwrap();
+        assert!(board.values().iter().zip(solution.values()).all(|(given, value)| *given == 0 || given == value));
+    }
+
+    #[test]
+    fn solve_16x16() {
+        let solved = pattern(4);
+        solved.verify().unwrap();
+        let values: Vec<Value> = solved.values().iter().enumerate().map(|(cell_id, value)| if cell_id % 3 == 0 { 0 } else { *value }).collect();
+        let board = SizedBoard::from_values(solved.spec(), &values).unwrap();
+        let board: SizedBoard = board.to_string().parse().unwrap();
+        assert_eq!(board.values(), values);
+        let solution = board.solve().unwrap();
+        solution.verify().unwrap();
+        assert!(values.iter().zip(solution.values()).all(|(given, value)| *given == 0 || given == value));
+    }
+
+    #[test]
+    fn count_4x4() {
+        let spec = BoardSpec::square(2).unwrap();
+        assert_eq!(SizedBoard::new(spec).count_solutions(usize::MAX), 288);
+        assert_eq!("1...\n..12\n2...\n..2.".parse::<SizedBoard>().unwrap().count_solutions(usize::MAX), 6);
+        assert_eq!(SizedBoard::new(spec).count_solutions(10), 10);
+        assert_eq!("11..............".parse::<SizedBoard>().unwrap().count_solutions(usize::MAX), 0);
+        assert!(pattern(2).has_unique_solution());
+    }
+
+    #[test]
+    fn conflicts_and_errors() {
+        let board: SizedBoard = "1..1............".parse().unwrap();
+        assert!(matches!(board.verify(), Err(Error::ConflictError { value: 1, .. })));
+        assert!(matches!(board.solve(), Err(Error::ConflictError { .. })));
+        assert!("1..5............".parse::<SizedBoard>().is_err());
+        assert!(".".repeat(15).parse::<SizedBoard>().is_err());
+        let mut board = SizedBoard::new(BoardSpec::square(2).unwrap());
+        assert!(matches!(board.try_set(16, 1), Err(Error::IdError { .. })));
+        assert!(matches!(board.try_set(0, 5), Err(Error::ValueError { .. })));
+        assert_eq!(board.legal_values(1), [1, 2, 3, 4]);
+        board.try_set(0, 1).unwrap();
+        assert_eq!(board.legal_values(1), [2, 3, 4]);
+    }
+
+    #[test]
+    fn classic_boards_convert() {
+        let values: Vec<Value> = "..75......6..2..4....3.65.22......8...8.3.1...9......79.32.7....7..9..5......89.."
+            .chars().map(|symbol| symbol.to_digit(10).unwrap_or(0) as Value).collect();
+        let board = Board::from_flattened_values(&values.try_into().unwrap());
+        let sized = SizedBoard::from(&board);
+        assert_eq!(sized.spec(), BoardSpec::CLASSIC);
+        assert!(board.diff(&Board::try_from(&sized).unwrap()).is_empty());
+        assert!(matches!(Board::try_from(&pattern(2)), Err(Error::SizeError { box_rows: 2, box_cols: 2 })));
+    }
+}
@@ -0,0 +1,254 @@
+//! Backtracking with conflict-directed backjumping (CBJ).
+//!
+//! Every decision of the search gets a level. Propagation of naked and
+//! hidden singles records for every eliminated candidate and every forced
+//! placement the set of decision levels it follows from. When propagation
+//! derives a contradiction, the union of the reasons involved forms the
+//! conflict set. Instead of undoing the most recent decision, the search
+//! jumps back to the latest decision in the conflict set, skipping all
+//! decisions which had nothing to do with the dead end. The saving depends on
+//! the puzzle: on most puzzles propagation leaves no decision to skip and both
+//! searches visit the same nodes, while on the solver killer of the example
+//! corpus `solve_measured` counts about 1.9 million nodes instead of the 2.6
+//! million of chronological backtracking, i.e. it saves less than a third.
+
+use crate::board::{Board, Value};
+use crate::candidate_set::CandidateSet;
+use crate::solver::backtracking::{Search, SearchHeuristic};
+use crate::solver::{SolveConfig, SolveOutcome, SolveStats};
+use crate::topology::{HOUSES, PEERS};
+
+/// A set of decision levels; a board has at most 81 decisions
+type LevelSet = u128;
+
+/// A change of the search state which can be undone
+enum Change {
+    Elimination { cell_id: usize, value: Value },
+    Assignment { cell_id: usize },
+}
+
+/// How a subtree of the search ended
+enum Descent {
+    Solved,
+    /// No solution; the levels responsible for this dead end
+    Conflict(LevelSet),
+    /// The budget of the search is exhausted
+    Aborted,
+}
+
+struct Backjumping<'a> {
+    search: Search<'a>,
+    heuristic: SearchHeuristic,
+    values: [Value; Board::COUNT_VALUES],
+    /// Levels which forced the value of every assigned cell
+    assignment_reasons: [LevelSet; Board::COUNT_VALUES],
+    /// Remaining values of every unassigned cell as bitmask (bit `v` for value `v`)
+    domains: [u16; Board::COUNT_VALUES],
+    /// Levels which eliminated value `v` from the domain of every cell
+    elimination_reasons: [[LevelSet; 10]; Board::COUNT_VALUES],
+    changes: Vec<Change>,
+}
+
+impl<'a> Backjumping<'a> {
+    /// Set up the domains of all unassigned cells given the assigned cells
+    fn new(board: &Board, config: &SolveConfig) -> Self {
+        let mut values = [0; Board::COUNT_VALUES];
+        let mut domains = [0; Board::COUNT_VALUES];
+        for cell_id in 0..Board::COUNT_VALUES {
+            values[cell_id] = board[cell_id].value();
+        }
+        for cell_id in 0..Board::COUNT_VALUES {
+            if values[cell_id] != 0 {
+                continue;
+            }
+            domains[cell_id] = 0b11_1111_1110;
+            for peer_id in PEERS[cell_id] {
+                domains[cell_id] &= !(1 << values[peer_id]);
+            }
+        }
+
+        Self {
+            search: Search::with_config(config),
+            heuristic: config.heuristic,
+            values,
+            assignment_reasons: [0; Board::COUNT_VALUES],
+            domains,
+            elimination_reasons: [[0; 10]; Board::COUNT_VALUES],
+            changes: vec![],
+        }
+    }
+
+    /// Levels which eliminated any value of the cell
+    fn pruning_reasons(&self, cell_id: usize) -> LevelSet {
+        self.elimination_reasons[cell_id].iter().fold(0, |reasons, reason| reasons | reason)
+    }
+
+    /// Why can't `cell_id` hold `value`? Either it holds another value or `value` got eliminated.
+    fn exclusion_reasons(&self, cell_id: usize, value: Value) -> LevelSet {
+        if self.values[cell_id] != 0 {
+            self.assignment_reasons[cell_id]
+        } else {
+            self.elimination_reasons[cell_id][value as usize]
+        }
+    }
+
+    fn eliminate(&mut self, cell_id: usize, value: Value, reasons: LevelSet) -> Result<(), LevelSet> {
+        if self.values[cell_id] != 0 || self.domains[cell_id] & (1 << value) == 0 {
+            return Ok(());
+        }
+
+        self.domains[cell_id] &= !(1 << value);
+        self.elimination_reasons[cell_id][value as usize] = reasons;
+        self.changes.push(Change::Elimination { cell_id, value });
+        if self.domains[cell_id] == 0 {
+            return Err(self.pruning_reasons(cell_id));
+        }
+        Ok(())
+    }
+
+    fn assign(&mut self, cell_id: usize, value: Value, reasons: LevelSet) -> Result<(), LevelSet> {
+        self.values[cell_id] = value;
+        self.assignment_reasons[cell_id] = reasons;
+        self.changes.push(Change::Assignment { cell_id });
+        for peer_id in PEERS[cell_id] {
+            self.eliminate(peer_id, value, reasons)?;
+        }
+        Ok(())
+    }
+
+    /// Undo all changes after the first `length` ones
+    fn undo(&mut self, length: usize) {
+        while self.changes.len() > length {
+            match self.changes.pop() {
+                Some(Change::Elimination { cell_id, value }) => {
+                    self.domains[cell_id] |= 1 << value;
+                    self.elimination_reasons[cell_id][value as usize] = 0;
+                },
+                Some(Change::Assignment { cell_id }) => {
+                    self.values[cell_id] = 0;
+                    self.assignment_reasons[cell_id] = 0;
+                },
+                None => break,
+            }
+        }
+    }
+
+    /// Apply naked and hidden singles until a fixpoint is reached.
+    /// A contradiction is reported with the levels it follows from.
+    fn propagate(&mut self) -> Result<(), LevelSet> {
+        loop {
+            let mut progress = false;
+
+            for cell_id in 0..Board::COUNT_VALUES {
+                let domain = self.domains[cell_id];
+                if self.values[cell_id] == 0 && domain.count_ones() == 1 {
+                    let reasons = self.pruning_reasons(cell_id);
+                    self.assign(cell_id, domain.trailing_zeros() as Value, reasons)?;
+                    progress = true;
+                }
+            }
+
+            for cells in HOUSES {
+                for value in 1..=9 {
+                    if cells.iter().any(|cell_id| self.values[*cell_id] == value) {
+                        continue;
+                    }
+
+                    let mut positions = cells.iter().copied().filter(|cell_id| self.values[*cell_id] == 0 && self.domains[*cell_id] & (1 << value) != 0);
+                    match (positions.next(), positions.next()) {
+                        (None, _) => {
+                            return Err(cells.iter().fold(0, |reasons, cell_id| reasons | self.exclusion_reasons(*cell_id, value)));
+                        },
+                        (Some(cell_id), None) => {
+                            let reasons = cells.iter()
+                                .filter(|other| **other != cell_id)
+                                .fold(0, |reasons, other| reasons | self.exclusion_reasons(*other, value));
+                            self.assign(cell_id, value, reasons)?;
+                            progress = true;
+                        },
+                        _ => {},
+                    }
+                }
+            }
+
+            if !progress {
+                return Ok(());
+            }
+        }
+    }
+
+    /// The unassigned cell to decide next or `None` if the board is full
+    fn select_cell(&mut self) -> Option<usize> {
+        let mut unassigned = (0..Board::COUNT_VALUES).filter(|cell_id| self.values[*cell_id] == 0);
+        match self.heuristic {
+            SearchHeuristic::FirstUnassigned => unassigned.next(),
+            SearchHeuristic::MinimumRemainingValues => {
+                let unassigned: Vec<usize> = unassigned.collect();
+                let fewest = unassigned.iter().map(|cell_id| self.domains[*cell_id].count_ones()).min()?;
+                let ties: Vec<usize> = unassigned.into_iter().filter(|cell_id| self.domains[*cell_id].count_ones() == fewest).collect();
+                self.search.break_tie(&ties)
+            },
+        }
+    }
+
+    /// Decide the cells from `level` on
+    fn descend(&mut self, level: usize) -> Descent {
+        if !self.search.enter_node() {
+            return Descent::Aborted;
+        }
+        let cell_id = match self.select_cell() {
+            Some(cell_id) => cell_id,
+            None => return Descent::Solved,
+        };
+
+        let level_bit: LevelSet = 1 << level;
+        let mut conflicts = self.pruning_reasons(cell_id);
+        let values = self.search.value_order(CandidateSet::from_bits(self.domains[cell_id]));
+
+        for value in values {
+            let mark = self.changes.len();
+            let outcome = match self.assign(cell_id, value, level_bit).and_then(|_| self.propagate()) {
+                Ok(()) => self.descend(level + 1),
+                Err(reasons) => Descent::Conflict(reasons),
+            };
+            if let Descent::Solved = outcome {
+                return outcome;
+            }
+            self.undo(mark);
+
+            match outcome {
+                Descent::Conflict(reasons) if reasons & level_bit == 0 => {
+                    // NOTE: this decision is not to blame, so jump over it
+                    return Descent::Conflict(reasons);
+                },
+                Descent::Conflict(reasons) => conflicts |= reasons & !level_bit,
+                _ => return outcome,
+            }
+        }
+
+        Descent::Conflict(conflicts)
+    }
+}
+
+/// Solve the board with conflict-directed backjumping within the budget of `config`.
+/// The assigned cells of the board must not violate any constraint.
+pub fn solve_with(board: &Board, config: &SolveConfig) -> SolveOutcome {
+    solve_measured(board, config).0
+}
+
+/// Solve the board like `solve_with` and return the statistics of the search,
+/// of which only the nodes and the time are counted
+pub(crate) fn solve_measured(board: &Board, config: &SolveConfig) -> (SolveOutcome, SolveStats) {
+    let mut search = Backjumping::new(board, config);
+    let consistent = (0..Board::COUNT_VALUES).all(|cell_id| search.values[cell_id] != 0 || search.domains[cell_id] != 0);
+    if !consistent || search.propagate().is_err() {
+        return (SolveOutcome::Unsolvable, search.search.stats());
+    }
+
+    let outcome = match search.descend(0) {
+        Descent::Solved => SolveOutcome::Solved(board.with_values(&search.values)),
+        Descent::Conflict(_) => SolveOutcome::Unsolvable,
+        Descent::Aborted => SolveOutcome::Aborted(search.search.stats()),
+    };
+    (outcome, search.search.stats())
+}
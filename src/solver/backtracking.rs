@@ -0,0 +1,379 @@
+//! Depth-first backtracking search on a `CandidateBoard`.
+//!
+//! Naked and hidden singles are propagated before the search starts
+//! and after every guess, so most cells are never branched on.
+//! Guesses are made in place on a single `CandidateBoard` and undone
+//! when backtracking instead of copying the board for every candidate.
+//! The assigned cells of the board must not violate any constraint;
+//! use `Sudoku::verify_board` to check this beforehand.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use crate::board::{Board, Value};
+use crate::candidate_set::CandidateSet;
+use crate::propagation::CandidateBoard;
+use crate::rng::Rng;
+use crate::solver::backjumping;
+use crate::solver::progress::{ProgressHook, ProgressTracker, SearchProgress};
+use crate::solver::trace::{SolveTrace, TraceEvent};
+use crate::solver::{is_consistent, SolveConfig, SolveOutcome, SolveStats, Solver};
+
+/// How the search picks the next cell to branch on
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub enum SearchHeuristic {
+    /// The unassigned cell with the smallest cell ID
+    FirstUnassigned,
+    /// The unassigned cell with the fewest remaining candidates
+    /// (ties are broken by the smallest cell ID)
+    #[default]
+    MinimumRemainingValues,
+}
+
+impl SearchHeuristic {
+    /// Select the cell to branch on or `None` if every cell is assigned
+    pub fn select_cell(&self, grid: &CandidateBoard) -> Option<usize> {
+        let unassigned = grid.board().unassigned();
+        match self {
+            SearchHeuristic::FirstUnassigned => unassigned.first().copied(),
+            SearchHeuristic::MinimumRemainingValues => {
+                unassigned.into_iter().min_by_key(|cell_id| grid.candidates(*cell_id).len())
+            },
+        }
+    }
+}
+
+/// Solve the board and return the first solution found, or `None` if there is none
+pub fn solve(board: &Board, heuristic: SearchHeuristic) -> Option<Board> {
+    let mut solution = None;
+    Search::new(heuristic).run_board(board, &mut |solved| {
+        solution = Some(solved.clone());
+        false
+    });
+    solution
+}
+
+/// Count the solutions of the board, but stop as soon as `limit` solutions are found
+pub fn count_solutions(board: &Board, heuristic: SearchHeuristic, limit: usize) -> usize {
+    let mut count = 0;
+    if limit == 0 {
+        return count;
+    }
+    Search::new(heuristic).run_board(board, &mut |_| {
+        count += 1;
+        count < limit
+    });
+    count
+}
+
+/// The solutions of the board in the order the search finds them, but at most `limit` of them
+pub fn solutions(board: &Board, heuristic: SearchHeuristic, limit: usize) -> Vec<Board> {
+    let mut solutions = vec![];
+    if limit == 0 {
+        return solutions;
+    }
+    Search::new(heuristic).run_board(board, &mut |solved| {
+        solutions.push(solved.clone());
+        solutions.len() < limit
+    });
+    solutions
+}
+
+/// Count the solutions of the board like `count_solutions`, but within the budget of `config`.
+/// Returns `None` if the budget runs out first. Backjumping is not used for counting.
+pub fn count_solutions_with(board: &Board, config: &SolveConfig, limit: usize) -> Option<usize> {
+    count_by(Search::with_config(config), board, limit)
+}
+
+/// Count the solutions of the board like `count_solutions_with`,
+/// passing the progress of the search to `observe` every now and then
+pub fn count_solutions_observed(board: &Board, config: &SolveConfig, limit: usize, observe: &mut dyn FnMut(&SearchProgress)) -> Option<usize> {
+    count_by(Search::with_config(config).observed_by(Box::new(observe)), board, limit)
+}
+
+/// Solve the board within the budget of `config`.
+/// With `config.backjumping` set, dead ends are left by conflict-directed backjumping.
+pub fn solve_with(board: &Board, config: &SolveConfig) -> SolveOutcome {
+    if config.backjumping {
+        return backjumping::solve_with(board, config);
+    }
+    solve_by(&mut Search::with_config(config), board)
+}
+
+/// Solve the board like `solve_with`, passing the progress of the search to `observe`
+/// every now and then, and return the statistics of the search as well.
+/// The search with backjumping reports no progress and counts its nodes only.
+pub fn solve_observed(board: &Board, config: &SolveConfig, observe: &mut dyn FnMut(&SearchProgress)) -> (SolveOutcome, SolveStats) {
+    if config.backjumping {
+        return backjumping::solve_measured(board, config);
+    }
+    let mut search = Search::with_config(config).observed_by(Box::new(observe));
+    let outcome = solve_by(&mut search, board);
+    (outcome, search.stats())
+}
+
+/// Count the solutions by the search, stopping at `limit`
+fn count_by(mut search: Search, board: &Board, limit: usize) -> Option<usize> {
+    let mut count = 0;
+    if limit == 0 {
+        return Some(count);
+    }
+    search.run_board(board, &mut |_| {
+        count += 1;
+        count < limit
+    });
+    match search.aborted() {
+        true => None,
+        false => Some(count),
+    }
+}
+
+/// The first solution the search finds
+fn solve_by(search: &mut Search, board: &Board) -> SolveOutcome {
+    let mut solution = None;
+    search.run_board(board, &mut |solved| {
+        solution = Some(solved.clone());
+        false
+    });
+
+    match solution {
+        Some(solved) => SolveOutcome::Solved(solved),
+        None if search.aborted() => SolveOutcome::Aborted(search.stats()),
+        None => SolveOutcome::Unsolvable,
+    }
+}
+
+/// Solve the board and record all decisions of the search
+pub fn solve_traced(board: &Board, heuristic: SearchHeuristic) -> (Option<Board>, SolveTrace) {
+    let mut search = Search::new(heuristic).traced();
+    let mut solution = None;
+    search.run_board(board, &mut |solved| {
+        solution = Some(solved.clone());
+        false
+    });
+    (solution, search.take_trace().unwrap_or_default())
+}
+
+/// State of one depth-first search run
+pub(crate) struct Search<'a> {
+    heuristic: SearchHeuristic,
+    /// The search stops at the next node once this flag is set
+    cancelled: Option<&'a AtomicBool>,
+    deadline: Option<Instant>,
+    node_limit: Option<u64>,
+    started: Instant,
+    nodes: u64,
+    aborted: bool,
+    trace: Option<SolveTrace>,
+    backtracks: u64,
+    propagations: u64,
+    /// Number of guesses leading to the current node
+    depth: usize,
+    max_depth: usize,
+    /// Randomizes the order of cells and values if set
+    rng: Option<Rng>,
+    progress: Option<ProgressTracker<'a>>,
+}
+
+impl<'a> Search<'a> {
+    pub(crate) fn new(heuristic: SearchHeuristic) -> Self {
+        Self {
+            heuristic,
+            cancelled: None,
+            deadline: None,
+            node_limit: None,
+            started: Instant::now(),
+            nodes: 0,
+            backtracks: 0,
+            propagations: 0,
+            depth: 0,
+            max_depth: 0,
+            aborted: false,
+            trace: None,
+            rng: None,
+            progress: None,
+        }
+    }
+
+    /// A search using the heuristic and the budget of `config`.
+    /// The timeout starts right now.
+    pub(crate) fn with_config(config: &SolveConfig) -> Self {
+        let mut search = Self::new(config.heuristic);
+        search.deadline = config.timeout.map(|timeout| search.started + timeout);
+        search.node_limit = config.node_limit;
+        search.rng = config.seed.map(Rng::new);
+        search
+    }
+
+    /// Stop the search as soon as `flag` is set (possibly by another thread)
+    pub(crate) fn cancelled_by(mut self, flag: &'a AtomicBool) -> Self {
+        self.cancelled = Some(flag);
+        self
+    }
+
+    /// Pass the progress of the search to `hook` every now and then
+    pub(crate) fn observed_by(mut self, hook: ProgressHook<'a>) -> Self {
+        self.progress = Some(ProgressTracker::new(hook));
+        self
+    }
+
+    /// Record every guess, placement, elimination and backtrack of the search
+    pub(crate) fn traced(mut self) -> Self {
+        self.trace = Some(SolveTrace::default());
+        self
+    }
+
+    /// Remove and return the trace recorded so far
+    pub(crate) fn take_trace(&mut self) -> Option<SolveTrace> {
+        self.trace.take()
+    }
+
+    fn record(&mut self, event: TraceEvent) {
+        if let TraceEvent::Placement { .. } = event {
+            self.propagations += 1;
+        }
+        if let Some(trace) = &mut self.trace {
+            trace.push(event);
+        }
+    }
+
+    /// Did the search stop because its budget was exceeded?
+    pub(crate) fn aborted(&self) -> bool {
+        self.aborted
+    }
+
+    /// Statistics of the search so far
+    pub(crate) fn stats(&self) -> SolveStats {
+        SolveStats {
+            nodes: self.nodes,
+            backtracks: self.backtracks,
+            propagations: self.propagations,
+            max_depth: self.max_depth,
+            elapsed: self.started.elapsed(),
+        }
+    }
+
+    /// Count another node and check whether the budget allows visiting it
+    pub(crate) fn enter_node(&mut self) -> bool {
+        if self.cancelled.is_some_and(|flag| flag.load(Ordering::Relaxed)) {
+            return false;
+        }
+
+        if self.node_limit.is_some_and(|limit| self.nodes >= limit)
+            || self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            self.aborted = true;
+            return false;
+        }
+
+        self.nodes += 1;
+        if let Some(progress) = &mut self.progress {
+            progress.visit(self.nodes, self.started);
+        }
+        true
+    }
+
+    /// Select the cell to branch on by the heuristic.
+    /// With a seed, ties between the cells with the fewest candidates are broken randomly.
+    fn select_cell(&mut self, grid: &CandidateBoard) -> Option<usize> {
+        if self.rng.is_none() || self.heuristic == SearchHeuristic::FirstUnassigned {
+            return self.heuristic.select_cell(grid);
+        }
+
+        let unassigned = grid.board().unassigned();
+        let fewest = unassigned.iter().map(|cell_id| grid.candidates(*cell_id).len()).min()?;
+        let ties: Vec<usize> = unassigned.into_iter().filter(|cell_id| grid.candidates(*cell_id).len() == fewest).collect();
+        self.break_tie(&ties)
+    }
+
+    /// The candidates in the order to try them: increasing or shuffled with a seed
+    pub(crate) fn value_order(&mut self, candidates: CandidateSet) -> Vec<Value> {
+        let mut values: Vec<Value> = candidates.iter().collect();
+        if let Some(rng) = &mut self.rng {
+            rng.shuffle(&mut values);
+        }
+        values
+    }
+
+    /// Break a tie between equally good cells: the first one or a random one with a seed
+    pub(crate) fn break_tie(&mut self, cells: &[usize]) -> Option<usize> {
+        match &mut self.rng {
+            Some(rng) => rng.choose(cells),
+            None => cells.first().copied(),
+        }
+    }
+
+    /// Propagate the initial board and start the search on it
+    pub(crate) fn run_board(&mut self, board: &Board, visit: &mut dyn FnMut(&Board) -> bool) {
+        let mut grid = CandidateBoard::new(board);
+        if grid.propagate_observed(&mut |event| self.record(event)).is_ok() {
+            self.run(&mut grid, visit);
+        }
+    }
+
+    /// Assign the selected cell each remaining candidate in turn,
+    /// propagate the consequences and recurse until the board is full
+    /// or no candidate is left. Every solution is passed to `visit` and
+    /// the search stops as soon as `visit` returns `false`.
+    /// Returns whether the search should go on.
+    ///
+    /// All guesses are made on `grid` itself and undone when backtracking,
+    /// so `grid` is unchanged afterwards unless the search was stopped.
+    pub(crate) fn run(&mut self, grid: &mut CandidateBoard, visit: &mut dyn FnMut(&Board) -> bool) -> bool {
+        if !self.enter_node() {
+            return false;
+        }
+
+        let cell_id = match self.select_cell(grid) {
+            Some(cell_id) => cell_id,
+            None => return visit(grid.board()),
+        };
+
+        let values = self.value_order(grid.candidates(cell_id));
+        if let Some(progress) = &mut self.progress {
+            progress.descend(values.len());
+        }
+        for candidate_value in values {
+            self.record(TraceEvent::guess(cell_id, candidate_value));
+            let checkpoint = grid.checkpoint();
+            let consistent = grid.assign_observed(cell_id, candidate_value, &mut |event| self.record(event)).is_ok()
+                && grid.propagate_observed(&mut |event| self.record(event)).is_ok();
+
+            self.depth += 1;
+            self.max_depth = self.max_depth.max(self.depth);
+            if consistent && !self.run(grid, visit) {
+                return false;
+            }
+            self.depth -= 1;
+            grid.undo(checkpoint);
+            self.backtracks += 1;
+            self.record(TraceEvent::backtrack(cell_id, candidate_value));
+            if let Some(progress) = &mut self.progress {
+                progress.finish_branch();
+            }
+        }
+
+        if let Some(progress) = &mut self.progress {
+            progress.ascend();
+        }
+        true
+    }
+}
+
+/// `Solver` backed by the depth-first backtracking search
+#[derive(Clone,Copy,Debug,Default)]
+pub struct BacktrackingSolver {
+    pub heuristic: SearchHeuristic,
+    /// Jump back to the decision causing a dead end
+    /// instead of backtracking chronologically
+    pub backjumping: bool,
+}
+
+impl Solver for BacktrackingSolver {
+    fn solve(&self, board: &Board) -> SolveOutcome {
+        if !is_consistent(board) {
+            return SolveOutcome::Unsolvable;
+        }
+        let config = SolveConfig { heuristic: self.heuristic, backjumping: self.backjumping, ..SolveConfig::default() };
+        solve_with(board, &config)
+    }
+}
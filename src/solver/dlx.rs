@@ -0,0 +1,234 @@
+//! Dancing Links implementation of Knuth's Algorithm X.
+//!
+//! A Sudoku is an exact cover problem with 324 constraints (columns):
+//! every cell holds exactly one value, and every row, column and block
+//! contains every value exactly once. Each of the 729 candidate placements
+//! "value v in cell c" is a matrix row covering four of these columns.
+
+use crate::board::{Board, Value};
+use crate::solver::{SolveOutcome, Solver};
+use crate::topology::{BLOCK_OF, COLUMN_OF, ROW_OF};
+
+/// Number of constraint columns: 4 families × 81 constraints each
+const COUNT_CONSTRAINTS: usize = 4 * Board::COUNT_VALUES;
+
+/// Index of the root header node in the node arrays
+const ROOT: usize = 0;
+
+/// The toroidal doubly-linked matrix as parallel index arrays.
+/// Nodes `1..=COUNT_CONSTRAINTS` are the column headers, all
+/// further nodes belong to placement rows.
+struct Matrix {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    column: Vec<usize>,
+    size: Vec<usize>,
+    /// Placement (`cell_id * 9 + value - 1`) of every node
+    placement: Vec<usize>,
+}
+
+impl Matrix {
+    /// Build the exact cover matrix for the given board.
+    /// Assigned cells only contribute the row of their value,
+    /// unassigned cells contribute all nine candidate rows.
+    fn from_board(board: &Board) -> Self {
+        let mut matrix = Matrix {
+            left: Vec::with_capacity(1 + COUNT_CONSTRAINTS + 4 * 729),
+            right: Vec::with_capacity(1 + COUNT_CONSTRAINTS + 4 * 729),
+            up: Vec::with_capacity(1 + COUNT_CONSTRAINTS + 4 * 729),
+            down: Vec::with_capacity(1 + COUNT_CONSTRAINTS + 4 * 729),
+            column: Vec::with_capacity(1 + COUNT_CONSTRAINTS + 4 * 729),
+            size: vec![0; 1 + COUNT_CONSTRAINTS],
+            placement: Vec::with_capacity(1 + COUNT_CONSTRAINTS + 4 * 729),
+        };
+
+        for header in 0..=COUNT_CONSTRAINTS {
+            matrix.left.push(if header == 0 { COUNT_CONSTRAINTS } else { header - 1 });
+            matrix.right.push(if header == COUNT_CONSTRAINTS { 0 } else { header + 1 });
+            matrix.up.push(header);
+            matrix.down.push(header);
+            matrix.column.push(header);
+            matrix.placement.push(usize::MAX);
+        }
+
+        for cell_id in 0..Board::COUNT_VALUES {
+            let assigned = board[cell_id].value();
+            for value in 1..=9 {
+                if assigned != 0 && assigned != value {
+                    continue;
+                }
+                matrix.add_placement(cell_id, value);
+            }
+        }
+
+        matrix
+    }
+
+    /// Append the matrix row for "`value` in cell `cell_id`"
+    fn add_placement(&mut self, cell_id: usize, value: Value) {
+        let (row_id, column_id, block_id) = (ROW_OF[cell_id], COLUMN_OF[cell_id], BLOCK_OF[cell_id]);
+        let digit = value as usize - 1;
+        let constraints = [
+            1 + cell_id,
+            1 + 81 + row_id * 9 + digit,
+            1 + 2 * 81 + column_id * 9 + digit,
+            1 + 3 * 81 + block_id * 9 + digit,
+        ];
+
+        let first = self.left.len();
+        for (i, header) in constraints.into_iter().enumerate() {
+            let node = first + i;
+            self.left.push(if i == 0 { first + 3 } else { node - 1 });
+            self.right.push(if i == 3 { first } else { node + 1 });
+            self.up.push(self.up[header]);
+            self.down.push(header);
+            self.column.push(header);
+            self.placement.push(cell_id * 9 + digit);
+
+            let last = self.up[header];
+            self.down[last] = node;
+            self.up[header] = node;
+            self.size[header] += 1;
+        }
+    }
+
+    /// Remove column `c` from the header list and all rows intersecting it
+    fn cover(&mut self, c: usize) {
+        let (l, r) = (self.left[c], self.right[c]);
+        self.right[l] = r;
+        self.left[r] = l;
+
+        let mut i = self.down[c];
+        while i != c {
+            let mut j = self.right[i];
+            while j != i {
+                let (u, d) = (self.up[j], self.down[j]);
+                self.down[u] = d;
+                self.up[d] = u;
+                self.size[self.column[j]] -= 1;
+                j = self.right[j];
+            }
+            i = self.down[i];
+        }
+    }
+
+    /// Undo `cover(c)` in exactly the reverse order
+    fn uncover(&mut self, c: usize) {
+        let mut i = self.up[c];
+        while i != c {
+            let mut j = self.left[i];
+            while j != i {
+                self.size[self.column[j]] += 1;
+                let (u, d) = (self.up[j], self.down[j]);
+                self.down[u] = j;
+                self.up[d] = j;
+                j = self.left[j];
+            }
+            i = self.up[i];
+        }
+
+        let (l, r) = (self.left[c], self.right[c]);
+        self.right[l] = c;
+        self.left[r] = c;
+    }
+
+    /// The uncovered column with the fewest remaining rows, if any column is left
+    fn choose_column(&self) -> Option<usize> {
+        let mut best = None;
+        let mut c = self.right[ROOT];
+        while c != ROOT {
+            if best.is_none_or(|b| self.size[c] < self.size[b]) {
+                best = Some(c);
+            }
+            c = self.right[c];
+        }
+        best
+    }
+
+    /// Algorithm X. Every exact cover found is passed to `visit`
+    /// as the list of chosen placements. The search stops as soon
+    /// as `visit` returns `false`. Returns whether the search should go on.
+    fn search(&mut self, chosen: &mut Vec<usize>, visit: &mut dyn FnMut(&[usize]) -> bool) -> bool {
+        let c = match self.choose_column() {
+            Some(c) => c,
+            None => return visit(chosen),
+        };
+        if self.size[c] == 0 {
+            return true;
+        }
+
+        self.cover(c);
+        let mut r = self.down[c];
+        let mut go_on = true;
+        while r != c && go_on {
+            chosen.push(self.placement[r]);
+            let mut j = self.right[r];
+            while j != r {
+                self.cover(self.column[j]);
+                j = self.right[j];
+            }
+
+            go_on = self.search(chosen, visit);
+
+            let mut j = self.left[r];
+            while j != r {
+                self.uncover(self.column[j]);
+                j = self.left[j];
+            }
+            chosen.pop();
+            r = self.down[r];
+        }
+        self.uncover(c);
+
+        go_on
+    }
+}
+
+/// Turn a list of chosen placements back into a filled board with the givens of `puzzle`
+fn board_from_placements(puzzle: &Board, placements: &[usize]) -> Board {
+    let mut values = [0; Board::COUNT_VALUES];
+    for placement in placements {
+        values[placement / 9] = (placement % 9 + 1) as Value;
+    }
+    puzzle.with_values(&values)
+}
+
+/// Solve the board via Algorithm X and return the first solution found.
+/// Returns `None` if the board has no solution (including boards
+/// whose assigned cells already conflict with each other).
+pub fn solve(board: &Board) -> Option<Board> {
+    let mut solution = None;
+    Matrix::from_board(board).search(&mut vec![], &mut |placements| {
+        solution = Some(board_from_placements(board, placements));
+        false
+    });
+    solution
+}
+
+/// Count the solutions of the board, but stop as soon as `limit` solutions are found.
+pub fn count_solutions(board: &Board, limit: usize) -> usize {
+    let mut count = 0;
+    if limit == 0 {
+        return count;
+    }
+    Matrix::from_board(board).search(&mut vec![], &mut |_| {
+        count += 1;
+        count < limit
+    });
+    count
+}
+
+/// `Solver` backed by Algorithm X on the exact cover matrix
+#[derive(Clone,Copy,Debug,Default)]
+pub struct DlxSolver;
+
+impl Solver for DlxSolver {
+    fn solve(&self, board: &Board) -> SolveOutcome {
+        match solve(board) {
+            Some(solved) => SolveOutcome::Solved(solved),
+            None => SolveOutcome::Unsolvable,
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! Backtracking search with an explicit stack instead of recursion.
+//!
+//! The search explores the same tree as `backtracking`, but keeps one
+//! frame per guess on the heap. So its depth is not limited by the call
+//! stack and it can be paused after any `step` and resumed later.
+
+use crate::board::Board;
+use crate::candidate_set::CandidateSet;
+use crate::propagation::CandidateBoard;
+use crate::solver::backtracking::SearchHeuristic;
+use crate::solver::{is_consistent, SolveOutcome, Solver};
+
+/// A cell being branched on together with its untried candidates
+#[derive(Clone,Debug)]
+struct Frame {
+    cell_id: usize,
+    remaining: CandidateSet,
+    /// State of the grid before the first guess on this cell
+    checkpoint: usize,
+}
+
+/// What the search does next
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+enum Phase {
+    /// Pick a cell to branch on (or report the solution if there is none)
+    Expand,
+    /// Try the next candidate of the topmost frame
+    Advance,
+    Done,
+}
+
+/// The result of a single `IterativeSearch::step`
+#[derive(Clone,Debug)]
+pub enum SearchStep {
+    /// The search made a guess or backtracked and can go on
+    Progress,
+    /// A solution was found; further steps look for the next one
+    Solution(Board),
+    /// The whole search tree has been explored
+    Exhausted,
+}
+
+/// A resumable depth-first search for all solutions of a board.
+/// The assigned cells of the board must not violate any constraint.
+#[derive(Clone,Debug)]
+pub struct IterativeSearch {
+    heuristic: SearchHeuristic,
+    grid: CandidateBoard,
+    frames: Vec<Frame>,
+    phase: Phase,
+    nodes: u64,
+}
+
+impl IterativeSearch {
+    /// Propagate the initial board and prepare the search on it
+    pub fn new(board: &Board, heuristic: SearchHeuristic) -> Self {
+        let mut grid = CandidateBoard::new(board);
+        let phase = if grid.propagate().is_ok() { Phase::Expand } else { Phase::Done };
+        Self { heuristic, grid, frames: vec![], phase, nodes: 0 }
+    }
+
+    /// Number of search tree nodes visited so far
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// Number of guesses currently made
+    pub fn depth(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// The board with all guesses and propagated placements made so far
+    pub fn board(&self) -> &Board {
+        self.grid.board()
+    }
+
+    /// Make one guess or undo one guess
+    pub fn step(&mut self) -> SearchStep {
+        match self.phase {
+            Phase::Done => SearchStep::Exhausted,
+            Phase::Expand => {
+                self.nodes += 1;
+                self.phase = Phase::Advance;
+                match self.heuristic.select_cell(&self.grid) {
+                    Some(cell_id) => {
+                        let frame = Frame { cell_id, remaining: self.grid.candidates(cell_id), checkpoint: self.grid.checkpoint() };
+                        self.frames.push(frame);
+                        SearchStep::Progress
+                    },
+                    None => SearchStep::Solution(self.grid.board().clone()),
+                }
+            },
+            Phase::Advance => {
+                let frame = match self.frames.last_mut() {
+                    Some(frame) => frame,
+                    None => {
+                        self.phase = Phase::Done;
+                        return SearchStep::Exhausted;
+                    },
+                };
+
+                self.grid.undo(frame.checkpoint);
+                match frame.remaining.first() {
+                    Some(value) => {
+                        frame.remaining.remove(value);
+                        let cell_id = frame.cell_id;
+                        if self.grid.assign(cell_id, value).is_ok() && self.grid.propagate().is_ok() {
+                            self.phase = Phase::Expand;
+                        }
+                    },
+                    None => {
+                        self.frames.pop();
+                    },
+                }
+                SearchStep::Progress
+            },
+        }
+    }
+
+    /// Run the search until the next solution is found.
+    /// Returns `None` once the search tree is exhausted.
+    pub fn next_solution(&mut self) -> Option<Board> {
+        loop {
+            match self.step() {
+                SearchStep::Progress => {},
+                SearchStep::Solution(solved) => return Some(solved),
+                SearchStep::Exhausted => return None,
+            }
+        }
+    }
+}
+
+impl Iterator for IterativeSearch {
+    type Item = Board;
+
+    fn next(&mut self) -> Option<Board> {
+        self.next_solution()
+    }
+}
+
+/// `Solver` backed by the non-recursive backtracking search
+#[derive(Clone,Copy,Debug,Default)]
+pub struct IterativeSolver {
+    pub heuristic: SearchHeuristic,
+}
+
+impl Solver for IterativeSolver {
+    fn solve(&self, board: &Board) -> SolveOutcome {
+        if !is_consistent(board) {
+            return SolveOutcome::Unsolvable;
+        }
+        match IterativeSearch::new(board, self.heuristic).next_solution() {
+            Some(solved) => SolveOutcome::Solved(solved),
+            None => SolveOutcome::Unsolvable,
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Logic-only solving which never guesses.
+//!
+//! The solver repeatedly applies the simplest applicable strategy to a
+//! `CandidateBoard` until the board is complete or no strategy applies
+//! anymore. In the latter case, it reports the progress made as
+//! `SolveOutcome::Stalled`.
+
+use crate::board::Board;
+use crate::propagation::CandidateBoard;
+use crate::solver::{is_consistent, SolveOutcome, Solver};
+use crate::strategies::{self, Strategy, TechniqueSet};
+
+/// `Solver` applying the human-style strategies of the `strategies` module
+#[derive(Clone,Copy,Debug)]
+pub struct LogicSolver {
+    /// Also apply techniques like the Unique Rectangle which are only sound
+    /// if the puzzle has exactly one solution. On multi-solution puzzles,
+    /// they may eliminate candidates of valid solutions.
+    pub assume_unique: bool,
+    /// The techniques the solver may use
+    pub techniques: TechniqueSet,
+}
+
+impl Default for LogicSolver {
+    /// Use every technique which does not assume a unique solution
+    fn default() -> Self {
+        Self { assume_unique: false, techniques: TechniqueSet::all() }
+    }
+}
+
+impl LogicSolver {
+    /// A solver restricted to the given techniques
+    pub fn with_techniques(techniques: TechniqueSet) -> Self {
+        Self { techniques, ..Self::default() }
+    }
+
+    /// The strategies this solver applies, from the simplest to the most advanced
+    pub fn strategies(&self) -> Vec<Box<dyn Strategy>> {
+        strategies::all().into_iter()
+            .filter(|strategy| self.techniques.contains(strategy.technique()))
+            .filter(|strategy| self.assume_unique || !strategy.requires_uniqueness())
+            .collect()
+    }
+
+    /// Can the board be solved completely with the techniques of this solver?
+    pub fn can_solve(&self, board: &Board) -> bool {
+        matches!(self.solve(board), SolveOutcome::Solved(_))
+    }
+}
+
+impl Solver for LogicSolver {
+    fn solve(&self, board: &Board) -> SolveOutcome {
+        if !is_consistent(board) {
+            return SolveOutcome::Unsolvable;
+        }
+
+        let strategies = self.strategies();
+        let mut grid = CandidateBoard::new(board);
+        if grid.is_contradictory() {
+            return SolveOutcome::Unsolvable;
+        }
+
+        while !grid.is_solved() {
+            let deduction = match strategies::next_deduction(&grid, &strategies) {
+                Some(deduction) => deduction,
+                None => return SolveOutcome::Stalled(grid.board().clone()),
+            };
+            if deduction.apply(&mut grid).is_err() {
+                return SolveOutcome::Unsolvable;
+            }
+        }
+
+        SolveOutcome::Solved(grid.board().clone())
+    }
+}
@@ -0,0 +1,98 @@
+//! Solver backends operating directly on a `Board`.
+//!
+//! Every backend implements the `Solver` trait so applications
+//! can swap strategies without changing the calling code.
+
+pub mod backjumping;
+pub mod backtracking;
+pub mod dlx;
+pub mod iterative;
+pub mod logic;
+pub mod parallel;
+pub mod progress;
+pub mod sat;
+pub mod trace;
+
+pub use backtracking::{BacktrackingSolver, SearchHeuristic};
+pub use dlx::DlxSolver;
+pub use iterative::{IterativeSearch, IterativeSolver, SearchStep};
+pub use logic::LogicSolver;
+pub use parallel::ParallelSolver;
+pub use progress::SearchProgress;
+pub use sat::SatSolver;
+pub use trace::{SolveTrace, TraceEvent};
+
+use std::time::Duration;
+
+use crate::board::Board;
+use crate::sudoku::Sudoku;
+
+/// The result of running a `Solver` on a board
+#[derive(Clone,Debug)]
+pub enum SolveOutcome {
+    /// A complete assignment satisfying all constraints was found
+    Solved(Board),
+    /// The board provably has no solution
+    Unsolvable,
+    /// The solver gave up without deciding solvability.
+    /// The board contains all placements it made until then.
+    Stalled(Board),
+    /// The search budget was exceeded before the search finished
+    Aborted(SolveStats),
+}
+
+/// Statistics about a (possibly unfinished) search
+#[derive(Clone,Copy,Debug,Default)]
+pub struct SolveStats {
+    /// Number of search tree nodes visited
+    pub nodes: u64,
+    /// Number of guesses which were undone
+    pub backtracks: u64,
+    /// Number of values placed by propagating singles
+    pub propagations: u64,
+    /// The most guesses on the way from the root to a node
+    pub max_depth: usize,
+    /// Wall-clock time spent searching
+    pub elapsed: Duration,
+}
+
+/// Settings and budget of a search as used by `Sudoku::solve_with`
+#[derive(Clone,Copy,Debug,Default)]
+pub struct SolveConfig {
+    pub heuristic: SearchHeuristic,
+    /// Abort the search after this much wall-clock time
+    pub timeout: Option<Duration>,
+    /// Abort the search after visiting this many search tree nodes
+    pub node_limit: Option<u64>,
+    /// Jump back to the decision causing a dead end (see `backjumping`)
+    /// instead of backtracking chronologically
+    pub backjumping: bool,
+    /// Try the candidates of a cell in pseudo-random order and break ties
+    /// between equally constrained cells pseudo-randomly.
+    /// The same seed always reproduces the same search.
+    pub seed: Option<u64>,
+}
+
+impl SolveOutcome {
+    /// The solved board, if a solution was found
+    pub fn solution(&self) -> Option<&Board> {
+        match self {
+            SolveOutcome::Solved(board) => Some(board),
+            _ => None,
+        }
+    }
+}
+
+/// A pluggable strategy to solve a Sudoku board
+pub trait Solver {
+    /// Solve the board. Boards whose assigned cells already
+    /// violate a constraint are reported as `SolveOutcome::Unsolvable`.
+    fn solve(&self, board: &Board) -> SolveOutcome;
+}
+
+/// Do the assigned cells of the board satisfy all Sudoku constraints?
+fn is_consistent(board: &Board) -> bool {
+    let mut sudoku = Sudoku::default();
+    sudoku.init_board(board);
+    sudoku.verify_board().is_ok()
+}
@@ -0,0 +1,86 @@
+//! Multi-threaded backtracking search.
+//!
+//! The board is propagated and the first branching cell is selected as usual.
+//! Each candidate value of this cell opens a subtree which is searched by one
+//! of several worker threads. The first solution found by any worker
+//! cancels all other workers.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::board::Board;
+use crate::propagation::CandidateBoard;
+use crate::solver::backtracking::{Search, SearchHeuristic};
+use crate::solver::{is_consistent, SolveOutcome, Solver};
+
+/// `Solver` distributing the top of the backtracking search tree across threads
+#[derive(Clone,Copy,Debug)]
+pub struct ParallelSolver {
+    pub heuristic: SearchHeuristic,
+    /// Maximum number of worker threads
+    pub threads: usize,
+}
+
+impl Default for ParallelSolver {
+    /// Use as many threads as the machine offers parallelism
+    fn default() -> Self {
+        let threads = thread::available_parallelism().map_or(1, |count| count.get());
+        Self { heuristic: SearchHeuristic::default(), threads }
+    }
+}
+
+impl Solver for ParallelSolver {
+    fn solve(&self, board: &Board) -> SolveOutcome {
+        if !is_consistent(board) {
+            return SolveOutcome::Unsolvable;
+        }
+
+        let mut grid = CandidateBoard::new(board);
+        if grid.propagate().is_err() {
+            return SolveOutcome::Unsolvable;
+        }
+        let cell_id = match self.heuristic.select_cell(&grid) {
+            Some(cell_id) => cell_id,
+            None => return SolveOutcome::Solved(grid.board().clone()),
+        };
+
+        let branches: Vec<CandidateBoard> = grid.candidates(cell_id).iter()
+            .filter_map(|value| {
+                let mut branch = grid.clone();
+                branch.assign(cell_id, value).ok()?;
+                branch.propagate().ok()?;
+                Some(branch)
+            })
+            .collect();
+
+        let found = AtomicBool::new(false);
+        let next_branch = AtomicUsize::new(0);
+        let solution = Mutex::new(None);
+
+        thread::scope(|scope| {
+            for _ in 0..self.threads.clamp(1, branches.len().max(1)) {
+                scope.spawn(|| {
+                    let mut search = Search::new(self.heuristic).cancelled_by(&found);
+                    loop {
+                        let branch_id = next_branch.fetch_add(1, Ordering::Relaxed);
+                        if branch_id >= branches.len() || found.load(Ordering::Relaxed) {
+                            break;
+                        }
+                        search.run(&mut branches[branch_id].clone(), &mut |solved| {
+                            if !found.swap(true, Ordering::Relaxed) {
+                                *solution.lock().unwrap() = Some(solved.clone());
+                            }
+                            false
+                        });
+                    }
+                });
+            }
+        });
+
+        match solution.into_inner().unwrap() {
+            Some(solved) => SolveOutcome::Solved(solved),
+            None => SolveOutcome::Unsolvable,
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! Progress reports of long searches, so an application can tell a slow search
+//! from a stuck one.
+//!
+//! Besides the nodes and the time, a report estimates the share of the search
+//! tree explored: every finished branch of the top level counts as its share of
+//! all branches there, and a partly finished one by the same rule one level below.
+
+use std::time::{Duration, Instant};
+
+/// How far a search got, as passed to the observer of `Sudoku::solve_observed`
+#[derive(Clone,Copy,Debug,Default)]
+pub struct SearchProgress {
+    /// Number of search tree nodes visited so far
+    pub nodes: u64,
+    /// Estimated share of the search tree explored, from 0 to 1
+    pub explored: f64,
+    /// Wall-clock time spent searching
+    pub elapsed: Duration,
+}
+
+/// The observer of a search, called with the progress every `REPORT_NODES` nodes
+pub(crate) type ProgressHook<'a> = Box<dyn FnMut(&SearchProgress) + 'a>;
+
+/// Number of nodes between two reports
+const REPORT_NODES: u64 = 1 << 10;
+
+/// The branches of a search on its way down the tree, reporting to the hook
+pub(crate) struct ProgressTracker<'a> {
+    hook: ProgressHook<'a>,
+    /// For every level entered the number of its finished branches and of all its branches
+    levels: Vec<(usize, usize)>,
+}
+
+impl<'a> ProgressTracker<'a> {
+    pub(crate) fn new(hook: ProgressHook<'a>) -> Self {
+        Self { hook, levels: vec![] }
+    }
+
+    /// Enter a level of the search with `count` branches
+    pub(crate) fn descend(&mut self, count: usize) {
+        self.levels.push((0, count));
+    }
+
+    /// Finish a branch of the current level
+    pub(crate) fn finish_branch(&mut self) {
+        if let Some((finished, _)) = self.levels.last_mut() {
+            *finished += 1;
+        }
+    }
+
+    /// Leave the current level after all its branches
+    pub(crate) fn ascend(&mut self) {
+        self.levels.pop();
+    }
+
+    /// Report the progress if another `REPORT_NODES` nodes were visited
+    pub(crate) fn visit(&mut self, nodes: u64, started: Instant) {
+        if nodes.is_multiple_of(REPORT_NODES) {
+            let progress = SearchProgress { nodes, explored: self.explored(), elapsed: started.elapsed() };
+            (self.hook)(&progress);
+        }
+    }
+
+    fn explored(&self) -> f64 {
+        let mut share = 1.0;
+        let mut explored = 0.0;
+        for (finished, count) in self.levels.iter().filter(|(_, count)| *count > 0) {
+            share /= *count as f64;
+            explored += *finished as f64 * share;
+        }
+        explored
+    }
+}
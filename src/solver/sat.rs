@@ -0,0 +1,291 @@
+//! SAT encoding of a board and a small built-in DPLL solver.
+//!
+//! The board is encoded as CNF over 729 boolean variables, one per placement
+//! "value v in cell c". Clauses require every cell to hold exactly one value
+//! and every row, column and block to contain every value exactly once.
+//! Assigned cells become unit clauses. The CNF can also be exported in the
+//! DIMACS format to cross-check results with any external SAT solver.
+
+use std::fmt::Write;
+use std::mem;
+
+use crate::board::{Board, Value};
+use crate::solver::{SolveOutcome, Solver};
+use crate::topology::HOUSES;
+
+/// A literal in DIMACS convention: a positive variable number for the
+/// variable itself, the negated number for its negation
+pub type Literal = i32;
+
+/// A formula in conjunctive normal form
+#[derive(Clone,Debug,Default)]
+pub struct Cnf {
+    /// Variables are numbered `1..=num_vars`
+    pub num_vars: usize,
+    pub clauses: Vec<Vec<Literal>>,
+}
+
+impl Cnf {
+    /// The formula in the DIMACS CNF text format
+    pub fn to_dimacs(&self) -> String {
+        let mut out = format!("p cnf {} {}\n", self.num_vars, self.clauses.len());
+        for clause in self.clauses.iter() {
+            for literal in clause.iter() {
+                let _ = write!(out, "{} ", literal);
+            }
+            out.push_str("0\n");
+        }
+        out
+    }
+}
+
+/// The variable stating "`value` in cell `cell_id`"
+pub fn variable(cell_id: usize, value: Value) -> Literal {
+    (cell_id * 9 + value as usize) as Literal
+}
+
+/// Add clauses requiring exactly one of the literals to be true
+fn exactly_one(clauses: &mut Vec<Vec<Literal>>, literals: &[Literal]) {
+    clauses.push(literals.to_vec());
+    for (i, a) in literals.iter().enumerate() {
+        for b in literals[i + 1..].iter() {
+            clauses.push(vec![-a, -b]);
+        }
+    }
+}
+
+/// Encode the board as CNF
+pub fn encode(board: &Board) -> Cnf {
+    let mut clauses = vec![];
+
+    for cell_id in 0..Board::COUNT_VALUES {
+        let literals: Vec<Literal> = (1..=9).map(|value| variable(cell_id, value)).collect();
+        exactly_one(&mut clauses, &literals);
+    }
+
+    for cells in HOUSES {
+        for value in 1..=9 {
+            let literals: Vec<Literal> = cells.iter().map(|cell_id| variable(*cell_id, value)).collect();
+            exactly_one(&mut clauses, &literals);
+        }
+    }
+
+    for cell_id in 0..Board::COUNT_VALUES {
+        let value = board[cell_id].value();
+        if (1..=9).contains(&value) {
+            clauses.push(vec![variable(cell_id, value)]);
+        }
+    }
+
+    Cnf { num_vars: 9 * Board::COUNT_VALUES, clauses }
+}
+
+/// Index of a literal into the watch lists
+fn literal_index(literal: Literal) -> usize {
+    2 * literal.unsigned_abs() as usize + (literal < 0) as usize
+}
+
+/// Truth value of a literal: 1 true, -1 false, 0 unassigned
+fn truth(values: &[i8], literal: Literal) -> i8 {
+    let value = values[literal.unsigned_abs() as usize];
+    if literal < 0 { -value } else { value }
+}
+
+/// DPLL with unit propagation on two watched literals per clause
+/// and chronological backtracking
+struct Dpll {
+    clauses: Vec<Vec<Literal>>,
+    watches: Vec<Vec<usize>>,
+    values: Vec<i8>,
+    trail: Vec<Literal>,
+    queue_head: usize,
+    /// Per decision: the trail length before it, its literal and whether
+    /// it is already the flipped second branch
+    decisions: Vec<(usize, Literal, bool)>,
+}
+
+impl Dpll {
+    /// Set up the watch lists. Fails if the unit clauses already conflict.
+    fn new(cnf: &Cnf) -> Option<Self> {
+        let mut dpll = Dpll {
+            clauses: vec![],
+            watches: vec![vec![]; 2 * cnf.num_vars + 2],
+            values: vec![0; cnf.num_vars + 1],
+            trail: vec![],
+            queue_head: 0,
+            decisions: vec![],
+        };
+
+        for clause in cnf.clauses.iter() {
+            match clause[..] {
+                [] => return None,
+                [literal] => {
+                    if !dpll.enqueue(literal) {
+                        return None;
+                    }
+                },
+                _ => {
+                    let index = dpll.clauses.len();
+                    dpll.watches[literal_index(clause[0])].push(index);
+                    dpll.watches[literal_index(clause[1])].push(index);
+                    dpll.clauses.push(clause.clone());
+                },
+            }
+        }
+
+        Some(dpll)
+    }
+
+    /// Make the literal true. Returns `false` if it is already false.
+    fn enqueue(&mut self, literal: Literal) -> bool {
+        match truth(&self.values, literal) {
+            -1 => false,
+            1 => true,
+            _ => {
+                self.values[literal.unsigned_abs() as usize] = if literal < 0 { -1 } else { 1 };
+                self.trail.push(literal);
+                true
+            },
+        }
+    }
+
+    /// Propagate all unit clauses. Returns `false` on a conflict.
+    fn propagate(&mut self) -> bool {
+        while self.queue_head < self.trail.len() {
+            let false_literal = -self.trail[self.queue_head];
+            self.queue_head += 1;
+
+            let mut watchers = mem::take(&mut self.watches[literal_index(false_literal)]);
+            let mut i = 0;
+            let mut conflict = false;
+            while i < watchers.len() {
+                let clause = &mut self.clauses[watchers[i]];
+                if clause[0] == false_literal {
+                    clause.swap(0, 1);
+                }
+                if truth(&self.values, clause[0]) == 1 {
+                    i += 1;
+                    continue;
+                }
+
+                if let Some(k) = (2..clause.len()).find(|k| truth(&self.values, clause[*k]) != -1) {
+                    clause.swap(1, k);
+                    self.watches[literal_index(clause[1])].push(watchers[i]);
+                    watchers.swap_remove(i);
+                    continue;
+                }
+
+                let unit = clause[0];
+                if !self.enqueue(unit) {
+                    conflict = true;
+                    break;
+                }
+                i += 1;
+            }
+
+            self.watches[literal_index(false_literal)] = watchers;
+            if conflict {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Pick the literal to branch on: the first unassigned literal of the
+    /// unsatisfied all-positive clause with the fewest unassigned literals,
+    /// otherwise any unassigned variable. `None` if everything is assigned.
+    fn pick_literal(&self) -> Option<Literal> {
+        let mut best: Option<(usize, Literal)> = None;
+        for clause in self.clauses.iter().filter(|clause| clause.iter().all(|literal| *literal > 0)) {
+            if clause.iter().any(|literal| truth(&self.values, *literal) == 1) {
+                continue;
+            }
+            let mut open = clause.iter().filter(|literal| truth(&self.values, **literal) == 0);
+            let first = match open.next() {
+                Some(first) => *first,
+                None => continue,
+            };
+            let count = 1 + open.count();
+            if best.is_none_or(|(best_count, _)| count < best_count) {
+                best = Some((count, first));
+            }
+        }
+
+        best.map(|(_, literal)| literal)
+            .or_else(|| (1..self.values.len()).find(|var| self.values[*var] == 0).map(|var| var as Literal))
+    }
+
+    /// Undo all assignments made after the trail had length `length`
+    fn undo(&mut self, length: usize) {
+        for literal in self.trail.drain(length..) {
+            self.values[literal.unsigned_abs() as usize] = 0;
+        }
+        self.queue_head = length;
+    }
+
+    /// Run the search and return whether the formula is satisfiable
+    fn search(&mut self) -> bool {
+        if !self.propagate() {
+            return false;
+        }
+
+        loop {
+            let literal = match self.pick_literal() {
+                Some(literal) => literal,
+                None => return true,
+            };
+            self.decisions.push((self.trail.len(), literal, false));
+            self.enqueue(literal);
+
+            while !self.propagate() {
+                loop {
+                    let (length, literal, flipped) = match self.decisions.pop() {
+                        Some(decision) => decision,
+                        None => return false,
+                    };
+                    self.undo(length);
+                    if !flipped {
+                        self.decisions.push((length, -literal, true));
+                        self.enqueue(-literal);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Decide satisfiability of the formula. Returns a satisfying assignment
+/// indexed by variable number (index 0 is unused) or `None` if there is none.
+pub fn solve_cnf(cnf: &Cnf) -> Option<Vec<bool>> {
+    let mut dpll = Dpll::new(cnf)?;
+    if !dpll.search() {
+        return None;
+    }
+    Some(dpll.values.iter().map(|value| *value == 1).collect())
+}
+
+/// Solve the board via its SAT encoding and return the solution found,
+/// or `None` if the board has no solution
+pub fn solve(board: &Board) -> Option<Board> {
+    let model = solve_cnf(&encode(board))?;
+
+    let mut values = [0; Board::COUNT_VALUES];
+    for (cell_id, value) in values.iter_mut().enumerate() {
+        *value = (1..=9).find(|candidate| model[variable(cell_id, *candidate) as usize]).unwrap_or(0);
+    }
+    Some(board.with_values(&values))
+}
+
+/// `Solver` backed by the SAT encoding and the built-in DPLL solver
+#[derive(Clone,Copy,Debug,Default)]
+pub struct SatSolver;
+
+impl Solver for SatSolver {
+    fn solve(&self, board: &Board) -> SolveOutcome {
+        match solve(board) {
+            Some(solved) => SolveOutcome::Solved(solved),
+            None => SolveOutcome::Unsolvable,
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Recording of the decisions made by the backtracking search.
+
+use std::fmt;
+
+use crate::board::Value;
+
+/// One step of the search. Rows and columns are zero-based.
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TraceEvent {
+    /// The search tried a value because no deduction applied
+    Guess { row: usize, col: usize, value: Value },
+    /// Propagation placed a value which is the only possibility left
+    Placement { row: usize, col: usize, value: Value },
+    /// A candidate was removed because a peer got assigned that value
+    Elimination { row: usize, col: usize, value: Value },
+    /// The guess of that value led to a contradiction and was undone
+    Backtrack { row: usize, col: usize, value: Value },
+}
+
+impl TraceEvent {
+    pub(crate) fn guess(cell_id: usize, value: Value) -> Self {
+        TraceEvent::Guess { row: cell_id / 9, col: cell_id % 9, value }
+    }
+
+    pub(crate) fn placement(cell_id: usize, value: Value) -> Self {
+        TraceEvent::Placement { row: cell_id / 9, col: cell_id % 9, value }
+    }
+
+    pub(crate) fn elimination(cell_id: usize, value: Value) -> Self {
+        TraceEvent::Elimination { row: cell_id / 9, col: cell_id % 9, value }
+    }
+
+    pub(crate) fn backtrack(cell_id: usize, value: Value) -> Self {
+        TraceEvent::Backtrack { row: cell_id / 9, col: cell_id % 9, value }
+    }
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TraceEvent::Guess { row, col, value } => write!(f, "guess r{}c{}={}", row + 1, col + 1, value),
+            TraceEvent::Placement { row, col, value } => write!(f, "place r{}c{}={}", row + 1, col + 1, value),
+            TraceEvent::Elimination { row, col, value } => write!(f, "eliminate {} from r{}c{}", value, row + 1, col + 1),
+            TraceEvent::Backtrack { row, col, value } => write!(f, "backtrack r{}c{}={}", row + 1, col + 1, value),
+        }
+    }
+}
+
+/// The ordered list of events of a search
+#[derive(Clone,Debug,Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolveTrace {
+    events: Vec<TraceEvent>,
+}
+
+impl SolveTrace {
+    pub(crate) fn push(&mut self, event: TraceEvent) {
+        self.events.push(event);
+    }
+
+    /// All events in the order they happened
+    pub fn events(&self) -> &[TraceEvent] {
+        &self.events
+    }
+
+    /// How many guesses did the search make?
+    pub fn count_guesses(&self) -> usize {
+        self.events.iter().filter(|event| matches!(event, TraceEvent::Guess { .. })).count()
+    }
+
+    /// How many guesses were undone?
+    pub fn count_backtracks(&self) -> usize {
+        self.events.iter().filter(|event| matches!(event, TraceEvent::Backtrack { .. })).count()
+    }
+}
+
+impl fmt::Display for SolveTrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for event in self.events.iter() {
+            writeln!(f, "{}", event)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use crate::board::{Board, Value};
+use crate::candidate_set::CandidateSet;
+use crate::propagation::CandidateBoard;
+use crate::strategies::{cell_names, combinations, sees, Deduction, Strategy, Technique};
+use crate::topology::HOUSES;
+
+/// Largest number of cells of an almost locked set considered by the search
+const MAX_ALS_SIZE: usize = 4;
+
+/// An almost locked set: `n` unassigned cells of one house with `n + 1` candidates in total
+struct AlmostLockedSet {
+    cells: Vec<usize>,
+    values: Vec<Value>,
+}
+
+impl AlmostLockedSet {
+    /// Cells of the set having `value` as candidate
+    fn cells_with(&self, grid: &CandidateBoard, value: Value) -> Vec<usize> {
+        self.cells.iter().copied().filter(|cell_id| grid.candidates(*cell_id).contains(value)).collect()
+    }
+}
+
+/// All distinct almost locked sets of up to `MAX_ALS_SIZE` cells
+fn almost_locked_sets(grid: &CandidateBoard) -> Vec<AlmostLockedSet> {
+    let mut sets: Vec<AlmostLockedSet> = vec![];
+    for cells in HOUSES {
+        let open_cells: Vec<usize> = cells.into_iter()
+            .filter(|cell_id| !grid.candidates(*cell_id).is_empty())
+            .collect();
+
+        for size in 1..=MAX_ALS_SIZE {
+            for cells in combinations(&open_cells, size) {
+                let values: Vec<Value> = cells.iter()
+                    .fold(CandidateSet::empty(), |values, cell_id| values | grid.candidates(*cell_id))
+                    .iter()
+                    .collect();
+                if values.len() == size + 1 && !sets.iter().any(|set| set.cells == cells) {
+                    sets.push(AlmostLockedSet { cells, values });
+                }
+            }
+        }
+    }
+    sets
+}
+
+/// ALS-XZ: two disjoint almost locked sets A and B share a restricted common
+/// candidate X, i.e. every X of A sees every X of B, so at most one of them
+/// holds X. Then the other set is locked without X. So for another common
+/// candidate Z, one of the sets holds Z and Z can be eliminated from every
+/// cell seeing all Z candidates of both sets.
+/// The deduction's cells are the cells of A followed by the cells of B.
+#[derive(Clone,Copy,Debug,Default)]
+pub struct AlsXz;
+
+impl Strategy for AlsXz {
+    fn technique(&self) -> Technique {
+        Technique::AlsXz
+    }
+
+    fn find(&self, grid: &CandidateBoard) -> Option<Deduction> {
+        let sets = almost_locked_sets(grid);
+        for (i, a) in sets.iter().enumerate() {
+            for b in sets[i + 1..].iter() {
+                if a.cells.iter().any(|cell_id| b.cells.contains(cell_id)) {
+                    continue;
+                }
+                let common: Vec<Value> = a.values.iter().copied().filter(|value| b.values.contains(value)).collect();
+                if common.len() < 2 {
+                    continue;
+                }
+
+                for x in common.iter().copied() {
+                    let (a_x, b_x) = (a.cells_with(grid, x), b.cells_with(grid, x));
+                    if !a_x.iter().all(|cell_a| b_x.iter().all(|cell_b| sees(*cell_a, *cell_b))) {
+                        continue;
+                    }
+
+                    for z in common.iter().copied().filter(|z| *z != x) {
+                        let z_cells = [a.cells_with(grid, z), b.cells_with(grid, z)].concat();
+                        let eliminations: Vec<(usize, Value)> = (0..Board::COUNT_VALUES)
+                            .filter(|cell_id| !a.cells.contains(cell_id) && !b.cells.contains(cell_id))
+                            .filter(|cell_id| grid.candidates(*cell_id).contains(z))
+                            .filter(|cell_id| z_cells.iter().all(|other| sees(*cell_id, *other)))
+                            .map(|cell_id| (cell_id, z))
+                            .collect();
+                        if eliminations.is_empty() {
+                            continue;
+                        }
+
+                        return Some(Deduction {
+                            technique: self.technique(),
+                            explanation: format!("almost locked sets {} and {} with restricted common {} both fit {}",
+                                cell_names(&a.cells), cell_names(&b.cells), x, z),
+                            cells: [a.cells.clone(), b.cells.clone()].concat(),
+                            placements: vec![],
+                            eliminations,
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+}
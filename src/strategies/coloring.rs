@@ -0,0 +1,107 @@
+use crate::board::{Board, Value};
+use crate::propagation::CandidateBoard;
+use crate::strategies::{cell_names, sees, Deduction, Strategy, Technique};
+use crate::topology::{COUNT_HOUSES, HOUSES};
+
+/// Conjugate pairs of a value: houses in which the value fits into exactly two cells.
+/// Either of the two cells holds the value.
+fn conjugate_pairs(grid: &CandidateBoard, value: Value) -> Vec<(usize, usize)> {
+    (0..COUNT_HOUSES)
+        .filter_map(|house_id| {
+            let positions: Vec<usize> = HOUSES[house_id].into_iter()
+                .filter(|cell_id| grid.candidates(*cell_id).contains(value))
+                .collect();
+            match positions[..] {
+                [a, b] => Some((a, b)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Split the cells connected by conjugate pairs into components and color
+/// each component with two alternating colors. Returns per component the
+/// cells of both colors. Within a component, one color holds the value
+/// in all of its cells and the other color in none.
+fn color_components(pairs: &[(usize, usize)]) -> Vec<[Vec<usize>; 2]> {
+    let mut color: [Option<usize>; Board::COUNT_VALUES] = [None; Board::COUNT_VALUES];
+    let mut components = vec![];
+
+    for (start, _) in pairs.iter() {
+        if color[*start].is_some() {
+            continue;
+        }
+
+        let mut component: [Vec<usize>; 2] = [vec![], vec![]];
+        let mut stack = vec![(*start, 0)];
+        color[*start] = Some(0);
+        while let Some((cell_id, cell_color)) = stack.pop() {
+            component[cell_color].push(cell_id);
+            for (a, b) in pairs.iter() {
+                let neighbor = if *a == cell_id { *b } else if *b == cell_id { *a } else { continue };
+                if color[neighbor].is_none() {
+                    color[neighbor] = Some(1 - cell_color);
+                    stack.push((neighbor, 1 - cell_color));
+                }
+            }
+        }
+        components.push(component);
+    }
+
+    components
+}
+
+/// Simple coloring of the conjugate pair chains of a single value.
+///
+/// * Color wrap: two cells of the same color see each other, so that color
+///   can't hold the value and it is eliminated from all its cells.
+/// * Color trap: a cell outside the chain sees cells of both colors, so it
+///   can't hold the value either way.
+///
+/// The deduction's cells are the cells of the colored chain.
+#[derive(Clone,Copy,Debug,Default)]
+pub struct SimpleColoring;
+
+impl Strategy for SimpleColoring {
+    fn technique(&self) -> Technique {
+        Technique::SimpleColoring
+    }
+
+    fn find(&self, grid: &CandidateBoard) -> Option<Deduction> {
+        for value in 1..=9 {
+            for colors in color_components(&conjugate_pairs(grid, value)) {
+                let chain: Vec<usize> = colors.concat();
+
+                for (color_id, cells) in colors.iter().enumerate() {
+                    let wrapped = cells.iter().any(|a| cells.iter().any(|b| sees(*a, *b)));
+                    if wrapped {
+                        return Some(Deduction {
+                            technique: self.technique(),
+                            explanation: format!("two cells of the color {} in the chain of {} see each other, so the other color {} holds {}",
+                                cell_names(cells), value, cell_names(&colors[1 - color_id]), value),
+                            placements: colors[1 - color_id].iter().map(|cell_id| (*cell_id, value)).collect(),
+                            eliminations: cells.iter().map(|cell_id| (*cell_id, value)).collect(),
+                            cells: chain,
+                        });
+                    }
+                }
+
+                let eliminations: Vec<(usize, Value)> = (0..Board::COUNT_VALUES)
+                    .filter(|cell_id| !chain.contains(cell_id) && grid.candidates(*cell_id).contains(value))
+                    .filter(|cell_id| colors.iter().all(|cells| cells.iter().any(|other| sees(*cell_id, *other))))
+                    .map(|cell_id| (cell_id, value))
+                    .collect();
+                if !eliminations.is_empty() {
+                    return Some(Deduction {
+                        technique: self.technique(),
+                        explanation: format!("either {} or {} hold {}", cell_names(&colors[0]), cell_names(&colors[1]), value),
+                        cells: chain,
+                        placements: vec![],
+                        eliminations,
+                    });
+                }
+            }
+        }
+        None
+    }
+}
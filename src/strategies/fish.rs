@@ -0,0 +1,165 @@
+use crate::board::Value;
+use crate::propagation::CandidateBoard;
+use crate::strategies::{cell_names, combinations, Deduction, Strategy, Technique};
+use crate::topology::BLOCK_OF;
+
+/// Cell ID of the cell at position `index` of a line
+/// which is a row if `by_rows` is set and a column otherwise
+pub(crate) fn line_cell(by_rows: bool, line: usize, index: usize) -> usize {
+    if by_rows { line * 9 + index } else { index * 9 + line }
+}
+
+/// Positions within the line which still have `value` as candidate
+pub(crate) fn line_positions(grid: &CandidateBoard, value: Value, by_rows: bool, line: usize) -> Vec<usize> {
+    (0..9).filter(|index| grid.candidates(line_cell(by_rows, line, *index)).contains(value)).collect()
+}
+
+/// Name of a line as used in explanations
+pub(crate) fn line_name(by_rows: bool, line: usize) -> String {
+    format!("{} {}", if by_rows { "row" } else { "column" }, line + 1)
+}
+
+/// `size` rows (base lines) in which a value is confined to the same `size` columns
+/// (cover lines), or vice versa. The value can be eliminated from all other
+/// cells of the cover lines. Size 2 is the X-Wing, 3 the Swordfish, 4 the Jellyfish.
+#[derive(Clone,Copy,Debug)]
+pub struct Fish {
+    /// Number of base and cover lines (2 to 4)
+    pub size: usize,
+}
+
+impl Strategy for Fish {
+    fn technique(&self) -> Technique {
+        match self.size {
+            2 => Technique::XWing,
+            3 => Technique::Swordfish,
+            _ => Technique::Jellyfish,
+        }
+    }
+
+    fn find(&self, grid: &CandidateBoard) -> Option<Deduction> {
+        for value in 1..=9 {
+            for by_rows in [true, false] {
+                let base_candidates: Vec<usize> = (0..9)
+                    .filter(|line| (2..=self.size).contains(&line_positions(grid, value, by_rows, *line).len()))
+                    .collect();
+
+                for base in combinations(&base_candidates, self.size) {
+                    let mut cover: Vec<usize> = base.iter().flat_map(|line| line_positions(grid, value, by_rows, *line)).collect();
+                    cover.sort();
+                    cover.dedup();
+                    if cover.len() != self.size {
+                        continue;
+                    }
+
+                    let eliminations: Vec<(usize, Value)> = cover.iter()
+                        .flat_map(|cover_line| (0..9)
+                            .filter(|index| !base.contains(index))
+                            .map(|index| line_cell(!by_rows, *cover_line, index)))
+                        .filter(|cell_id| grid.candidates(*cell_id).contains(value))
+                        .map(|cell_id| (cell_id, value))
+                        .collect();
+                    if eliminations.is_empty() {
+                        continue;
+                    }
+
+                    let cells = base.iter()
+                        .flat_map(|line| line_positions(grid, value, by_rows, *line).into_iter().map(|index| line_cell(by_rows, *line, index)))
+                        .collect();
+                    let base_names = base.iter().map(|line| line_name(by_rows, *line)).collect::<Vec<String>>().join(", ");
+                    let cover_names = cover.iter().map(|line| line_name(!by_rows, *line)).collect::<Vec<String>>().join(", ");
+                    return Some(Deduction {
+                        technique: self.technique(),
+                        explanation: format!("{} in {} is confined to {}", value, base_names, cover_names),
+                        cells,
+                        placements: vec![],
+                        eliminations,
+                    });
+                }
+            }
+        }
+        None
+    }
+}
+
+/// A fish whose base lines have additional candidates (fins) outside the cover
+/// lines, all within a single block. Either a fin holds the value or the fish
+/// does, so the value can be eliminated from the cover line cells in the fin's
+/// block. If removing the fins leaves a base line with a single candidate,
+/// the fish is called sashimi.
+#[derive(Clone,Copy,Debug)]
+pub struct FinnedFish {
+    /// Number of base and cover lines (2 to 4)
+    pub size: usize,
+}
+
+impl Strategy for FinnedFish {
+    fn technique(&self) -> Technique {
+        Technique::FinnedFish
+    }
+
+    fn find(&self, grid: &CandidateBoard) -> Option<Deduction> {
+        let fish_name = ["X-Wing", "Swordfish", "Jellyfish"][self.size.clamp(2, 4) - 2];
+        for value in 1..=9 {
+            for by_rows in [true, false] {
+                let base_candidates: Vec<usize> = (0..9)
+                    .filter(|line| (1..=self.size + 2).contains(&line_positions(grid, value, by_rows, *line).len()))
+                    .collect();
+
+                for base in combinations(&base_candidates, self.size) {
+                    let positions: Vec<Vec<usize>> = base.iter().map(|line| line_positions(grid, value, by_rows, *line)).collect();
+                    let mut union: Vec<usize> = positions.concat();
+                    union.sort();
+                    union.dedup();
+
+                    for cover in combinations(&union, self.size) {
+                        if positions.iter().any(|line| !line.iter().any(|index| cover.contains(index))) {
+                            continue;
+                        }
+
+                        let fins: Vec<usize> = base.iter().zip(positions.iter())
+                            .flat_map(|(line, indices)| indices.iter()
+                                .filter(|index| !cover.contains(index))
+                                .map(|index| line_cell(by_rows, *line, *index)))
+                            .collect();
+                        let fin_block = match fins.first() {
+                            Some(fin) => BLOCK_OF[*fin],
+                            None => continue,
+                        };
+                        if fins.iter().any(|fin| BLOCK_OF[*fin] != fin_block) {
+                            continue;
+                        }
+
+                        let eliminations: Vec<(usize, Value)> = cover.iter()
+                            .flat_map(|cover_line| (0..9)
+                                .filter(|index| !base.contains(index))
+                                .map(|index| line_cell(!by_rows, *cover_line, index)))
+                            .filter(|cell_id| BLOCK_OF[*cell_id] == fin_block)
+                            .filter(|cell_id| grid.candidates(*cell_id).contains(value))
+                            .map(|cell_id| (cell_id, value))
+                            .collect();
+                        if eliminations.is_empty() {
+                            continue;
+                        }
+
+                        let sashimi = positions.iter().any(|line| line.iter().filter(|index| cover.contains(index)).count() < 2);
+                        let cells = base.iter().zip(positions.iter())
+                            .flat_map(|(line, indices)| indices.iter().map(|index| line_cell(by_rows, *line, *index)))
+                            .collect();
+                        let base_names = base.iter().map(|line| line_name(by_rows, *line)).collect::<Vec<String>>().join(", ");
+                        let cover_names = cover.iter().map(|line| line_name(!by_rows, *line)).collect::<Vec<String>>().join(", ");
+                        return Some(Deduction {
+                            technique: self.technique(),
+                            explanation: format!("{} {} on {} in {} over {} with fins {}",
+                                if sashimi { "sashimi" } else { "finned" }, fish_name, value, base_names, cover_names, cell_names(&fins)),
+                            cells,
+                            placements: vec![],
+                            eliminations,
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+}
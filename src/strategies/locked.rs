@@ -0,0 +1,77 @@
+use std::ops::Range;
+
+use crate::board::Value;
+use crate::propagation::CandidateBoard;
+use crate::strategies::{cell_names, house_name, Deduction, Strategy, Technique};
+use crate::topology::{HOUSES, HOUSES_OF};
+
+/// Look for a value whose candidates in the house `confining` all lie in
+/// a single house of the kind `kind` (0 = row, 1 = column, 2 = block) and
+/// eliminate it from the rest of that house.
+fn find_locked(grid: &CandidateBoard, technique: Technique, confining: Range<usize>, kinds: &[usize]) -> Option<Deduction> {
+    for house_id in confining {
+        let cells = HOUSES[house_id];
+        for value in 1..=9 {
+            let positions: Vec<usize> = cells.iter().copied()
+                .filter(|cell_id| grid.candidates(*cell_id).contains(value))
+                .collect();
+            if positions.len() < 2 {
+                continue;
+            }
+
+            for kind in kinds {
+                let target = HOUSES_OF[positions[0]][*kind];
+                if positions.iter().any(|cell_id| HOUSES_OF[*cell_id][*kind] != target) {
+                    continue;
+                }
+
+                let eliminations: Vec<(usize, Value)> = HOUSES[target].into_iter()
+                    .filter(|cell_id| !cells.contains(cell_id) && grid.candidates(*cell_id).contains(value))
+                    .map(|cell_id| (cell_id, value))
+                    .collect();
+                if eliminations.is_empty() {
+                    continue;
+                }
+
+                return Some(Deduction {
+                    technique,
+                    explanation: format!("{} in {} is confined to {} within {}", value, house_name(house_id), cell_names(&positions), house_name(target)),
+                    cells: positions,
+                    placements: vec![],
+                    eliminations,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// The candidates of a value in a block all lie in one row or column.
+/// The value can be eliminated from the rest of that row or column.
+#[derive(Clone,Copy,Debug,Default)]
+pub struct PointingCandidates;
+
+impl Strategy for PointingCandidates {
+    fn technique(&self) -> Technique {
+        Technique::PointingCandidates
+    }
+
+    fn find(&self, grid: &CandidateBoard) -> Option<Deduction> {
+        find_locked(grid, self.technique(), 18..27, &[0, 1])
+    }
+}
+
+/// The candidates of a value in a row or column all lie in one block.
+/// The value can be eliminated from the rest of that block.
+#[derive(Clone,Copy,Debug,Default)]
+pub struct BoxLineReduction;
+
+impl Strategy for BoxLineReduction {
+    fn technique(&self) -> Technique {
+        Technique::BoxLineReduction
+    }
+
+    fn find(&self, grid: &CandidateBoard) -> Option<Deduction> {
+        find_locked(grid, self.technique(), 0..18, &[2])
+    }
+}
@@ -0,0 +1,382 @@
+//! Human-style solving techniques.
+//!
+//! Every technique is a `Strategy` which inspects a `CandidateBoard` and
+//! reports what it can deduce as a `Deduction`: the placements and candidate
+//! eliminations it justifies, the cells forming the pattern and an
+//! explanation a human can follow. Strategies never guess.
+
+mod als;
+mod coloring;
+mod fish;
+mod locked;
+mod singles;
+mod subsets;
+mod uniqueness;
+mod wings;
+
+pub use als::AlsXz;
+pub use coloring::SimpleColoring;
+pub use fish::{FinnedFish, Fish};
+pub use locked::{BoxLineReduction, PointingCandidates};
+pub use singles::{HiddenSingle, NakedSingle};
+pub use subsets::{HiddenSubset, NakedSubset};
+pub use uniqueness::{BugPlusOne, UniqueRectangleType1, UniqueRectangleType2};
+pub use wings::{WWing, XyWing, XyzWing};
+
+use std::fmt;
+
+use crate::board::Value;
+use crate::error::Error;
+use crate::propagation::CandidateBoard;
+use crate::topology::{BLOCK_OF, COLUMN_OF, ROW_OF};
+
+/// The solving techniques known to the strategy engine
+#[derive(Clone,Copy,Debug,PartialEq,Eq,PartialOrd,Ord,Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Technique {
+    NakedSingle,
+    HiddenSingle,
+    PointingCandidates,
+    BoxLineReduction,
+    NakedPair,
+    HiddenPair,
+    NakedTriple,
+    HiddenTriple,
+    NakedQuad,
+    HiddenQuad,
+    XWing,
+    Swordfish,
+    Jellyfish,
+    XyWing,
+    XyzWing,
+    WWing,
+    SimpleColoring,
+    UniqueRectangleType1,
+    UniqueRectangleType2,
+    BugPlusOne,
+    FinnedFish,
+    AlsXz,
+}
+
+impl Technique {
+    /// All techniques ordered from the simplest to the most advanced
+    pub const ALL: [Technique; 22] = [
+        Technique::NakedSingle,
+        Technique::HiddenSingle,
+        Technique::PointingCandidates,
+        Technique::BoxLineReduction,
+        Technique::NakedPair,
+        Technique::HiddenPair,
+        Technique::NakedTriple,
+        Technique::HiddenTriple,
+        Technique::NakedQuad,
+        Technique::HiddenQuad,
+        Technique::XWing,
+        Technique::Swordfish,
+        Technique::Jellyfish,
+        Technique::XyWing,
+        Technique::XyzWing,
+        Technique::WWing,
+        Technique::SimpleColoring,
+        Technique::UniqueRectangleType1,
+        Technique::UniqueRectangleType2,
+        Technique::BugPlusOne,
+        Technique::FinnedFish,
+        Technique::AlsXz,
+    ];
+
+    /// Human-readable name of the technique
+    pub fn name(&self) -> &'static str {
+        match self {
+            Technique::NakedSingle => "Naked Single",
+            Technique::HiddenSingle => "Hidden Single",
+            Technique::PointingCandidates => "Pointing Candidates",
+            Technique::BoxLineReduction => "Box/Line Reduction",
+            Technique::NakedPair => "Naked Pair",
+            Technique::HiddenPair => "Hidden Pair",
+            Technique::NakedTriple => "Naked Triple",
+            Technique::HiddenTriple => "Hidden Triple",
+            Technique::NakedQuad => "Naked Quad",
+            Technique::HiddenQuad => "Hidden Quad",
+            Technique::XWing => "X-Wing",
+            Technique::Swordfish => "Swordfish",
+            Technique::Jellyfish => "Jellyfish",
+            Technique::XyWing => "XY-Wing",
+            Technique::XyzWing => "XYZ-Wing",
+            Technique::WWing => "W-Wing",
+            Technique::SimpleColoring => "Simple Coloring",
+            Technique::UniqueRectangleType1 => "Unique Rectangle Type 1",
+            Technique::UniqueRectangleType2 => "Unique Rectangle Type 2",
+            Technique::BugPlusOne => "BUG+1",
+            Technique::FinnedFish => "Finned Fish",
+            Technique::AlsXz => "ALS-XZ",
+        }
+    }
+}
+
+impl fmt::Display for Technique {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A set of techniques, e.g. the curriculum a logic solver may use
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Hash)]
+pub struct TechniqueSet(u32);
+
+impl TechniqueSet {
+    /// The set without any technique
+    pub fn empty() -> Self {
+        Self(0)
+    }
+
+    /// The set of all known techniques
+    pub fn all() -> Self {
+        Technique::ALL.into_iter().collect()
+    }
+
+    /// Naked and hidden singles only
+    pub fn singles() -> Self {
+        Self::empty().with(Technique::NakedSingle).with(Technique::HiddenSingle)
+    }
+
+    /// This set extended by `technique`
+    pub fn with(mut self, technique: Technique) -> Self {
+        self.insert(technique);
+        self
+    }
+
+    pub fn insert(&mut self, technique: Technique) {
+        self.0 |= 1 << technique as u32;
+    }
+
+    pub fn remove(&mut self, technique: Technique) {
+        self.0 &= !(1 << technique as u32);
+    }
+
+    pub fn contains(&self, technique: Technique) -> bool {
+        self.0 & (1 << technique as u32) != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The techniques of this set ordered from the simplest to the most advanced
+    pub fn iter(&self) -> impl Iterator<Item = Technique> + '_ {
+        Technique::ALL.into_iter().filter(|technique| self.contains(*technique))
+    }
+}
+
+impl FromIterator<Technique> for TechniqueSet {
+    fn from_iter<I: IntoIterator<Item = Technique>>(iter: I) -> Self {
+        let mut set = Self::empty();
+        for technique in iter {
+            set.insert(technique);
+        }
+        set
+    }
+}
+
+/// The outcome of applying a technique once
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct Deduction {
+    pub technique: Technique,
+    /// The cells forming the pattern which justifies the deduction
+    pub cells: Vec<usize>,
+    /// Values which can be placed as `(cell_id, value)`
+    pub placements: Vec<(usize, Value)>,
+    /// Candidates which can be removed as `(cell_id, value)`
+    pub eliminations: Vec<(usize, Value)>,
+    /// Why the deduction holds, in words
+    pub explanation: String,
+}
+
+impl Deduction {
+    /// Apply all placements and eliminations to the grid
+    pub fn apply(&self, grid: &mut CandidateBoard) -> Result<(), Error> {
+        for (cell_id, value) in self.placements.iter() {
+            grid.assign(*cell_id, *value)?;
+        }
+        for (cell_id, value) in self.eliminations.iter() {
+            grid.eliminate(*cell_id, *value)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Deduction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let placements = self.placements.iter().map(|(cell_id, value)| format!("{}={}", cell_name(*cell_id), value));
+        let eliminations = self.eliminations.iter().map(|(cell_id, value)| format!("{}<>{}", cell_name(*cell_id), value));
+        let effects = placements.chain(eliminations).collect::<Vec<String>>().join(", ");
+        write!(f, "{}: {} => {}", self.technique, self.explanation, effects)
+    }
+}
+
+/// A solving technique
+pub trait Strategy {
+    /// The technique implemented by this strategy
+    fn technique(&self) -> Technique;
+
+    /// Find one deduction on the grid or `None` if the technique does not apply.
+    /// A returned deduction always makes progress, i.e. it places a value
+    /// or eliminates at least one remaining candidate.
+    fn find(&self, grid: &CandidateBoard) -> Option<Deduction>;
+
+    /// Is the technique only sound if the puzzle has a unique solution?
+    fn requires_uniqueness(&self) -> bool {
+        false
+    }
+}
+
+/// All strategies ordered from the simplest to the most advanced.
+/// This includes the techniques which require a unique solution.
+pub fn all() -> Vec<Box<dyn Strategy>> {
+    vec![
+        Box::new(NakedSingle),
+        Box::new(HiddenSingle),
+        Box::new(PointingCandidates),
+        Box::new(BoxLineReduction),
+        Box::new(NakedSubset { size: 2 }),
+        Box::new(HiddenSubset { size: 2 }),
+        Box::new(NakedSubset { size: 3 }),
+        Box::new(HiddenSubset { size: 3 }),
+        Box::new(NakedSubset { size: 4 }),
+        Box::new(HiddenSubset { size: 4 }),
+        Box::new(Fish { size: 2 }),
+        Box::new(Fish { size: 3 }),
+        Box::new(Fish { size: 4 }),
+        Box::new(XyWing),
+        Box::new(XyzWing),
+        Box::new(WWing),
+        Box::new(SimpleColoring),
+        Box::new(UniqueRectangleType1),
+        Box::new(UniqueRectangleType2),
+        Box::new(BugPlusOne),
+        Box::new(FinnedFish { size: 2 }),
+        Box::new(FinnedFish { size: 3 }),
+        Box::new(FinnedFish { size: 4 }),
+        Box::new(AlsXz),
+    ]
+}
+
+/// Find the first deduction of the simplest applicable strategy
+pub fn next_deduction(grid: &CandidateBoard, strategies: &[Box<dyn Strategy>]) -> Option<Deduction> {
+    strategies.iter().find_map(|strategy| strategy.find(grid))
+}
+
+/// Standard notation of a cell, e.g. `r4c5` for row 4 and column 5
+pub(crate) fn cell_name(cell_id: usize) -> String {
+    format!("r{}c{}", cell_id / 9 + 1, cell_id % 9 + 1)
+}
+
+/// Do two different cells share a row, a column or a block?
+pub(crate) fn sees(cell_a: usize, cell_b: usize) -> bool {
+    cell_a != cell_b
+        && (ROW_OF[cell_a] == ROW_OF[cell_b] || COLUMN_OF[cell_a] == COLUMN_OF[cell_b] || BLOCK_OF[cell_a] == BLOCK_OF[cell_b])
+}
+
+/// Comma-separated standard notation of several cells
+pub(crate) fn cell_names(cell_ids: &[usize]) -> String {
+    cell_ids.iter().map(|cell_id| cell_name(*cell_id)).collect::<Vec<String>>().join(", ")
+}
+
+/// Comma-separated list of values
+pub(crate) fn value_names(values: &[Value]) -> String {
+    values.iter().map(|value| value.to_string()).collect::<Vec<String>>().join(", ")
+}
+
+/// All subsets of `items` with exactly `size` elements, in lexicographic order
+pub(crate) fn combinations<T: Copy>(items: &[T], size: usize) -> Vec<Vec<T>> {
+    if size == 0 {
+        return vec![vec![]];
+    }
+
+    let mut subsets = vec![];
+    for (i, item) in items.iter().enumerate() {
+        for mut rest in combinations(&items[i + 1..], size - 1) {
+            rest.insert(0, *item);
+            subsets.push(rest);
+        }
+    }
+    subsets
+}
+
+/// Description of a house as used by `topology::HOUSES`, e.g. `row 3` or `block top-left`
+pub(crate) fn house_name(house_id: usize) -> String {
+    match house_id {
+        0..=8 => format!("row {}", house_id + 1),
+        9..=17 => format!("column {}", house_id - 9 + 1),
+        _ => {
+            let vertical_pos = ["top", "middle", "bottom"];
+            let horizontal_pos = ["left", "center", "right"];
+            let block_id = house_id - 18;
+            format!("block {}-{}", vertical_pos[block_id / 3], horizontal_pos[block_id % 3])
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku::Sudoku;
+
+    /// Apply the simplest deduction until none is found, checking on the way that every
+    /// strategy only places values of the solution and never eliminates one of them.
+    /// Returns the techniques which found a deduction.
+    fn check_soundness(cells: &str) -> Vec<Technique> {
+        let values: Vec<Value> = cells.chars().map(|symbol| symbol.to_digit(10).unwrap_or(0) as Value).collect();
+        let mut sudoku = Sudoku::default();
+        sudoku.init_board_values(&values.try_into().unwrap());
+        let board = sudoku.board().clone();
+        let solution = sudoku.solve().unwrap();
+
+        let strategies = all();
+        let mut used = Vec::new();
+        let mut grid = CandidateBoard::new(&board);
+        while !grid.is_solved() {
+            let deductions: Vec<Deduction> = strategies.iter().filter_map(|strategy| strategy.find(&grid)).collect();
+            for deduction in deductions.iter() {
+                for (cell_id, value) in deduction.placements.iter() {
+                    assert_eq!(solution[*cell_id].value(), *value, "{deduction} on {cells}");
+                }
+                for (cell_id, value) in deduction.eliminations.iter() {
+                    assert_ne!(solution[*cell_id].value(), *value, "{deduction} on {cells}");
+                }
+                if !used.contains(&deduction.technique) {
+                    used.push(deduction.technique);
+                }
+            }
+            match deductions.first() {
+                Some(deduction) => deduction.apply(&mut grid).unwrap(),
+                None => break,
+            }
+        }
+        used
+    }
+
+    #[test]
+    fn strategies_agree_with_the_solution() {
+        let used = check_soundness("..75......6..2..4....3.65.22......8...8.3.1...9......79.32.7....7..9..5......89..");
+        assert!(used.contains(&Technique::NakedSingle));
+    }
+
+    #[cfg(feature = "examples")]
+    #[test]
+    fn strategies_agree_with_the_solutions_of_the_corpus() {
+        use crate::corpus;
+        use crate::difficulty::Difficulty;
+
+        let mut used = Vec::new();
+        for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard, Difficulty::Expert] {
+            for board in corpus::by_difficulty(difficulty).boards() {
+                used.extend(check_soundness(&board.to_line_string('.')));
+            }
+        }
+        // NOTE: the corpus has no BUG+1, which the tests of `uniqueness` cover instead
+        for technique in Technique::ALL.into_iter().filter(|technique| *technique != Technique::BugPlusOne) {
+            assert!(used.contains(&technique), "{technique}");
+        }
+    }
+}
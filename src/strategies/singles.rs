@@ -0,0 +1,55 @@
+use crate::board::Board;
+use crate::propagation::CandidateBoard;
+use crate::strategies::{cell_name, house_name, Deduction, Strategy, Technique};
+use crate::topology::HOUSES;
+
+/// An unassigned cell with exactly one candidate left takes that value
+#[derive(Clone,Copy,Debug,Default)]
+pub struct NakedSingle;
+
+impl Strategy for NakedSingle {
+    fn technique(&self) -> Technique {
+        Technique::NakedSingle
+    }
+
+    fn find(&self, grid: &CandidateBoard) -> Option<Deduction> {
+        (0..Board::COUNT_VALUES).find_map(|cell_id| grid.candidates(cell_id).single_value().map(|value| Deduction {
+            technique: self.technique(),
+            cells: vec![cell_id],
+            placements: vec![(cell_id, value)],
+            eliminations: vec![],
+            explanation: format!("{} has no other candidate", cell_name(cell_id)),
+        }))
+    }
+}
+
+/// A value with only one possible cell in a house goes into that cell
+#[derive(Clone,Copy,Debug,Default)]
+pub struct HiddenSingle;
+
+impl Strategy for HiddenSingle {
+    fn technique(&self) -> Technique {
+        Technique::HiddenSingle
+    }
+
+    fn find(&self, grid: &CandidateBoard) -> Option<Deduction> {
+        for (house_id, cells) in HOUSES.into_iter().enumerate() {
+            for value in 1..=9 {
+                let mut positions = cells.iter().filter(|cell_id| grid.candidates(**cell_id).contains(value));
+                if let (Some(cell_id), None) = (positions.next(), positions.next()) {
+                    if cells.iter().any(|other| grid.board()[*other].value() == value) {
+                        continue;
+                    }
+                    return Some(Deduction {
+                        technique: self.technique(),
+                        cells: vec![*cell_id],
+                        placements: vec![(*cell_id, value)],
+                        eliminations: vec![],
+                        explanation: format!("{} is the only place for {} in {}", cell_name(*cell_id), value, house_name(house_id)),
+                    });
+                }
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,116 @@
+use crate::board::Value;
+use crate::candidate_set::CandidateSet;
+use crate::propagation::CandidateBoard;
+use crate::strategies::{cell_names, combinations, house_name, value_names, Deduction, Strategy, Technique};
+use crate::topology::HOUSES;
+
+/// `size` cells of a house whose candidates are limited to the same `size` values.
+/// Those values can be eliminated from all other cells of the house.
+#[derive(Clone,Copy,Debug)]
+pub struct NakedSubset {
+    /// Number of cells and values forming the subset (2 to 4)
+    pub size: usize,
+}
+
+impl Strategy for NakedSubset {
+    fn technique(&self) -> Technique {
+        match self.size {
+            2 => Technique::NakedPair,
+            3 => Technique::NakedTriple,
+            _ => Technique::NakedQuad,
+        }
+    }
+
+    fn find(&self, grid: &CandidateBoard) -> Option<Deduction> {
+        for (house_id, cells) in HOUSES.into_iter().enumerate() {
+            let open_cells: Vec<usize> = cells.into_iter()
+                .filter(|cell_id| (2..=self.size).contains(&grid.candidates(*cell_id).len()))
+                .collect();
+
+            for subset in combinations(&open_cells, self.size) {
+                let values: Vec<Value> = subset.iter()
+                    .fold(CandidateSet::empty(), |values, cell_id| values | grid.candidates(*cell_id))
+                    .iter()
+                    .collect();
+                if values.len() != self.size {
+                    continue;
+                }
+
+                let eliminations: Vec<(usize, Value)> = cells.into_iter()
+                    .filter(|cell_id| !subset.contains(cell_id))
+                    .flat_map(|cell_id| grid.candidates(cell_id).iter()
+                        .filter(|value| values.contains(value))
+                        .map(move |value| (cell_id, value)))
+                    .collect();
+                if eliminations.is_empty() {
+                    continue;
+                }
+
+                return Some(Deduction {
+                    technique: self.technique(),
+                    explanation: format!("{} in {} only contain {}", cell_names(&subset), house_name(house_id), value_names(&values)),
+                    cells: subset,
+                    placements: vec![],
+                    eliminations,
+                });
+            }
+        }
+        None
+    }
+}
+
+/// `size` values of a house which are confined to the same `size` cells.
+/// All other candidates can be eliminated from those cells.
+#[derive(Clone,Copy,Debug)]
+pub struct HiddenSubset {
+    /// Number of cells and values forming the subset (2 to 4)
+    pub size: usize,
+}
+
+impl Strategy for HiddenSubset {
+    fn technique(&self) -> Technique {
+        match self.size {
+            2 => Technique::HiddenPair,
+            3 => Technique::HiddenTriple,
+            _ => Technique::HiddenQuad,
+        }
+    }
+
+    fn find(&self, grid: &CandidateBoard) -> Option<Deduction> {
+        for (house_id, cells) in HOUSES.into_iter().enumerate() {
+            let positions = |value: Value| -> Vec<usize> {
+                cells.iter().copied().filter(|cell_id| grid.candidates(*cell_id).contains(value)).collect()
+            };
+            let open_values: Vec<Value> = (1..=9)
+                .filter(|value| (2..=self.size).contains(&positions(*value).len()))
+                .collect();
+
+            for values in combinations(&open_values, self.size) {
+                let mut subset: Vec<usize> = values.iter().flat_map(|value| positions(*value)).collect();
+                subset.sort();
+                subset.dedup();
+                if subset.len() != self.size {
+                    continue;
+                }
+
+                let eliminations: Vec<(usize, Value)> = subset.iter()
+                    .flat_map(|cell_id| grid.candidates(*cell_id).iter()
+                        .filter(|value| !values.contains(value))
+                        .map(|value| (*cell_id, value)))
+                    .collect();
+                if eliminations.is_empty() {
+                    continue;
+                }
+
+                return Some(Deduction {
+                    technique: self.technique(),
+                    explanation: format!("{} in {} only fit into {}", value_names(&values), house_name(house_id), cell_names(&subset)),
+                    cells: subset,
+                    placements: vec![],
+                    eliminations,
+                });
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,217 @@
+use crate::board::{Board, Value};
+use crate::propagation::CandidateBoard;
+use crate::strategies::{cell_name, cell_names, sees, Deduction, Strategy, Technique};
+use crate::topology::{COUNT_HOUSES, HOUSES, HOUSES_OF};
+
+/// All rectangles of unassigned cells spanning two rows, two columns and
+/// exactly two blocks, together with the value pairs all four cells share.
+/// The cells are ordered top-left, top-right, bottom-left, bottom-right.
+fn rectangles(grid: &CandidateBoard) -> Vec<([usize; 4], [Value; 2])> {
+    let mut found = vec![];
+    for row_a in 0..9 {
+        for row_b in row_a + 1..9 {
+            for column_a in 0..9 {
+                for column_b in column_a + 1..9 {
+                    if (row_a / 3 == row_b / 3) == (column_a / 3 == column_b / 3) {
+                        continue;
+                    }
+
+                    let cells = [row_a * 9 + column_a, row_a * 9 + column_b, row_b * 9 + column_a, row_b * 9 + column_b];
+                    let shared: Vec<Value> = (1..=9)
+                        .filter(|value| cells.iter().all(|cell_id| grid.candidates(*cell_id).contains(*value)))
+                        .collect();
+                    for (i, a) in shared.iter().enumerate() {
+                        for b in shared[i + 1..].iter() {
+                            found.push((cells, [*a, *b]));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    found
+}
+
+/// Unique Rectangle type 1: three corners of a rectangle contain only the
+/// candidates `{a, b}`. If the fourth corner held neither `a` nor `b`, the
+/// two values could be swapped and the puzzle had two solutions. So `a` and
+/// `b` are eliminated from the fourth corner.
+/// The deduction's cells are the four corners.
+#[derive(Clone,Copy,Debug,Default)]
+pub struct UniqueRectangleType1;
+
+impl Strategy for UniqueRectangleType1 {
+    fn technique(&self) -> Technique {
+        Technique::UniqueRectangleType1
+    }
+
+    fn requires_uniqueness(&self) -> bool {
+        true
+    }
+
+    fn find(&self, grid: &CandidateBoard) -> Option<Deduction> {
+        for (cells, pair) in rectangles(grid) {
+            let extra: Vec<usize> = cells.iter().copied().filter(|cell_id| grid.candidates(*cell_id).len() > 2).collect();
+            if let [corner] = extra[..] {
+                return Some(Deduction {
+                    technique: self.technique(),
+                    explanation: format!("{} would form a deadly pattern on {}/{} unless {} holds another value",
+                        cell_names(&cells), pair[0], pair[1], cell_name(corner)),
+                    cells: cells.to_vec(),
+                    placements: vec![],
+                    eliminations: vec![(corner, pair[0]), (corner, pair[1])],
+                });
+            }
+        }
+        None
+    }
+}
+
+/// Unique Rectangle type 2: two corners sharing a row or column contain only
+/// `{a, b}` and the other two corners contain exactly `{a, b, c}`. One of the
+/// latter must hold `c` to avoid the deadly pattern, so `c` is eliminated from
+/// every cell seeing both of them.
+/// The deduction's cells are the four corners.
+#[derive(Clone,Copy,Debug,Default)]
+pub struct UniqueRectangleType2;
+
+impl Strategy for UniqueRectangleType2 {
+    fn technique(&self) -> Technique {
+        Technique::UniqueRectangleType2
+    }
+
+    fn requires_uniqueness(&self) -> bool {
+        true
+    }
+
+    fn find(&self, grid: &CandidateBoard) -> Option<Deduction> {
+        for (cells, pair) in rectangles(grid) {
+            // NOTE: the floor and roof corners must share a row or column
+            for (floor, roof) in [([0, 1], [2, 3]), ([2, 3], [0, 1]), ([0, 2], [1, 3]), ([1, 3], [0, 2])] {
+                let floor = floor.map(|i| cells[i]);
+                let roof = roof.map(|i| cells[i]);
+                if floor.iter().any(|cell_id| grid.candidates(*cell_id).len() != 2) {
+                    continue;
+                }
+                if roof.iter().any(|cell_id| grid.candidates(*cell_id).len() != 3)
+                    || grid.candidates(roof[0]) != grid.candidates(roof[1]) {
+                    continue;
+                }
+
+                let extra = match grid.candidates(roof[0]).iter().find(|value| !pair.contains(value)) {
+                    Some(extra) => extra,
+                    None => continue,
+                };
+                let eliminations: Vec<(usize, Value)> = (0..Board::COUNT_VALUES)
+                    .filter(|cell_id| !cells.contains(cell_id) && grid.candidates(*cell_id).contains(extra))
+                    .filter(|cell_id| roof.iter().all(|corner| sees(*cell_id, *corner)))
+                    .map(|cell_id| (cell_id, extra))
+                    .collect();
+                if eliminations.is_empty() {
+                    continue;
+                }
+
+                return Some(Deduction {
+                    technique: self.technique(),
+                    explanation: format!("{} would form a deadly pattern on {}/{} unless {} or {} holds {}",
+                        cell_names(&cells), pair[0], pair[1], cell_name(roof[0]), cell_name(roof[1]), extra),
+                    cells: cells.to_vec(),
+                    placements: vec![],
+                    eliminations,
+                });
+            }
+        }
+        None
+    }
+}
+
+/// Bivalue Universal Grave + 1: every unassigned cell has two candidates except
+/// for one cell with three, and one of its values occurs three times in each of
+/// its row, column and block. Without that value in that cell, each candidate
+/// would occur exactly twice per house and the puzzle had two solutions.
+/// So the value is placed there.
+/// The deduction's cell is the trivalue cell.
+#[derive(Clone,Copy,Debug,Default)]
+pub struct BugPlusOne;
+
+impl Strategy for BugPlusOne {
+    fn technique(&self) -> Technique {
+        Technique::BugPlusOne
+    }
+
+    fn requires_uniqueness(&self) -> bool {
+        true
+    }
+
+    fn find(&self, grid: &CandidateBoard) -> Option<Deduction> {
+        let unassigned = grid.board().unassigned();
+        if unassigned.iter().any(|cell_id| !(2..=3).contains(&grid.candidates(*cell_id).len())) {
+            return None;
+        }
+        let trivalue: Vec<usize> = unassigned.into_iter().filter(|cell_id| grid.candidates(*cell_id).len() == 3).collect();
+        let cell_id = match trivalue[..] {
+            [cell_id] => cell_id,
+            _ => return None,
+        };
+
+        let occurrences = |house_id: usize, value: Value| HOUSES[house_id].iter().filter(|other| grid.candidates(**other).contains(value)).count();
+        let houses = HOUSES_OF[cell_id];
+        let value = grid.candidates(cell_id).iter()
+            .find(|value| houses.iter().all(|house_id| occurrences(*house_id, *value) == 3))?;
+        // NOTE: without the value in the trivalue cell, the grid must be the BUG itself
+        for house_id in 0..COUNT_HOUSES {
+            for other in 1..=9 {
+                let extra = usize::from(other == value && houses.contains(&house_id));
+                if !matches!(occurrences(house_id, other) - extra, 0 | 2) {
+                    return None;
+                }
+            }
+        }
+
+        Some(Deduction {
+            technique: self.technique(),
+            explanation: format!("all other unassigned cells are bivalue and {} occurs three times in the row, column and block of {}",
+                value, cell_name(cell_id)),
+            cells: vec![cell_id],
+            placements: vec![(cell_id, value)],
+            eliminations: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A grid of bivalue cells holding their values of two solutions which differ in every
+    /// cell, the second relabelling the first, except for the cells with the given candidates
+    fn bivalue_grid(changes: &[(usize, &[Value])]) -> CandidateBoard {
+        let mut grid = CandidateBoard::new(&Board::default());
+        for cell_id in 0..Board::COUNT_VALUES {
+            let (row, col) = (cell_id / 9, cell_id % 9);
+            let value = ((row * 3 + row / 3 + col) % 9 + 1) as Value;
+            let kept = match changes.iter().find(|(changed, _)| *changed == cell_id) {
+                Some((_, values)) => values.to_vec(),
+                None => vec![value, value % 9 + 1],
+            };
+            for other in (1..=9).filter(|other| !kept.contains(other)) {
+                grid.eliminate(cell_id, other).unwrap();
+            }
+        }
+        grid
+    }
+
+    #[test]
+    fn bug_plus_one_places_the_extra_value() {
+        let grid = bivalue_grid(&[(0, &[1, 2, 5])]);
+        let deduction = BugPlusOne.find(&grid).unwrap();
+        assert_eq!(deduction.placements, vec![(0, 5)]);
+    }
+
+    #[test]
+    fn bug_plus_one_requires_every_other_value_twice_per_house() {
+        // NOTE: 5 still occurs three times in the row of r1c1, but r9c9 breaks the BUG
+        let grid = bivalue_grid(&[(0, &[1, 2, 5]), (80, &[3, 8])]);
+        assert!(BugPlusOne.find(&grid).is_none());
+    }
+}
@@ -0,0 +1,186 @@
+use crate::board::{Board, Value};
+use crate::candidate_set::CandidateSet;
+use crate::propagation::CandidateBoard;
+use crate::strategies::{cell_name, sees, Deduction, Strategy, Technique};
+use crate::topology::HOUSES;
+
+/// Eliminate `value` from every cell that sees all of `cells` (of which there is at least one)
+fn eliminations_seeing_all(grid: &CandidateBoard, cells: &[usize], value: Value) -> Vec<(usize, Value)> {
+    // NOTE: a cell seeing all of them is in particular a peer of the first one
+    Board::peers(cells[0]).iter().copied()
+        .filter(|cell_id| !cells.contains(cell_id) && grid.candidates(*cell_id).contains(value))
+        .filter(|cell_id| cells.iter().all(|other| sees(*cell_id, *other)))
+        .map(|cell_id| (cell_id, value))
+        .collect()
+}
+
+/// Cells having exactly `count` candidates
+fn cells_with_candidate_count(grid: &CandidateBoard, count: usize) -> Vec<usize> {
+    (0..Board::COUNT_VALUES).filter(|cell_id| grid.candidates(*cell_id).len() == count).collect()
+}
+
+/// The single value `pincer` has besides the value `shared` with the pivot
+fn other_value(candidates: CandidateSet, shared: Value) -> Option<Value> {
+    if candidates.len() != 2 || !candidates.contains(shared) {
+        return None;
+    }
+    (candidates - CandidateSet::single(shared)).first()
+}
+
+/// A bivalue pivot `{x, y}` sees two bivalue pincers `{x, z}` and `{y, z}`.
+/// Whatever the pivot holds, one pincer holds `z`, so `z` can be eliminated
+/// from every cell seeing both pincers.
+/// The deduction's cells are the pivot followed by both pincers.
+#[derive(Clone,Copy,Debug,Default)]
+pub struct XyWing;
+
+impl Strategy for XyWing {
+    fn technique(&self) -> Technique {
+        Technique::XyWing
+    }
+
+    fn find(&self, grid: &CandidateBoard) -> Option<Deduction> {
+        let bivalue = cells_with_candidate_count(grid, 2);
+        for pivot in bivalue.iter().copied() {
+            let mut values = grid.candidates(pivot).iter();
+            let (x, y) = match (values.next(), values.next()) {
+                (Some(x), Some(y)) => (x, y),
+                _ => continue,
+            };
+            for pincer_x in bivalue.iter().copied().filter(|cell_id| sees(pivot, *cell_id)) {
+                let z = match other_value(grid.candidates(pincer_x), x) {
+                    Some(z) if z != y => z,
+                    _ => continue,
+                };
+                for pincer_y in bivalue.iter().copied().filter(|cell_id| sees(pivot, *cell_id) && *cell_id != pincer_x) {
+                    if other_value(grid.candidates(pincer_y), y) != Some(z) {
+                        continue;
+                    }
+
+                    let eliminations = eliminations_seeing_all(grid, &[pincer_x, pincer_y], z);
+                    if eliminations.is_empty() {
+                        continue;
+                    }
+                    return Some(Deduction {
+                        technique: self.technique(),
+                        cells: vec![pivot, pincer_x, pincer_y],
+                        placements: vec![],
+                        eliminations,
+                        explanation: format!("pivot {} ({}/{}) with pincers {} and {} forces {} into one pincer",
+                            cell_name(pivot), x, y, cell_name(pincer_x), cell_name(pincer_y), z),
+                    });
+                }
+            }
+        }
+        None
+    }
+}
+
+/// A trivalue pivot `{x, y, z}` sees two bivalue pincers `{x, z}` and `{y, z}`.
+/// One of the three cells holds `z`, so `z` can be eliminated from every cell
+/// seeing the pivot and both pincers.
+/// The deduction's cells are the pivot followed by both pincers.
+#[derive(Clone,Copy,Debug,Default)]
+pub struct XyzWing;
+
+impl Strategy for XyzWing {
+    fn technique(&self) -> Technique {
+        Technique::XyzWing
+    }
+
+    fn find(&self, grid: &CandidateBoard) -> Option<Deduction> {
+        let bivalue = cells_with_candidate_count(grid, 2);
+        for pivot in cells_with_candidate_count(grid, 3) {
+            let pivot_values = grid.candidates(pivot);
+            let pincers: Vec<usize> = bivalue.iter().copied()
+                .filter(|cell_id| sees(pivot, *cell_id) && grid.candidates(*cell_id).is_subset(pivot_values))
+                .collect();
+
+            for (i, pincer_a) in pincers.iter().copied().enumerate() {
+                for pincer_b in pincers[i + 1..].iter().copied() {
+                    let (a, b) = (grid.candidates(pincer_a), grid.candidates(pincer_b));
+                    if a == b {
+                        continue;
+                    }
+                    let z = match (a & b).first() {
+                        Some(z) => z,
+                        None => continue,
+                    };
+
+                    let eliminations = eliminations_seeing_all(grid, &[pivot, pincer_a, pincer_b], z);
+                    if eliminations.is_empty() {
+                        continue;
+                    }
+                    return Some(Deduction {
+                        technique: self.technique(),
+                        cells: vec![pivot, pincer_a, pincer_b],
+                        placements: vec![],
+                        eliminations,
+                        explanation: format!("pivot {} with pincers {} and {} forces {} into one of them",
+                            cell_name(pivot), cell_name(pincer_a), cell_name(pincer_b), z),
+                    });
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Two bivalue cells `{x, y}` which don't see each other are connected by a
+/// strong link on `x`: a house where `x` fits into two cells only, one seeing
+/// each bivalue cell. Then one of the bivalue cells holds `y`, so `y` can be
+/// eliminated from every cell seeing both of them.
+/// The deduction's cells are both bivalue cells followed by the strong link.
+#[derive(Clone,Copy,Debug,Default)]
+pub struct WWing;
+
+impl Strategy for WWing {
+    fn technique(&self) -> Technique {
+        Technique::WWing
+    }
+
+    fn find(&self, grid: &CandidateBoard) -> Option<Deduction> {
+        let bivalue = cells_with_candidate_count(grid, 2);
+        for (i, cell_a) in bivalue.iter().copied().enumerate() {
+            for cell_b in bivalue[i + 1..].iter().copied() {
+                if grid.candidates(cell_a) != grid.candidates(cell_b) || sees(cell_a, cell_b) {
+                    continue;
+                }
+
+                let values: Vec<Value> = grid.candidates(cell_a).iter().collect();
+                for (x, y) in [(values[0], values[1]), (values[1], values[0])] {
+                    for cells in HOUSES {
+                        let link: Vec<usize> = cells.into_iter()
+                            .filter(|cell_id| grid.candidates(*cell_id).contains(x))
+                            .collect();
+                        let (end_a, end_b) = match link[..] {
+                            [end_a, end_b] => (end_a, end_b),
+                            _ => continue,
+                        };
+                        if [end_a, end_b].iter().any(|end| *end == cell_a || *end == cell_b) {
+                            continue;
+                        }
+                        let linked = (sees(end_a, cell_a) && sees(end_b, cell_b)) || (sees(end_a, cell_b) && sees(end_b, cell_a));
+                        if !linked {
+                            continue;
+                        }
+
+                        let eliminations = eliminations_seeing_all(grid, &[cell_a, cell_b], y);
+                        if eliminations.is_empty() {
+                            continue;
+                        }
+                        return Some(Deduction {
+                            technique: self.technique(),
+                            cells: vec![cell_a, cell_b, end_a, end_b],
+                            placements: vec![],
+                            eliminations,
+                            explanation: format!("{} and {} ({}/{}) are connected by the strong link {}-{} on {}",
+                                cell_name(cell_a), cell_name(cell_b), x, y, cell_name(end_a), cell_name(end_b), x),
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+}
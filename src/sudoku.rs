@@ -0,0 +1,725 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::board::{Board, Value};
+use crate::candidate_set::CandidateSet;
+use crate::coord::Coord;
+use crate::error::Error;
+use crate::hint::Hint;
+use crate::moves::Move;
+use crate::propagation::CandidateBoard;
+use crate::constraint::{self, Constraint};
+use crate::rng::Rng;
+use crate::search::Search;
+use crate::solver::backtracking::{self, SearchHeuristic};
+use crate::solver::progress::ProgressTracker;
+use crate::solver::{LogicSolver, SearchProgress, SolveConfig, SolveOutcome, SolveStats, SolveTrace, TraceEvent};
+use crate::strategies;
+use crate::variant::{Arrow, Regions, Thermometer, Variant};
+
+/// A move applied to the game together with the value it replaced
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct AppliedMove {
+    placement: Move,
+    previous: Value,
+}
+
+/// The game instance of Sudoku. So it contains a board as current state
+/// and can be extended by further game-related members.
+#[derive(Clone,Debug,Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(into = "SudokuData", from = "SudokuData"))]
+pub struct Sudoku {
+    board: Board,
+    /// Moves applied to the board, the latest one last
+    history: Vec<AppliedMove>,
+    /// Undone moves which can be redone, the latest undone one last
+    undone: Vec<Move>,
+    /// The rules of the game
+    variant: Variant,
+    /// Rules beyond those of the variant, e.g. defined by other crates
+    constraints: Vec<Arc<dyn Constraint>>,
+}
+
+impl Sudoku {
+    pub fn init_board(&mut self, board: &Board) {
+        self.start(board.clone());
+    }
+
+    pub fn init_board_values(&mut self, values: &[Value; Board::COUNT_VALUES]) {
+        self.start(Board::from_flattened_values(values));
+    }
+
+    pub fn init_board_rows(&mut self, values: &[[Value; Board::COUNT_COLUMNS]; Board::COUNT_ROWS]) {
+        self.start(Board::from_values_per_row(values));
+    }
+
+    /// Start a new game on `board` without any history
+    fn start(&mut self, board: Board) {
+        self.board = board;
+        self.history.clear();
+        self.undone.clear();
+    }
+
+    /// Reference to the Board instance active in this game
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// The rules of this game
+    pub fn variant(&self) -> &Variant {
+        &self.variant
+    }
+
+    /// This game played by the rules of `variant`
+    pub fn with_variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// This game as a jigsaw Sudoku whose `regions` replace the 3×3 blocks
+    pub fn with_regions(self, regions: Regions) -> Self {
+        let variant = self.variant.clone().with_regions(regions);
+        self.with_variant(variant)
+    }
+
+    /// This game as a Sudoku X where both main diagonals must hold the values 1 to 9
+    pub fn with_diagonals(self) -> Self {
+        let variant = self.variant.clone().with_diagonals();
+        self.with_variant(variant)
+    }
+
+    /// This game as a Hypersudoku where four extra 3×3 windows must hold the values 1 to 9
+    pub fn with_windows(self) -> Self {
+        let variant = self.variant.clone().with_windows();
+        self.with_variant(variant)
+    }
+
+    /// This game as a Latin square: only the rows and the columns must hold the
+    /// values 1 to 9, a stepping stone towards the full rules
+    pub fn with_latin_square(self) -> Self {
+        let variant = self.variant.clone().with_latin_square();
+        self.with_variant(variant)
+    }
+
+    /// This game with the anti-knight rule: cells a knight's move apart hold different values
+    pub fn with_anti_knight(self) -> Self {
+        let variant = self.variant.clone().with_anti_knight();
+        self.with_variant(variant)
+    }
+
+    /// This game with the anti-king rule: cells a king's move apart hold different values
+    pub fn with_anti_king(self) -> Self {
+        let variant = self.variant.clone().with_anti_king();
+        self.with_variant(variant)
+    }
+
+    /// This game with the non-consecutive rule: orthogonally adjacent cells hold values differing by more than one
+    pub fn with_non_consecutive(self) -> Self {
+        let variant = self.variant.clone().with_non_consecutive();
+        self.with_variant(variant)
+    }
+
+    /// This game with the values along the thermometer strictly increasing from its bulb
+    pub fn with_thermometer(self, thermometer: Thermometer) -> Self {
+        let variant = self.variant.clone().with_thermometer(thermometer);
+        self.with_variant(variant)
+    }
+
+    /// This game with the values along the shaft of the arrow summing up to the value in its circle
+    pub fn with_arrow(self, arrow: Arrow) -> Self {
+        let variant = self.variant.clone().with_arrow(arrow);
+        self.with_variant(variant)
+    }
+
+    /// This game with the rule in addition to those of the variant.
+    /// Games with such rules are solved by the generic search, which prunes
+    /// its candidates with the rule and checks it after every step.
+    pub fn with_constraint(mut self, constraint: impl Constraint + 'static) -> Self {
+        self.constraints.push(Arc::new(constraint));
+        self
+    }
+
+    /// All rules of the game: the constraints of the variant and then the added ones
+    pub fn constraints(&self) -> impl Iterator<Item = &Arc<dyn Constraint>> {
+        self.variant.constraints().iter().chain(self.constraints.iter())
+    }
+
+    /// Is this a classic game which the solvers tailored to the classic rules can play?
+    fn is_classic(&self) -> bool {
+        self.variant.is_classic() && self.constraints.is_empty()
+    }
+
+    /// The generic search on the rules of the game, starting from the board
+    fn search(&self) -> Search<'_> {
+        let mut search = Search::new(self.variant.topology(), &self.board.values());
+        let hooked: Vec<Arc<dyn Constraint>> = self.variant.hooked_constraints().into_iter().chain(self.constraints.iter().cloned()).collect();
+        if !hooked.is_empty() {
+            search.prune = Some(Box::new(move |cells, candidates| constraint::prune_search(&hooked, &self.board, cells, candidates)));
+        }
+        search
+    }
+
+    /// Does our board satisfy all constraints of the game?
+    /// If yes, returns nothing. If no, returns the error of the first violated
+    /// constraint in the order of `constraints`: a ``Error::ConstraintError``
+    /// or, for equal values a forbidden chess move apart, a ``Error::ConflictError``
+    /// or, for consecutive values in adjacent cells, values out of order on
+    /// a thermometer or an arrow whose shaft cannot sum up to its circle, a ``Error::RuleError``.
+    pub fn verify_board(&self) -> Result<(), Error> {
+        self.constraints().try_for_each(|constraint| constraint.check(&self.board))
+    }
+
+    /// All violations of the constraints by our board in the order of `constraints`,
+    /// e.g. a ``Error::ConflictError`` for every value repeated in a row, column or block.
+    /// Empty if and only if `verify_board` succeeds.
+    pub fn violations(&self) -> Vec<Error> {
+        self.constraints().flat_map(|constraint| constraint.violations(&self.board)).collect()
+    }
+
+    /// The values which can be placed in the unassigned cell without violating
+    /// a constraint, as far as the constraints prune them. Empty for assigned cells.
+    pub fn legal_values(&self, cell_id: usize) -> CandidateSet {
+        if self.is_classic() {
+            return self.board.legal_values(cell_id);
+        }
+        let mut candidates = [CandidateSet::empty(); Board::COUNT_VALUES];
+        for unassigned_id in self.board.unassigned() {
+            candidates[unassigned_id] = CandidateSet::all();
+        }
+        for constraint in self.constraints() {
+            constraint.prune(&self.board, &mut candidates);
+        }
+        candidates[cell_id]
+    }
+
+    /// The board with lines along the borders of the regions and the thermometers and arrows of the variant
+    pub fn to_variant_string(&self) -> String {
+        self.variant.render(&self.board)
+    }
+
+    /// Apply the move to the board and record it in the history.
+    /// A move with value 0 clears the cell. The move must not overwrite a given
+    /// nor conflict with the values in the houses of its cell; otherwise the
+    /// error of `Board::try_set_consistent` is returned and nothing changes.
+    /// Applying a move discards the moves which could be redone.
+    pub fn apply_move(&mut self, placement: &Move) -> Result<(), Error> {
+        let cell_id = placement.cell_id();
+        // NOTE: the move is checked by `try_move`, so an invalid cell ID only needs not to panic
+        let previous = if cell_id < Board::COUNT_VALUES { self.board[cell_id].value() } else { 0 };
+        self.board = self.board_with_move(placement)?;
+        self.history.push(AppliedMove { placement: *placement, previous });
+        self.undone.clear();
+        Ok(())
+    }
+
+    /// Apply all moves in order like `apply_move`, e.g. to replay a recorded game.
+    /// If a move fails, its error is returned and the game, including its
+    /// history, stays as it was before the first move.
+    pub fn apply_moves(&mut self, placements: &[Move]) -> Result<(), Error> {
+        let mut game = self.clone();
+        for placement in placements {
+            game.apply_move(placement)?;
+        }
+        *self = game;
+        Ok(())
+    }
+
+    /// A copy of the board with the move applied after checking it against the rules of the variant
+    fn board_with_move(&self, placement: &Move) -> Result<Board, Error> {
+        let mut board = self.board.clone();
+        if self.is_classic() {
+            board.try_move(placement)?;
+            return Ok(board);
+        }
+
+        if placement.cell.row >= Board::COUNT_ROWS || placement.cell.col >= Board::COUNT_COLUMNS {
+            return Err(Error::IdError { admissible: 0..Board::COUNT_VALUES, actual: placement.cell_id() });
+        }
+        board.try_set(placement.cell_id(), placement.value)?;
+        if placement.value != 0 {
+            self.constraints().try_for_each(|constraint| constraint.check_cell(&board, placement.cell_id()))?;
+        }
+        Ok(board)
+    }
+
+    /// Take back the latest applied move and return it, or `None` if there is none
+    pub fn undo(&mut self) -> Option<Move> {
+        let applied = self.history.pop()?;
+        self.board.place(applied.placement.cell_id(), applied.previous);
+        self.undone.push(applied.placement);
+        Some(applied.placement)
+    }
+
+    /// Apply the latest undone move again and return it, or `None` if there is none
+    pub fn redo(&mut self) -> Option<Move> {
+        let placement = self.undone.pop()?;
+        let cell_id = placement.cell_id();
+        let previous = self.board[cell_id].value();
+        // NOTE: the board is in the state the move was applied to before, so it is still valid
+        self.board.place(cell_id, placement.value);
+        self.history.push(AppliedMove { placement, previous });
+        Some(placement)
+    }
+
+    /// The moves applied since the game was initialized, the earliest one first.
+    /// Undone moves are not part of the history.
+    pub fn history(&self) -> Vec<Move> {
+        self.history.iter().map(|applied| applied.placement).collect()
+    }
+
+    /// Can a move be taken back?
+    pub fn can_undo(&self) -> bool {
+        !self.history.is_empty()
+    }
+
+    /// Can an undone move be applied again?
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Is the game finished in this state?
+    pub fn finished(&self) -> bool {
+        self.board().unassigned().is_empty()
+    }
+
+    /// Determine the set of next possible moves, i.e. every legal value
+    /// of every unassigned cell, ordered by cell and value.
+    pub fn next_possible_moves(&self) -> Vec<Move> {
+        let b = self.board();
+        let cells_to_update = b.unassigned();
+
+        let mut moves = vec![];
+        for cell_id in cells_to_update {
+            for candidate_value in self.legal_values(cell_id) {
+                moves.push(Move::new(Coord::from_cell_id(cell_id), candidate_value));
+            }
+        }
+
+        moves
+    }
+
+    /// The legal values of every unassigned cell, keyed by cell ID.
+    /// Cells mapped to a single value are forced, cells mapped to no value make the game unsolvable.
+    pub fn candidate_map(&self) -> BTreeMap<usize, CandidateSet> {
+        self.board.unassigned().into_iter()
+            .map(|cell_id| (cell_id, self.legal_values(cell_id)))
+            .collect()
+    }
+
+    /// Place naked singles (cells with exactly one legal value) and hidden singles
+    /// (values with only one possible cell in a house) until none is left.
+    /// Returns the placements made in order, which are also recorded in the
+    /// history so every one of them can be undone. If this runs into a contradiction,
+    /// the board stays unchanged and ``Error::Unsolvable`` is returned.
+    /// A board which already violates a constraint yields its ``Error::ConstraintError``.
+    pub fn fill_forced(&mut self) -> Result<Vec<Move>, Error> {
+        self.verify_board()?;
+
+        let mut placements = vec![];
+        if self.is_classic() {
+            let mut grid = CandidateBoard::new(&self.board);
+            grid.propagate_observed(&mut |event| {
+                if let TraceEvent::Placement { row, col, value } = event {
+                    placements.push(Move::new(Coord::new(row, col), value));
+                }
+            })?;
+            self.board = grid.board().clone();
+        } else {
+            let board = {
+                let mut search = self.search();
+                if !search.propagate() {
+                    return Err(Error::Unsolvable);
+                }
+                placements = search.assigned().iter().map(|cell_id| Move::new(Coord::from_cell_id(*cell_id), search.cells()[*cell_id])).collect();
+                self.board.with_values(search.cells().try_into().unwrap())
+            };
+            self.board = board;
+        }
+
+        self.history.extend(placements.iter().map(|placement| AppliedMove { placement: *placement, previous: 0 }));
+        if !placements.is_empty() {
+            self.undone.clear();
+        }
+        Ok(placements)
+    }
+
+    /// Solve the game by depth-first backtracking, branching on the cell
+    /// with the fewest remaining candidates first.
+    /// Returns the solved `Board` or ``Error::Unsolvable`` if no assignment
+    /// of the unassigned cells satisfies all Sudoku constraints.
+    /// If the initial board already violates a constraint, the corresponding
+    /// ``Error::ConstraintError`` is returned instead.
+    pub fn solve(&self) -> Result<Board, Error> {
+        self.solve_with_heuristic(SearchHeuristic::default())
+    }
+
+    /// Like `solve`, but the search branches on the cells picked by `heuristic`
+    /// Games of other variants than the classic one always branch on the cell
+    /// with the fewest remaining candidates.
+    pub fn solve_with_heuristic(&self, heuristic: SearchHeuristic) -> Result<Board, Error> {
+        self.verify_board()?;
+        if !self.is_classic() {
+            return self.search_variant(&mut self.search()).ok_or(Error::Unsolvable);
+        }
+        backtracking::solve(&self.board, heuristic).ok_or(Error::Unsolvable)
+    }
+
+    /// The first solution the search on the houses of the variant comes across
+    fn search_variant(&self, search: &mut Search) -> Option<Board> {
+        let mut solution = None;
+        search.run(&mut |cells| {
+            solution = Some(self.board.with_values(cells.try_into().unwrap()));
+            false
+        });
+        solution
+    }
+
+    /// Like `solve`, but also return the `SolveTrace` of all guesses,
+    /// propagated placements, eliminations and backtracks of the search.
+    /// The search on other variants than the classic one is not traced,
+    /// so its trace is empty.
+    pub fn solve_traced(&self) -> Result<(Board, SolveTrace), Error> {
+        self.verify_board()?;
+        if !self.is_classic() {
+            return self.solve().map(|solved| (solved, SolveTrace::default()));
+        }
+        match backtracking::solve_traced(&self.board, SearchHeuristic::default()) {
+            (Some(solved), trace) => Ok((solved, trace)),
+            (None, _) => Err(Error::Unsolvable),
+        }
+    }
+
+    /// Solve the game by backtracking within the budget of `config`.
+    /// If the budget runs out, ``SolveOutcome::Aborted`` carries the
+    /// statistics of the search so far. A board which already violates
+    /// a constraint is ``SolveOutcome::Unsolvable``.
+    /// For other variants than the classic one, only the budget and the seed of
+    /// `config` apply. So solving an empty board with a seed generates a random
+    /// solution grid of the variant, e.g. a Latin square.
+    pub fn solve_with(&self, config: &SolveConfig) -> SolveOutcome {
+        if self.verify_board().is_err() {
+            return SolveOutcome::Unsolvable;
+        }
+        if self.is_classic() {
+            return backtracking::solve_with(&self.board, config);
+        }
+
+        let started = Instant::now();
+        let mut search = self.search_within(config, started);
+        self.outcome_of(&mut search, started)
+    }
+
+    /// Solve the game like `solve_with` and return the statistics of the search as well,
+    /// e.g. to compare heuristics
+    pub fn solve_with_stats(&self, config: &SolveConfig) -> (SolveOutcome, SolveStats) {
+        self.solve_observed(config, &mut |_| {})
+    }
+
+    /// Solve the game like `solve_with_stats`, passing the `SearchProgress` to `observe`
+    /// every now and then, e.g. to show that a long search is still going on
+    pub fn solve_observed(&self, config: &SolveConfig, observe: &mut dyn FnMut(&SearchProgress)) -> (SolveOutcome, SolveStats) {
+        if self.verify_board().is_err() {
+            return (SolveOutcome::Unsolvable, SolveStats::default());
+        }
+        if self.is_classic() {
+            return backtracking::solve_observed(&self.board, config, observe);
+        }
+
+        let started = Instant::now();
+        let mut search = self.search_within(config, started);
+        search.progress = Some(ProgressTracker::new(Box::new(observe)));
+        let outcome = self.outcome_of(&mut search, started);
+        (outcome, stats_of(&search, started))
+    }
+
+    /// The search of a variant with the budget and the seed of `config`, starting at `started`
+    fn search_within(&self, config: &SolveConfig, started: Instant) -> Search<'_> {
+        let mut search = self.search();
+        search.node_limit = config.node_limit;
+        search.deadline = config.timeout.map(|timeout| started + timeout);
+        search.rng = config.seed.map(Rng::new);
+        search
+    }
+
+    /// Run the search of a variant until the first solution
+    fn outcome_of(&self, search: &mut Search, started: Instant) -> SolveOutcome {
+        match self.search_variant(search) {
+            Some(solved) => SolveOutcome::Solved(solved),
+            None if search.aborted => SolveOutcome::Aborted(stats_of(search, started)),
+            None => SolveOutcome::Unsolvable,
+        }
+    }
+
+    /// Count the solutions of the game, but stop exploring the search tree
+    /// as soon as `limit` solutions are found. So the result is at most `limit`.
+    /// A board which already violates a constraint has no solutions.
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        if self.verify_board().is_err() || limit == 0 {
+            return 0;
+        }
+        if !self.is_classic() {
+            let mut count = 0;
+            self.search().run(&mut |_| {
+                count += 1;
+                count < limit
+            });
+            return count;
+        }
+        backtracking::count_solutions(&self.board, SearchHeuristic::default(), limit)
+    }
+
+    /// The solutions of the game, but at most `limit` of them, so the search stops
+    /// early even for boards with countless solutions like the empty one.
+    /// A board which already violates a constraint has no solutions.
+    pub fn solutions(&self, limit: usize) -> Vec<Board> {
+        if self.verify_board().is_err() || limit == 0 {
+            return vec![];
+        }
+        if !self.is_classic() {
+            let mut solutions = vec![];
+            self.search().run(&mut |cells| {
+                solutions.push(self.board.with_values(cells.try_into().unwrap()));
+                solutions.len() < limit
+            });
+            return solutions;
+        }
+        backtracking::solutions(&self.board, SearchHeuristic::default(), limit)
+    }
+
+    /// Count the solutions like `count_solutions`, but within the budget of `config`.
+    /// Returns `None` if the budget runs out before the count is decided.
+    pub fn count_solutions_with(&self, limit: usize, config: &SolveConfig) -> Option<usize> {
+        if self.verify_board().is_err() || limit == 0 {
+            return Some(0);
+        }
+        if !self.is_classic() {
+            return count_variant(&mut self.search_within(config, Instant::now()), limit);
+        }
+        backtracking::count_solutions_with(&self.board, config, limit)
+    }
+
+    /// Count the solutions like `count_solutions_with`, passing the `SearchProgress`
+    /// to `observe` every now and then
+    pub fn count_solutions_observed(&self, limit: usize, config: &SolveConfig, observe: &mut dyn FnMut(&SearchProgress)) -> Option<usize> {
+        if self.verify_board().is_err() || limit == 0 {
+            return Some(0);
+        }
+        if !self.is_classic() {
+            let mut search = self.search_within(config, Instant::now());
+            search.progress = Some(ProgressTracker::new(Box::new(observe)));
+            return count_variant(&mut search, limit);
+        }
+        backtracking::count_solutions_observed(&self.board, config, limit, observe)
+    }
+
+    /// Does the game have exactly one solution, i.e. is it a proper Sudoku?
+    /// This counts solutions up to 2 only, so the search short-circuits
+    /// as soon as a second solution is found.
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == 1
+    }
+
+    /// Suggest the next logical step using the simplest applicable technique.
+    /// Techniques which assume a unique solution are not used.
+    /// Returns `None` if the game is finished, violates a constraint
+    /// or no technique applies. The techniques only know the classic rules,
+    /// so there are no hints for other variants.
+    pub fn next_hint(&self) -> Option<Hint> {
+        if !self.is_classic() || self.verify_board().is_err() {
+            return None;
+        }
+        let grid = CandidateBoard::new(&self.board);
+        strategies::next_deduction(&grid, &LogicSolver::default().strategies()).map(Hint::from)
+    }
+}
+
+/// The statistics of the search of a variant which started at `started`
+fn stats_of(search: &Search, started: Instant) -> SolveStats {
+    SolveStats {
+        nodes: search.nodes,
+        backtracks: search.backtracks,
+        propagations: search.propagations,
+        max_depth: search.max_depth,
+        elapsed: started.elapsed(),
+    }
+}
+
+/// Count the solutions the search of a variant finds, stopping at `limit`.
+/// `None` if the budget of the search runs out first.
+fn count_variant(search: &mut Search, limit: usize) -> Option<usize> {
+    let mut count = 0;
+    search.run(&mut |_| {
+        count += 1;
+        count < limit
+    });
+    if search.aborted { None } else { Some(count) }
+}
+
+/// The game as serialized: the board, the history and the variant.
+/// Constraints added with `with_constraint` are not serialized.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SudokuData {
+    board: Board,
+    #[serde(default)]
+    history: Vec<AppliedMove>,
+    #[serde(default)]
+    undone: Vec<Move>,
+    #[serde(default)]
+    variant: Variant,
+}
+
+#[cfg(feature = "serde")]
+impl From<Sudoku> for SudokuData {
+    fn from(sudoku: Sudoku) -> Self {
+        Self { board: sudoku.board, history: sudoku.history, undone: sudoku.undone, variant: sudoku.variant }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<SudokuData> for Sudoku {
+    fn from(data: SudokuData) -> Self {
+        Self { board: data.board, history: data.history, undone: data.undone, variant: data.variant, constraints: vec![] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{BacktrackingSolver, DlxSolver, IterativeSolver, LogicSolver, ParallelSolver, SatSolver, SolveOutcome, Solver};
+
+    const PUZZLE: &str = "...26.7.168..7..9.19...45..82.1...4...46.29...5...3.28..93...74.4..5..367.3.18...";
+    const SOLUTION: &str = "435269781682571493197834562826195347374682915951743628519326874248957136763418259";
+
+    /// The values of the cells in the one-line format with `.` for unassigned cells
+    fn values_of(cells: &str) -> [Value; Board::COUNT_VALUES] {
+        let values: Vec<Value> = cells.chars().map(|symbol| symbol.to_digit(10).unwrap_or(0) as Value).collect();
+        values.try_into().unwrap()
+    }
+
+    fn sudoku_of(cells: &str) -> Sudoku {
+        let mut sudoku = Sudoku::default();
+        sudoku.init_board_values(&values_of(cells));
+        sudoku
+    }
+
+    /// The values of the board as one line of digits
+    fn line_of(board: &Board) -> String {
+        (0..Board::COUNT_VALUES).map(|cell_id| board[cell_id].value().to_string()).collect()
+    }
+
+    /// The engines of `solver` which always decide a puzzle, with their names
+    fn engines() -> Vec<(&'static str, Box<dyn Solver>)> {
+        vec![
+            ("backtracking", Box::new(BacktrackingSolver::default())),
+            ("backjumping", Box::new(BacktrackingSolver { backjumping: true, ..BacktrackingSolver::default() })),
+            ("dlx", Box::new(DlxSolver)),
+            ("sat", Box::new(SatSolver)),
+            ("iterative", Box::new(IterativeSolver::default())),
+            ("parallel", Box::new(ParallelSolver::default())),
+        ]
+    }
+
+    #[test]
+    fn solve_finds_the_solution() {
+        let solution = sudoku_of(PUZZLE).solve().unwrap();
+        assert_eq!(line_of(&solution), SOLUTION);
+    }
+
+    #[test]
+    fn solve_fails_without_a_solution() {
+        // NOTE: r1c9 can only hold 9, which r2c9 already holds
+        let sudoku = sudoku_of("12345678........9................................................................");
+        assert!(matches!(sudoku.solve(), Err(Error::Unsolvable)));
+    }
+
+    #[test]
+    fn solve_fails_for_a_board_violating_the_rules() {
+        let sudoku = sudoku_of("11...............................................................................");
+        assert!(matches!(sudoku.solve(), Err(Error::ConstraintError { .. })));
+    }
+
+    #[test]
+    fn count_solutions_stops_at_the_limit() {
+        assert_eq!(sudoku_of(PUZZLE).count_solutions(1000), 1);
+        assert_eq!(Sudoku::default().count_solutions(5), 5);
+        assert_eq!(Sudoku::default().count_solutions(0), 0);
+    }
+
+    #[test]
+    fn count_solutions_is_zero_for_a_board_violating_the_rules() {
+        assert_eq!(sudoku_of("11...............................................................................").count_solutions(2), 0);
+        assert_eq!(sudoku_of("12345678........9................................................................").count_solutions(2), 0);
+    }
+
+    #[test]
+    fn has_unique_solution() {
+        assert!(sudoku_of(PUZZLE).has_unique_solution());
+        // NOTE: without the givens of its first row the puzzle has several solutions
+        assert!(!sudoku_of(&format!("{}{}", ".".repeat(9), &PUZZLE[9..])).has_unique_solution());
+        assert!(!Sudoku::default().has_unique_solution());
+    }
+
+    #[test]
+    fn engines_agree_with_solve() {
+        let board = sudoku_of(PUZZLE).board().clone();
+        for (name, engine) in engines() {
+            let solution = engine.solve(&board).solution().map(line_of);
+            assert_eq!(solution.as_deref(), Some(SOLUTION), "{name}");
+        }
+    }
+
+    #[test]
+    fn logic_never_places_a_wrong_value() {
+        let board = sudoku_of(PUZZLE).board().clone();
+        match LogicSolver::default().solve(&board) {
+            SolveOutcome::Solved(found) | SolveOutcome::Stalled(found) => {
+                let wrong = (0..Board::COUNT_VALUES).find(|cell_id| found[*cell_id].value() != 0 && found[*cell_id].value().to_string() != SOLUTION[*cell_id..*cell_id + 1]);
+                assert_eq!(wrong, None);
+            },
+            outcome => panic!("{outcome:?}"),
+        }
+    }
+
+    #[test]
+    fn engines_report_a_board_violating_the_rules() {
+        let board = sudoku_of("11...............................................................................").board().clone();
+        for (name, engine) in engines() {
+            assert!(matches!(engine.solve(&board), SolveOutcome::Unsolvable), "{name}");
+        }
+        assert!(matches!(LogicSolver::default().solve(&board), SolveOutcome::Unsolvable));
+    }
+
+    #[cfg(feature = "examples")]
+    #[test]
+    fn engines_agree_on_the_corpus() {
+        use crate::corpus;
+        use crate::difficulty::Difficulty;
+        use crate::solver::dlx;
+
+        for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard, Difficulty::Expert] {
+            for board in corpus::by_difficulty(difficulty).boards() {
+                let mut sudoku = Sudoku::default();
+                sudoku.init_board(board);
+                let solution = sudoku.solve().unwrap();
+                let line = line_of(&solution);
+                assert_eq!(sudoku.count_solutions(2), 1, "{}", board.to_line_string('.'));
+                assert_eq!(dlx::count_solutions(board, 2), 1, "{}", board.to_line_string('.'));
+                for (name, engine) in engines() {
+                    let found = engine.solve(board).solution().map(line_of);
+                    assert_eq!(found.as_ref(), Some(&line), "{name} on {}", board.to_line_string('.'));
+                }
+                // NOTE: the logic solver may get stuck, but never on a wrong value
+                match LogicSolver::default().solve(board) {
+                    SolveOutcome::Solved(found) | SolveOutcome::Stalled(found) => {
+                        let wrong = (0..Board::COUNT_VALUES).find(|cell_id| found[*cell_id].value() != 0 && found[*cell_id].value() != solution[*cell_id].value());
+                        assert_eq!(wrong, None, "logic on {}", board.to_line_string('.'));
+                    },
+                    outcome => panic!("logic on {}: {outcome:?}", board.to_line_string('.')),
+                }
+            }
+        }
+    }
+}
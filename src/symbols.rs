@@ -0,0 +1,136 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::board::Value;
+use crate::error::Error;
+use crate::sized::MAX_SIZE;
+
+/// Symbols of `SymbolSet::alphanumeric`: digits followed by letters
+const ALPHANUMERIC: &str = "123456789ABCDEFGHIJKLMNOP";
+
+/// The characters representing the values of a board when printing and parsing it,
+/// e.g. the letters of a nine-letter word for a Wordoku. Boards store numbers
+/// internally, so a value `v` is written as the `v`-th symbol of the set.
+/// Classic boards need 9 symbols, boards of other sizes one per value.
+#[derive(Clone,Debug,PartialEq,Eq,Hash)]
+pub struct SymbolSet {
+    symbols: Vec<char>,
+}
+
+impl SymbolSet {
+    /// Character of unassigned cells
+    pub const BLANK: char = '.';
+
+    /// The symbols for the values from 1 in this order. Fails with ``Error::ParseError``
+    /// unless there are 1 to `MAX_SIZE` distinct symbols which are neither whitespace nor `BLANK`.
+    pub fn new(symbols: &str) -> Result<Self, Error> {
+        let error = |reason: String| Error::ParseError { input: symbols.to_string(), reason };
+
+        let symbols: Vec<char> = symbols.chars().collect();
+        if symbols.is_empty() || symbols.len() > MAX_SIZE {
+            return Err(error(format!("expected 1 to {MAX_SIZE} symbols but got {}", symbols.len())));
+        }
+        for (i, symbol) in symbols.iter().enumerate() {
+            if symbol.is_whitespace() || *symbol == Self::BLANK {
+                return Err(error(format!("symbol {symbol:?} cannot represent a value")));
+            }
+            if symbols[..i].contains(symbol) {
+                return Err(error(format!("symbol {symbol:?} occurs more than once")));
+            }
+        }
+        Ok(Self { symbols })
+    }
+
+    /// The digits 1 to 9
+    pub fn digits() -> Self {
+        Self { symbols: ('1'..='9').collect() }
+    }
+
+    /// The digits up to 9 followed by the letters from `A` (10) for the values 1 to `size`,
+    /// e.g. `1`–`9` and `A`–`G` for a 16×16 board. Panics if `size` exceeds `MAX_SIZE`.
+    pub fn alphanumeric(size: usize) -> Self {
+        assert!(size <= MAX_SIZE, "expected at most {MAX_SIZE} symbols");
+        Self { symbols: ALPHANUMERIC.chars().take(size).collect() }
+    }
+
+    /// The hexadecimal digits `0`–`F` for the values 1 to 16 of a 16×16 board.
+    /// Blanks are then only written as `BLANK`.
+    pub fn hexadecimal() -> Self {
+        Self { symbols: "0123456789ABCDEF".chars().collect() }
+    }
+
+    /// Number of symbols, i.e. the largest value the set represents
+    pub fn size(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// The symbols in the order of their values
+    pub fn symbols(&self) -> &[char] {
+        &self.symbols
+    }
+
+    /// The symbol of the value, `BLANK` for 0 (unassigned) and `None` for invalid values
+    pub fn symbol_of(&self, value: Value) -> Option<char> {
+        match value {
+            0 => Some(Self::BLANK),
+            _ => self.symbols.get(value as usize - 1).copied(),
+        }
+    }
+
+    /// The value of the symbol, 0 for blanks and `None` for other characters.
+    /// Blanks are `BLANK` and `0` unless it is a symbol of the set. Letters
+    /// match regardless of case if the set does not contain both cases.
+    pub fn value_of(&self, symbol: char) -> Option<Value> {
+        let position = self.symbols.iter().position(|other| *other == symbol)
+            .or_else(|| self.symbols.iter().position(|other| other.eq_ignore_ascii_case(&symbol)));
+        match position {
+            Some(i) => Some(i as Value + 1),
+            None if symbol == Self::BLANK || symbol == '0' => Some(0),
+            None => None,
+        }
+    }
+
+    /// Number of terminal columns taken by the widest symbol
+    pub fn width(&self) -> usize {
+        self.symbols.iter().map(|symbol| display_width(*symbol)).max().unwrap_or(1)
+    }
+
+    /// The symbol of the value for a cell of a box-drawing grid: padded to `width`
+    /// with a space on either side, and `I` (as in "invalid") for invalid values
+    pub(crate) fn to_padded_string(&self, value: Value) -> String {
+        let symbol = self.symbol_of(value).unwrap_or('I');
+        format!(" {symbol}{} ", " ".repeat(self.width() - display_width(symbol)))
+    }
+}
+
+/// Number of terminal columns taken by the character: 2 for the wide characters of East
+/// Asian scripts and most emoji, 1 otherwise.
+// ASSUME: an approximation of Unicode's East Asian Width covering the common wide blocks
+fn display_width(symbol: char) -> usize {
+    match symbol as u32 {
+        0x1100..=0x115F | 0x2E80..=0xA4CF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60 | 0xFFE0..=0xFFE6 | 0x1F300..=0x1F64F | 0x1F680..=0x1F6FF | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+impl Default for SymbolSet {
+    fn default() -> Self {
+        Self::digits()
+    }
+}
+
+impl FromStr for SymbolSet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Self::new(s)
+    }
+}
+
+impl fmt::Display for SymbolSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbols.iter().collect::<String>())
+    }
+}
@@ -0,0 +1,103 @@
+//! Constant lookup tables describing the topology of the board.
+//!
+//! All tables are computed at compile time. Houses are identified from 0 to 26:
+//! 0..9 are the rows, 9..18 the columns and 18..27 the blocks.
+
+/// Number of houses (rows, columns and blocks)
+pub(crate) const COUNT_HOUSES: usize = 27;
+
+/// Number of cells sharing a house with a cell (the cell itself excluded)
+pub(crate) const COUNT_PEERS: usize = 20;
+
+const fn row_of(cell_id: usize) -> usize {
+    cell_id / 9
+}
+
+const fn column_of(cell_id: usize) -> usize {
+    cell_id % 9
+}
+
+const fn block_of(cell_id: usize) -> usize {
+    (row_of(cell_id) / 3) * 3 + column_of(cell_id) / 3
+}
+
+const fn shares_house(cell_a: usize, cell_b: usize) -> bool {
+    row_of(cell_a) == row_of(cell_b) || column_of(cell_a) == column_of(cell_b) || block_of(cell_a) == block_of(cell_b)
+}
+
+/// Row identifier of every cell
+pub(crate) const ROW_OF: [usize; 81] = {
+    let mut table = [0; 81];
+    let mut cell_id = 0;
+    while cell_id < 81 {
+        table[cell_id] = row_of(cell_id);
+        cell_id += 1;
+    }
+    table
+};
+
+/// Column identifier of every cell
+pub(crate) const COLUMN_OF: [usize; 81] = {
+    let mut table = [0; 81];
+    let mut cell_id = 0;
+    while cell_id < 81 {
+        table[cell_id] = column_of(cell_id);
+        cell_id += 1;
+    }
+    table
+};
+
+/// Block identifier of every cell (0 is at the top-left, 8 at the bottom-right)
+pub(crate) const BLOCK_OF: [usize; 81] = {
+    let mut table = [0; 81];
+    let mut cell_id = 0;
+    while cell_id < 81 {
+        table[cell_id] = block_of(cell_id);
+        cell_id += 1;
+    }
+    table
+};
+
+/// House identifiers of the row, the column and the block of every cell
+pub(crate) const HOUSES_OF: [[usize; 3]; 81] = {
+    let mut table = [[0; 3]; 81];
+    let mut cell_id = 0;
+    while cell_id < 81 {
+        table[cell_id] = [row_of(cell_id), 9 + column_of(cell_id), 18 + block_of(cell_id)];
+        cell_id += 1;
+    }
+    table
+};
+
+/// The 9 cell IDs of every house in reading order
+pub(crate) const HOUSES: [[usize; 9]; COUNT_HOUSES] = {
+    let mut table = [[0; 9]; COUNT_HOUSES];
+    let mut cell_id = 0;
+    while cell_id < 81 {
+        let (row_id, column_id, block_id) = (row_of(cell_id), column_of(cell_id), block_of(cell_id));
+        table[row_id][column_id] = cell_id;
+        table[9 + column_id][row_id] = cell_id;
+        table[18 + block_id][(row_id % 3) * 3 + column_id % 3] = cell_id;
+        cell_id += 1;
+    }
+    table
+};
+
+/// The cell IDs sharing a row, a column or a block with every cell in increasing order
+pub(crate) const PEERS: [[usize; COUNT_PEERS]; 81] = {
+    let mut table = [[0; COUNT_PEERS]; 81];
+    let mut cell_id = 0;
+    while cell_id < 81 {
+        let mut count = 0;
+        let mut peer_id = 0;
+        while peer_id < 81 {
+            if peer_id != cell_id && shares_house(cell_id, peer_id) {
+                table[cell_id][count] = peer_id;
+                count += 1;
+            }
+            peer_id += 1;
+        }
+        cell_id += 1;
+    }
+    table
+};
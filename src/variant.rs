@@ -0,0 +1,1041 @@
+//! Sudoku variants with other rules than the classic ones.
+//!
+//! A `Variant` describes the houses of a game: the rows, the columns and
+//! either the classic 3×3 blocks, the irregular regions of a jigsaw Sudoku
+//! or, for a Latin square, no regions at all,
+//! plus optionally the two main diagonals of Sudoku X and the four windows
+//! of Hypersudoku. Anti-knight and anti-king rules add no houses, but make
+//! cells a chess move apart peers which must hold different values.
+//! Likewise the non-consecutive rule forbids values differing by one in
+//! orthogonally adjacent cells, thermometers require strictly increasing
+//! values from their bulb to their tip, and the values along the shaft of
+//! an arrow sum up to the value in its circle.
+//! Games of a variant other than the classic one are verified and solved on
+//! the houses of the variant by the generic search in `search`, because the
+//! solvers in `solver` rely on the fixed topology of the classic board.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::board::{Board, Value};
+use crate::candidate_set::CandidateSet;
+use crate::constraint::{self, Constraint, House};
+use crate::coord::Coord;
+use crate::error::Error;
+use crate::search::Topology;
+use crate::strategies::{cell_name, house_name};
+use crate::topology::{BLOCK_OF, COLUMN_OF, ROW_OF};
+
+/// Assignment of every cell to one of 9 regions of 9 orthogonally connected cells.
+/// The classic regions are the 3×3 blocks; a jigsaw Sudoku has irregular ones.
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Hash)]
+pub struct Regions {
+    region_of: [usize; Board::COUNT_VALUES],
+}
+
+impl Regions {
+    /// The 3×3 blocks of the classic Sudoku
+    pub fn classic() -> Self {
+        Self { region_of: BLOCK_OF }
+    }
+
+    /// The regions given the region identifier from 0 to 8 of every cell.
+    /// Fails with ``Error::LayoutError`` unless every region has 9 cells
+    /// which are connected by orthogonal steps.
+    pub fn new(region_of: [usize; Board::COUNT_VALUES]) -> Result<Self, Error> {
+        let error = |reason: String| Err(Error::LayoutError { reason });
+
+        if let Some(cell_id) = (0..Board::COUNT_VALUES).find(|cell_id| region_of[*cell_id] >= Board::COUNT_BLOCKS) {
+            return error(format!("region {} of cell {} lies outside of 1..=9", region_of[cell_id] + 1, cell_name(cell_id)));
+        }
+        for region_id in 0..Board::COUNT_BLOCKS {
+            let cells: Vec<usize> = (0..Board::COUNT_VALUES).filter(|cell_id| region_of[*cell_id] == region_id).collect();
+            if cells.len() != 9 {
+                return error(format!("region {} has {} instead of 9 cells", region_id + 1, cells.len()));
+            }
+
+            // NOTE: flood fill the region from its first cell
+            let mut reached = vec![cells[0]];
+            let mut i = 0;
+            while i < reached.len() {
+                let cell_id = reached[i];
+                for neighbor_id in orthogonal_neighbors(cell_id) {
+                    if region_of[neighbor_id] == region_id && !reached.contains(&neighbor_id) {
+                        reached.push(neighbor_id);
+                    }
+                }
+                i += 1;
+            }
+            if reached.len() != cells.len() {
+                return error(format!("the cells of region {} are not connected", region_id + 1));
+            }
+        }
+
+        Ok(Self { region_of })
+    }
+
+    /// Identifier of the region containing the cell, from 0 to 8
+    pub fn region_of(&self, cell_id: usize) -> usize {
+        self.region_of[cell_id]
+    }
+
+    /// The IDs of the 9 cells of the region in increasing order
+    pub fn cells(&self, region_id: usize) -> [usize; 9] {
+        let mut cells = [0; 9];
+        for (i, cell_id) in (0..Board::COUNT_VALUES).filter(|cell_id| self.region_of[*cell_id] == region_id).enumerate() {
+            cells[i] = cell_id;
+        }
+        cells
+    }
+
+    /// Are these the 3×3 blocks of the classic Sudoku?
+    pub fn is_classic(&self) -> bool {
+        self.region_of == BLOCK_OF
+    }
+
+    /// Description of a region, e.g. `block top-left` for the classic or `region 4` for irregular ones
+    pub(crate) fn name(&self, region_id: usize) -> String {
+        if self.is_classic() { house_name(18 + region_id) } else { format!("region {}", region_id + 1) }
+    }
+}
+
+impl Default for Regions {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl FromStr for Regions {
+    type Err = Error;
+
+    /// Parse the region of every cell in reading order as a digit from `1` to `9`
+    /// or a letter from `a` to `i`. Whitespace is ignored, e.g. 9 lines of 9 symbols.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let error = |reason: &str| Error::ParseError { input: s.to_string(), reason: reason.to_string() };
+
+        let mut region_of = [0; Board::COUNT_VALUES];
+        let mut count = 0;
+        for symbol in s.chars().filter(|symbol| !symbol.is_whitespace()) {
+            let region_id = match symbol {
+                '1'..='9' => symbol as usize - '1' as usize,
+                'a'..='i' => symbol as usize - 'a' as usize,
+                'A'..='I' => symbol as usize - 'A' as usize,
+                _ => return Err(error("expected regions 1 to 9 or a to i")),
+            };
+            if count < Board::COUNT_VALUES {
+                region_of[count] = region_id;
+            }
+            count += 1;
+        }
+        if count != Board::COUNT_VALUES {
+            return Err(error("expected the region of all 81 cells"));
+        }
+
+        Self::new(region_of)
+    }
+}
+
+/// Offsets in rows and columns of the cells a knight's move away
+const KNIGHT_MOVES: [(isize, isize); 8] = [(-2, -1), (-2, 1), (-1, -2), (-1, 2), (1, -2), (1, 2), (2, -1), (2, 1)];
+
+/// Offsets in rows and columns of the cells a king's move away
+const KING_MOVES: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+/// The cells reached from the cell by the moves as far as they exist
+fn cells_apart(cell_id: usize, moves: &[(isize, isize)]) -> impl Iterator<Item = usize> + '_ {
+    let (row, col) = (ROW_OF[cell_id], COLUMN_OF[cell_id]);
+    moves.iter()
+        .map(move |(rows, cols)| (row.wrapping_add_signed(*rows), col.wrapping_add_signed(*cols)))
+        .filter(|(row, col)| *row < Board::COUNT_ROWS && *col < Board::COUNT_COLUMNS)
+        .map(|(row, col)| row * Board::COUNT_COLUMNS + col)
+}
+
+/// The cells left of, right of, above and below the cell as far as they exist
+fn orthogonal_neighbors(cell_id: usize) -> impl Iterator<Item = usize> {
+    let (row, col) = (ROW_OF[cell_id], COLUMN_OF[cell_id]);
+    [(row, col.wrapping_sub(1)), (row, col + 1), (row.wrapping_sub(1), col), (row + 1, col)].into_iter()
+        .filter(|(row, col)| *row < Board::COUNT_ROWS && *col < Board::COUNT_COLUMNS)
+        .map(|(row, col)| row * Board::COUNT_COLUMNS + col)
+}
+
+/// A line of 2 to 9 cells, each one a king's move from the previous one,
+/// whose values strictly increase from the bulb (the first cell) to the tip
+#[derive(Clone,Debug,PartialEq,Eq,Hash)]
+pub struct Thermometer {
+    cells: Vec<usize>,
+}
+
+impl Thermometer {
+    /// The thermometer along the IDs of the cells, starting at the bulb.
+    /// Fails with ``Error::LayoutError`` unless the 2 to 9 cells are distinct
+    /// and every cell is a king's move from the previous one.
+    pub fn new(cells: Vec<usize>) -> Result<Self, Error> {
+        if !(2..=9).contains(&cells.len()) {
+            return Err(Error::LayoutError { reason: format!("a thermometer has {} instead of 2 to 9 cells", cells.len()) });
+        }
+        check_line("a thermometer", &cells)?;
+        Ok(Self { cells })
+    }
+
+    /// The IDs of the cells from the bulb to the tip
+    pub fn cells(&self) -> &[usize] {
+        &self.cells
+    }
+
+    /// The ID of the cell holding the smallest value
+    pub fn bulb(&self) -> usize {
+        self.cells[0]
+    }
+
+    /// Parse the thermometers of a config section: an optional `[thermometers]`
+    /// header followed by one thermometer per line. Blank lines and lines
+    /// starting with `#` are ignored, e.g.
+    ///
+    /// ```text
+    /// [thermometers]
+    /// # bulb first
+    /// r1c1 r2c2 r3c3
+    /// r9c9, r9c8, r8c8
+    /// ```
+    pub fn parse_section(section: &str) -> Result<Vec<Self>, Error> {
+        parse_section(section, "[thermometers]")
+    }
+}
+
+impl FromStr for Thermometer {
+    type Err = Error;
+
+    /// Parse the cells from the bulb to the tip in the standard notation,
+    /// separated by whitespace, `,`, `-` or `>`, e.g. `r1c1 r2c2 r3c3`
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Self::new(parse_cells(s)?)
+    }
+}
+
+/// Parse the shapes of a config section, one per line after an optional header
+fn parse_section<T: FromStr<Err = Error>>(section: &str, header: &str) -> Result<Vec<T>, Error> {
+    let mut lines = section.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')).peekable();
+    lines.next_if(|line| line.eq_ignore_ascii_case(header));
+    lines.map(str::parse).collect()
+}
+
+/// Parse the IDs of cells in the standard notation separated by whitespace, `,`, `-` or `>`
+fn parse_cells(s: &str) -> Result<Vec<usize>, Error> {
+    s.split(|symbol: char| symbol.is_whitespace() || ",->".contains(symbol))
+        .filter(|cell| !cell.is_empty())
+        .map(|cell| cell.parse::<Coord>().map(|coord| coord.cell_id()))
+        .collect()
+}
+
+/// Fail with ``Error::LayoutError`` unless the cells of the line named `shape` are
+/// distinct cells of the board and every cell is a king's move from the previous one
+fn check_line(shape: &str, cells: &[usize]) -> Result<(), Error> {
+    let error = |reason: String| Err(Error::LayoutError { reason });
+
+    if let Some(cell_id) = cells.iter().find(|cell_id| **cell_id >= Board::COUNT_VALUES) {
+        return error(format!("cell {cell_id} of {shape} lies outside of the board"));
+    }
+    for (i, cell_id) in cells.iter().enumerate() {
+        if cells[..i].contains(cell_id) {
+            return error(format!("{shape} passes cell {} twice", cell_name(*cell_id)));
+        }
+    }
+    if let Some(step) = cells.windows(2).find(|step| !cells_apart(step[0], &KING_MOVES).any(|cell_id| cell_id == step[1])) {
+        return error(format!("cells {} and {} of {shape} are not adjacent", cell_name(step[0]), cell_name(step[1])));
+    }
+    Ok(())
+}
+
+/// The cells as a line in the standard notation, e.g. `r1c1 r2c2 r3c3`
+fn cells_string(cells: &[usize]) -> String {
+    cells.iter().map(|cell_id| cell_name(*cell_id)).collect::<Vec<String>>().join(" ")
+}
+
+impl fmt::Display for Thermometer {
+    /// The cells from the bulb to the tip, e.g. `r1c1 r2c2 r3c3`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", cells_string(&self.cells))
+    }
+}
+
+impl Constraint for Thermometer {
+    /// Check that the assigned values along the thermometer strictly increase.
+    /// Otherwise return a ``Error::RuleError`` naming the first two cells out of order.
+    fn check(&self, board: &Board) -> Result<(), Error> {
+        let assigned: Vec<usize> = self.cells.iter().copied().filter(|cell_id| (1..=9).contains(&board[*cell_id].value())).collect();
+        if let Some(step) = assigned.windows(2).find(|step| board[step[0]].value() >= board[step[1]].value()) {
+            let cells = step.iter().map(|cell_id| (Coord::from_cell_id(*cell_id), board[*cell_id].value())).collect();
+            let rule = format!("strictly increasing values along the thermometer {self}");
+            return Err(Error::RuleError { rule, cells });
+        }
+        Ok(())
+    }
+
+    /// Keep the values which leave room for increasing values before and after the
+    /// cell, i.e. at least one more per step from an assigned cell towards the tip
+    fn prune(&self, board: &Board, candidates: &mut [CandidateSet; Board::COUNT_VALUES]) {
+        let length = self.cells.len() as isize;
+        for (position, cell_id) in self.cells.iter().enumerate() {
+            let position = position as isize;
+            let fits = |value: Value| {
+                let value = value as isize;
+                value > position && value <= 9 - (length - 1 - position)
+                    && self.cells.iter().enumerate().all(|(other_position, other_id)| {
+                        let (other_value, steps) = (board[*other_id].value() as isize, position - other_position as isize);
+                        other_value == 0 || steps == 0 || (steps > 0 && value >= other_value + steps) || (steps < 0 && value <= other_value + steps)
+                    })
+            };
+            candidates[*cell_id] &= (1..=9).filter(|value| fits(*value)).collect::<CandidateSet>();
+        }
+    }
+
+    fn check_cell(&self, board: &Board, cell_id: usize) -> Result<(), Error> {
+        match self.cells.contains(&cell_id) {
+            true => self.check(board),
+            false => Ok(()),
+        }
+    }
+}
+
+/// A circle cell with a shaft of 1 to 8 cells leaving it, each one a king's move
+/// from the previous one. The values along the shaft sum up to the value in the
+/// circle. Values may repeat on the shaft unless other rules forbid it.
+#[derive(Clone,Debug,PartialEq,Eq,Hash)]
+pub struct Arrow {
+    cells: Vec<usize>,
+}
+
+impl Arrow {
+    /// The arrow along the IDs of the cells, starting at the circle.
+    /// Fails with ``Error::LayoutError`` unless the 2 to 9 cells are distinct
+    /// and every cell is a king's move from the previous one.
+    pub fn new(cells: Vec<usize>) -> Result<Self, Error> {
+        if !(2..=9).contains(&cells.len()) {
+            return Err(Error::LayoutError { reason: format!("an arrow has {} instead of 2 to 9 cells", cells.len()) });
+        }
+        check_line("an arrow", &cells)?;
+        Ok(Self { cells })
+    }
+
+    /// The IDs of the circle and then the cells of the shaft towards the tip
+    pub fn cells(&self) -> &[usize] {
+        &self.cells
+    }
+
+    /// The ID of the cell holding the sum
+    pub fn circle(&self) -> usize {
+        self.cells[0]
+    }
+
+    /// The IDs of the cells whose values are summed, from the circle to the tip
+    pub fn shaft(&self) -> &[usize] {
+        &self.cells[1..]
+    }
+
+    /// Parse the arrows of a config section: an optional `[arrows]` header
+    /// followed by one arrow per line, circle first. Blank lines and lines
+    /// starting with `#` are ignored (see `Thermometer::parse_section`).
+    pub fn parse_section(section: &str) -> Result<Vec<Self>, Error> {
+        parse_section(section, "[arrows]")
+    }
+}
+
+impl FromStr for Arrow {
+    type Err = Error;
+
+    /// Parse the circle and then the cells of the shaft in the standard notation,
+    /// separated by whitespace, `,`, `-` or `>`, e.g. `r1c1 -> r1c2 -> r1c3`
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Self::new(parse_cells(s)?)
+    }
+}
+
+impl fmt::Display for Arrow {
+    /// The circle and then the cells of the shaft, e.g. `r1c1 r1c2 r1c3`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", cells_string(&self.cells))
+    }
+}
+
+impl Constraint for Arrow {
+    /// Check that the assigned values along the shaft can still sum up to the value
+    /// in the circle, counting every unassigned cell of the shaft as at least 1.
+    /// Otherwise return a ``Error::RuleError`` with the assigned cells of the arrow.
+    fn check(&self, board: &Board) -> Result<(), Error> {
+        let value = |cell_id: &usize| board[*cell_id].value();
+        let assigned: Vec<usize> = self.shaft().iter().copied().filter(|cell_id| (1..=9).contains(&value(cell_id))).collect();
+        let least = assigned.iter().map(|cell_id| value(cell_id) as usize).sum::<usize>() + self.shaft().len() - assigned.len();
+        let circle = value(&self.circle()) as usize;
+        let violated = match circle {
+            1..=9 => least > circle || (assigned.len() == self.shaft().len() && least != circle),
+            _ => least > 9,
+        };
+        if violated {
+            let cells = [self.circle()].iter().chain(assigned.iter())
+                .filter(|cell_id| value(cell_id) != 0)
+                .map(|cell_id| (Coord::from_cell_id(*cell_id), value(cell_id)))
+                .collect();
+            let rule = format!("values along the arrow {self} summing up to the value in its circle");
+            return Err(Error::RuleError { rule, cells });
+        }
+        Ok(())
+    }
+
+    /// Keep the values of the circle between the smallest and the largest sum of the
+    /// candidates along the shaft, and the values of every cell of the shaft which
+    /// leave room for such a sum with the candidates of the other cells
+    fn prune(&self, board: &Board, candidates: &mut [CandidateSet; Board::COUNT_VALUES]) {
+        // NOTE: an assigned cell stands for its value, an unassigned one for its candidates
+        let bounds = |candidates: &[CandidateSet; Board::COUNT_VALUES], cell_id: usize| match board[cell_id].value() {
+            value @ 1..=9 => (value as usize, value as usize),
+            _ => {
+                let values: Vec<usize> = candidates[cell_id].iter().map(|value| value as usize).collect();
+                (values.first().copied().unwrap_or(10), values.last().copied().unwrap_or(0))
+            }
+        };
+        let within = |low: usize, high: usize| (1..=9).filter(|value| low <= *value as usize && *value as usize <= high).collect::<CandidateSet>();
+
+        let shaft: Vec<(usize, usize)> = self.shaft().iter().map(|cell_id| bounds(candidates, *cell_id)).collect();
+        let (least, most) = shaft.iter().fold((0, 0), |(least, most), (low, high)| (least + low, most + high));
+        if board[self.circle()].value() == 0 {
+            candidates[self.circle()] &= within(least, most);
+        }
+        let (circle_low, circle_high) = bounds(candidates, self.circle());
+        for (cell_id, (low, high)) in self.shaft().iter().zip(shaft) {
+            if board[*cell_id].value() == 0 {
+                // NOTE: the other cells of the shaft sum up to between `least - low` and `most - high`
+                candidates[*cell_id] &= within(circle_low.saturating_sub(most - high), circle_high.saturating_sub(least - low));
+            }
+        }
+    }
+
+    fn check_cell(&self, board: &Board, cell_id: usize) -> Result<(), Error> {
+        match self.cells.contains(&cell_id) {
+            true => self.check(board),
+            false => Ok(()),
+        }
+    }
+}
+
+/// Cells a knight's move apart hold different values
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub struct AntiKnight;
+
+/// Cells a king's move apart, i.e. also diagonally adjacent cells, hold different values
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub struct AntiKing;
+
+impl Constraint for AntiKnight {
+    fn check(&self, board: &Board) -> Result<(), Error> {
+        (0..Board::COUNT_VALUES).try_for_each(|cell_id| check_moves(board, cell_id, "cells a knight's move apart", &KNIGHT_MOVES))
+    }
+
+    fn prune(&self, board: &Board, candidates: &mut [CandidateSet; Board::COUNT_VALUES]) {
+        prune_moves(board, &KNIGHT_MOVES, candidates);
+    }
+
+    fn violations(&self, board: &Board) -> Vec<Error> {
+        move_violations(board, "cells a knight's move apart", &KNIGHT_MOVES)
+    }
+
+    fn check_cell(&self, board: &Board, cell_id: usize) -> Result<(), Error> {
+        check_moves(board, cell_id, "cells a knight's move apart", &KNIGHT_MOVES)
+    }
+}
+
+impl Constraint for AntiKing {
+    fn check(&self, board: &Board) -> Result<(), Error> {
+        (0..Board::COUNT_VALUES).try_for_each(|cell_id| check_moves(board, cell_id, "cells a king's move apart", &KING_MOVES))
+    }
+
+    fn prune(&self, board: &Board, candidates: &mut [CandidateSet; Board::COUNT_VALUES]) {
+        prune_moves(board, &KING_MOVES, candidates);
+    }
+
+    fn violations(&self, board: &Board) -> Vec<Error> {
+        move_violations(board, "cells a king's move apart", &KING_MOVES)
+    }
+
+    fn check_cell(&self, board: &Board, cell_id: usize) -> Result<(), Error> {
+        check_moves(board, cell_id, "cells a king's move apart", &KING_MOVES)
+    }
+}
+
+/// Fail with ``Error::ConflictError`` if a cell the moves away from the cell holds its value
+fn check_moves(board: &Board, cell_id: usize, region: &str, moves: &[(isize, isize)]) -> Result<(), Error> {
+    let value = board[cell_id].value();
+    if !(1..=9).contains(&value) {
+        return Ok(());
+    }
+    if let Some(other_id) = cells_apart(cell_id, moves).find(|other_id| board[*other_id].value() == value) {
+        let cells = vec![Coord::from_cell_id(cell_id.min(other_id)), Coord::from_cell_id(cell_id.max(other_id))];
+        return Err(Error::ConflictError { region: region.to_string(), value, cells });
+    }
+    Ok(())
+}
+
+/// A ``Error::ConflictError`` for every pair of cells the moves apart holding the same value
+fn move_violations(board: &Board, region: &str, moves: &[(isize, isize)]) -> Vec<Error> {
+    let mut errors = vec![];
+    for cell_id in 0..Board::COUNT_VALUES {
+        let value = board[cell_id].value();
+        if !(1..=9).contains(&value) {
+            continue;
+        }
+        // NOTE: every pair is reported once, from its first cell
+        for other_id in cells_apart(cell_id, moves).filter(|other_id| *other_id > cell_id && board[*other_id].value() == value) {
+            let cells = vec![Coord::from_cell_id(cell_id), Coord::from_cell_id(other_id)];
+            errors.push(Error::ConflictError { region: region.to_string(), value, cells });
+        }
+    }
+    errors
+}
+
+/// Remove the value of every assigned cell from the candidates of the cells the moves away
+fn prune_moves(board: &Board, moves: &[(isize, isize)], candidates: &mut [CandidateSet; Board::COUNT_VALUES]) {
+    for cell_id in 0..Board::COUNT_VALUES {
+        let value = board[cell_id].value();
+        for other_id in cells_apart(cell_id, moves) {
+            candidates[other_id].remove(value);
+        }
+    }
+}
+
+/// Orthogonally adjacent cells hold values which differ by more than one
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub struct NonConsecutive;
+
+impl Constraint for NonConsecutive {
+    fn check(&self, board: &Board) -> Result<(), Error> {
+        (0..Board::COUNT_VALUES).try_for_each(|cell_id| self.check_cell(board, cell_id))
+    }
+
+    fn prune(&self, board: &Board, candidates: &mut [CandidateSet; Board::COUNT_VALUES]) {
+        for cell_id in 0..Board::COUNT_VALUES {
+            let value = board[cell_id].value();
+            // NOTE: `remove` ignores 0 and 10
+            if value != 0 {
+                for neighbor_id in orthogonal_neighbors(cell_id) {
+                    candidates[neighbor_id].remove(value - 1);
+                    candidates[neighbor_id].remove(value + 1);
+                }
+            }
+        }
+    }
+
+    fn check_cell(&self, board: &Board, cell_id: usize) -> Result<(), Error> {
+        let value = board[cell_id].value();
+        if !(1..=9).contains(&value) {
+            return Ok(());
+        }
+        // NOTE: unassigned neighbors hold 0, which is one less than 1
+        let neighbor_id = orthogonal_neighbors(cell_id)
+            .find(|neighbor_id| (1..=9).contains(&board[*neighbor_id].value()) && board[*neighbor_id].value().abs_diff(value) == 1);
+        if let Some(neighbor_id) = neighbor_id {
+            let (first_id, second_id) = (cell_id.min(neighbor_id), cell_id.max(neighbor_id));
+            let cells = vec![(Coord::from_cell_id(first_id), board[first_id].value()), (Coord::from_cell_id(second_id), board[second_id].value())];
+            return Err(Error::RuleError { rule: "no consecutive values in orthogonally adjacent cells".to_string(), cells });
+        }
+        Ok(())
+    }
+
+    fn violations(&self, board: &Board) -> Vec<Error> {
+        let mut errors = vec![];
+        for cell_id in 0..Board::COUNT_VALUES {
+            let value = board[cell_id].value();
+            if !(1..=9).contains(&value) {
+                continue;
+            }
+            for neighbor_id in orthogonal_neighbors(cell_id).filter(|neighbor_id| *neighbor_id > cell_id) {
+                let neighbor_value = board[neighbor_id].value();
+                if (1..=9).contains(&neighbor_value) && neighbor_value.abs_diff(value) == 1 {
+                    let cells = vec![(Coord::from_cell_id(cell_id), value), (Coord::from_cell_id(neighbor_id), neighbor_value)];
+                    errors.push(Error::RuleError { rule: "no consecutive values in orthogonally adjacent cells".to_string(), cells });
+                }
+            }
+        }
+        errors
+    }
+}
+
+/// The rules of a game: which sets of cells must hold the values 1 to 9
+#[derive(Clone,Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(into = "VariantData", try_from = "VariantData"))]
+pub struct Variant {
+    regions: Regions,
+    /// Are there no regions at all, so the game is a Latin square?
+    latin_square: bool,
+    /// Must both main diagonals hold the values 1 to 9 (Sudoku X)?
+    diagonals: bool,
+    /// Must the four 3×3 windows of Hypersudoku hold the values 1 to 9?
+    windows: bool,
+    /// Must cells a knight's move apart hold different values?
+    anti_knight: bool,
+    /// Must cells a king's move apart (diagonally adjacent included) hold different values?
+    anti_king: bool,
+    /// Must orthogonally adjacent cells hold values which are not consecutive?
+    non_consecutive: bool,
+    /// Lines whose values strictly increase from the bulb to the tip
+    thermometers: Vec<Thermometer>,
+    /// Shafts whose values sum up to the value in their circle
+    arrows: Vec<Arrow>,
+    /// The rules as checked by `verify`
+    constraints: Vec<Arc<dyn Constraint>>,
+    /// The houses of the rules as searched by the generic search
+    topology: Topology,
+}
+
+impl Variant {
+    /// The rules of the classic Sudoku
+    pub fn classic() -> Self {
+        Self::default()
+    }
+
+    /// These rules with the regions replacing the classic blocks
+    pub fn with_regions(mut self, regions: Regions) -> Self {
+        self.regions = regions;
+        self.update_topology();
+        self
+    }
+
+    /// These rules without regions: only the rows and the columns (and the other
+    /// houses of the rules) must hold the values 1 to 9, as in a Latin square
+    pub fn with_latin_square(mut self) -> Self {
+        self.latin_square = true;
+        self.update_topology();
+        self
+    }
+
+    /// These rules with both main diagonals holding the values 1 to 9 (Sudoku X)
+    pub fn with_diagonals(mut self) -> Self {
+        self.diagonals = true;
+        self.update_topology();
+        self
+    }
+
+    /// These rules with the four windows of Hypersudoku (windoku) holding the
+    /// values 1 to 9. The windows are the 3×3 squares with their top-left
+    /// corners at r2c2, r2c6, r6c2 and r6c6.
+    pub fn with_windows(mut self) -> Self {
+        self.windows = true;
+        self.update_topology();
+        self
+    }
+
+    /// These rules with cells a knight's move apart holding different values
+    pub fn with_anti_knight(mut self) -> Self {
+        self.anti_knight = true;
+        self.update_topology();
+        self
+    }
+
+    /// These rules with cells a king's move apart holding different values,
+    /// i.e. also diagonally adjacent cells
+    pub fn with_anti_king(mut self) -> Self {
+        self.anti_king = true;
+        self.update_topology();
+        self
+    }
+
+    /// These rules with orthogonally adjacent cells holding values which
+    /// differ by more than one
+    pub fn with_non_consecutive(mut self) -> Self {
+        self.non_consecutive = true;
+        self.update_topology();
+        self
+    }
+
+    /// These rules with the values along the thermometer strictly increasing from its bulb
+    pub fn with_thermometer(mut self, thermometer: Thermometer) -> Self {
+        self.thermometers.push(thermometer);
+        self.update_topology();
+        self
+    }
+
+    /// These rules with the values along the shaft of the arrow summing up to the value in its circle
+    pub fn with_arrow(mut self, arrow: Arrow) -> Self {
+        self.arrows.push(arrow);
+        self.update_topology();
+        self
+    }
+
+    /// The regions which must hold the values 1 to 9 besides rows and columns
+    pub fn regions(&self) -> &Regions {
+        &self.regions
+    }
+
+    /// Are there no regions, so only rows and columns must hold the values 1 to 9?
+    pub fn is_latin_square(&self) -> bool {
+        self.latin_square
+    }
+
+    /// Must both main diagonals hold the values 1 to 9?
+    pub fn has_diagonals(&self) -> bool {
+        self.diagonals
+    }
+
+    /// Must the four windows of Hypersudoku hold the values 1 to 9?
+    pub fn has_windows(&self) -> bool {
+        self.windows
+    }
+
+    /// Must cells a knight's move apart hold different values?
+    pub fn has_anti_knight(&self) -> bool {
+        self.anti_knight
+    }
+
+    /// Must cells a king's move apart hold different values?
+    pub fn has_anti_king(&self) -> bool {
+        self.anti_king
+    }
+
+    /// Must orthogonally adjacent cells hold values which are not consecutive?
+    pub fn has_non_consecutive(&self) -> bool {
+        self.non_consecutive
+    }
+
+    /// The lines whose values strictly increase from the bulb to the tip
+    pub fn thermometers(&self) -> &[Thermometer] {
+        &self.thermometers
+    }
+
+    /// The shafts whose values sum up to the value in their circle
+    pub fn arrows(&self) -> &[Arrow] {
+        &self.arrows
+    }
+
+    /// Are these the rules of the classic Sudoku?
+    pub fn is_classic(&self) -> bool {
+        self.regions.is_classic() && !self.latin_square && !self.diagonals && !self.windows && !self.anti_knight && !self.anti_king && !self.non_consecutive
+            && self.thermometers.is_empty() && self.arrows.is_empty()
+    }
+
+    /// The chess moves of the rules with a description of the cells they connect
+    fn chess_moves(&self) -> Vec<(&'static str, &'static [(isize, isize)])> {
+        let mut moves: Vec<(&'static str, &'static [(isize, isize)])> = vec![];
+        if self.anti_knight {
+            moves.push(("cells a knight's move apart", &KNIGHT_MOVES));
+        }
+        if self.anti_king {
+            moves.push(("cells a king's move apart", &KING_MOVES));
+        }
+        moves
+    }
+
+    /// Does the cell lie in one of the windows of Hypersudoku (if they are part of the rules)?
+    pub(crate) fn is_shaded(&self, row: usize, col: usize) -> bool {
+        let in_window = |i: usize| (1..=3).contains(&i) || (5..=7).contains(&i);
+        self.windows && in_window(row) && in_window(col)
+    }
+
+    /// Number of regions: 9 unless this is a Latin square
+    fn count_regions(&self) -> usize {
+        if self.latin_square { 0 } else { Board::COUNT_BLOCKS }
+    }
+
+    /// All houses with their descriptions: the columns, the rows, the regions, the diagonals and then the windows
+    pub(crate) fn houses(&self) -> Vec<(String, Vec<usize>)> {
+        let columns = (0..Board::COUNT_COLUMNS).map(|column_id| (house_name(9 + column_id), (0..9).map(|row| row * 9 + column_id).collect()));
+        let rows = (0..Board::COUNT_ROWS).map(|row_id| (house_name(row_id), (0..9).map(|col| row_id * 9 + col).collect()));
+        let regions = (0..self.count_regions()).map(|region_id| (self.regions.name(region_id), self.regions.cells(region_id).to_vec()));
+        let mut houses: Vec<(String, Vec<usize>)> = columns.chain(rows).chain(regions).collect();
+        if self.diagonals {
+            houses.push(("main diagonal".to_string(), (0..9).map(|i| i * 9 + i).collect()));
+            houses.push(("anti-diagonal".to_string(), (0..9).map(|i| i * 9 + 8 - i).collect()));
+        }
+        if self.windows {
+            for (name, top, left) in [("top-left", 1, 1), ("top-right", 1, 5), ("bottom-left", 5, 1), ("bottom-right", 5, 5)] {
+                houses.push((format!("window {name}"), (0..9).map(|i| (top + i / 3) * 9 + left + i % 3).collect()));
+            }
+        }
+        houses
+    }
+
+    /// The constraints of the rules in the order they are checked: the columns,
+    /// the rows, the regions, the diagonals, the windows, the chess moves, the
+    /// non-consecutive rule, the thermometers and then the arrows. A house repeating
+    /// a value yields a ``Error::ConstraintError``, two equal values a forbidden
+    /// chess move apart a ``Error::ConflictError``, and consecutive values in adjacent
+    /// cells, values out of order on a thermometer as well as an arrow whose shaft
+    /// cannot sum up to its circle a ``Error::RuleError``.
+    pub fn constraints(&self) -> &[Arc<dyn Constraint>] {
+        &self.constraints
+    }
+
+    pub(crate) fn topology(&self) -> &Topology {
+        &self.topology
+    }
+
+    /// The constraints the topology cannot express, which the generic search prunes with
+    pub(crate) fn hooked_constraints(&self) -> Vec<Arc<dyn Constraint>> {
+        self.arrows.iter().map(|arrow| Arc::new(arrow.clone()) as Arc<dyn Constraint>).collect()
+    }
+
+    /// Compute the constraints and the topology of the generic search from the rules
+    fn update_topology(&mut self) {
+        self.constraints = constraint::classic();
+        if self.latin_square || !self.regions.is_classic() {
+            self.constraints.pop();
+            self.constraints.extend((0..self.count_regions()).map(|region_id| {
+                Arc::new(House::new(self.regions.name(region_id), self.regions.cells(region_id).to_vec())) as Arc<dyn Constraint>
+            }));
+        }
+        // NOTE: the columns, the rows and the regions are the first houses
+        for (name, cells) in self.houses().into_iter().skip(18 + self.count_regions()) {
+            self.constraints.push(Arc::new(House::new(name, cells)));
+        }
+        if self.anti_knight {
+            self.constraints.push(Arc::new(AntiKnight));
+        }
+        if self.anti_king {
+            self.constraints.push(Arc::new(AntiKing));
+        }
+        if self.non_consecutive {
+            self.constraints.push(Arc::new(NonConsecutive));
+        }
+        for thermometer in self.thermometers.iter() {
+            self.constraints.push(Arc::new(thermometer.clone()));
+        }
+        self.constraints.extend(self.hooked_constraints());
+
+        let houses = self.houses().into_iter().map(|(_, cells)| cells).collect();
+        self.topology = Topology::from_houses(Board::COUNT_ROWS, houses);
+        for (_, moves) in self.chess_moves() {
+            for cell_id in 0..Board::COUNT_VALUES {
+                self.topology.add_peers(cell_id, cells_apart(cell_id, moves));
+            }
+        }
+        if self.non_consecutive {
+            for cell_id in 0..Board::COUNT_VALUES {
+                self.topology.add_non_consecutive(cell_id, orthogonal_neighbors(cell_id));
+            }
+        }
+        for thermometer in self.thermometers.iter() {
+            self.topology.add_thermometer(thermometer.cells.clone());
+        }
+    }
+
+    /// The line joining the cells if they are consecutive cells of a thermometer (`=`) or an arrow (`-`)
+    fn join(&self, cell_id: usize, other_id: usize) -> Option<char> {
+        let joins = |cells: &[usize]| cells.windows(2).any(|step| (step[0], step[1]) == (cell_id, other_id) || (step[1], step[0]) == (cell_id, other_id));
+        if self.thermometers.iter().any(|thermometer| joins(&thermometer.cells)) {
+            Some('=')
+        } else if self.arrows.iter().any(|arrow| joins(&arrow.cells)) {
+            Some('-')
+        } else {
+            None
+        }
+    }
+
+    /// Render the board with lines along the borders of the regions (only around
+    /// the board for a Latin square) and the
+    /// windows of Hypersudoku shaded. Unassigned cells are shown as `.`.
+    /// The bulbs of thermometers are shown as `(5)` and their other cells as `=5=`,
+    /// joined by `=` or `‖` where the line runs between orthogonal neighbors of a region.
+    /// Likewise the circles of arrows are shown as `<5>` and their shafts as `-5-`, joined by `-` or `|`.
+    pub(crate) fn render(&self, board: &Board) -> String {
+        let size = Board::COUNT_ROWS;
+        let region = |row: usize, col: usize| if self.latin_square { 0 } else { self.regions.region_of(row * size + col) };
+        // NOTE: a vertical border lies left of column `col` in `row`, a horizontal one above `row` in `col`
+        let vertical = |row: usize, col: usize| col == 0 || col == size || region(row, col - 1) != region(row, col);
+        let horizontal = |row: usize, col: usize| row == 0 || row == size || region(row - 1, col) != region(row, col);
+        // NOTE: adjacent shaded cells always lie in the same window
+        let shaded = |rows: &[usize], cols: &[usize]| rows.iter().all(|row| cols.iter().all(|col| *row < size && *col < size && self.is_shaded(*row, *col)));
+
+        let mut out = String::new();
+        for row in 0..=size {
+            for col in 0..=size {
+                let up = row > 0 && vertical(row - 1, col);
+                let down = row < size && vertical(row, col);
+                let left = col > 0 && horizontal(row, col - 1);
+                let right = col < size && horizontal(row, col);
+                match joint(up, down, left, right) {
+                    ' ' if row > 0 && col > 0 && shaded(&[row - 1, row], &[col - 1, col]) => out.push('░'),
+                    shown => out.push(shown),
+                }
+                if col < size {
+                    match horizontal(row, col) {
+                        true => out.push_str("───"),
+                        false => match self.join((row - 1) * size + col, row * size + col) {
+                            Some('=') => out.push_str(" ‖ "),
+                            Some(_) => out.push_str(" | "),
+                            None if shaded(&[row - 1, row], &[col]) => out.push_str("░░░"),
+                            None => out.push_str("   "),
+                        },
+                    }
+                }
+            }
+            out.push('\n');
+
+            if row < size {
+                for col in 0..=size {
+                    match vertical(row, col) {
+                        true => out.push('│'),
+                        false => match self.join(row * size + col - 1, row * size + col) {
+                            Some(line) => out.push(line),
+                            None if shaded(&[row], &[col - 1, col]) => out.push('░'),
+                            None => out.push(' '),
+                        },
+                    }
+                    if col < size {
+                        let cell_id = row * size + col;
+                        let (before, after) = match self.thermometers.iter().find(|thermometer| thermometer.cells.contains(&cell_id)) {
+                            Some(thermometer) if thermometer.bulb() == cell_id => ('(', ')'),
+                            Some(_) => ('=', '='),
+                            None => match self.arrows.iter().find(|arrow| arrow.cells.contains(&cell_id)) {
+                                Some(arrow) if arrow.circle() == cell_id => ('<', '>'),
+                                Some(_) => ('-', '-'),
+                                None if self.is_shaded(row, col) => ('░', '░'),
+                                None => (' ', ' '),
+                            },
+                        };
+                        let shown = match board[cell_id].value() {
+                            value @ 1..=9 => (b'0' + value) as char,
+                            _ => '.',
+                        };
+                        out.push_str(&format!("{before}{shown}{after}"));
+                    }
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+impl Default for Variant {
+    fn default() -> Self {
+        let mut variant = Self {
+            regions: Regions::classic(), latin_square: false, diagonals: false, windows: false, anti_knight: false, anti_king: false,
+            non_consecutive: false, thermometers: vec![], arrows: vec![], constraints: vec![],
+            topology: Topology::from_houses(0, vec![]),
+        };
+        variant.update_topology();
+        variant
+    }
+}
+
+/// The rules as serialized: the flags of the rules, the region of every cell
+/// as a digit like `Regions::from_str` parses it and the shapes in the standard notation
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+struct VariantData {
+    regions: Option<String>,
+    latin_square: bool,
+    diagonals: bool,
+    windows: bool,
+    anti_knight: bool,
+    anti_king: bool,
+    non_consecutive: bool,
+    thermometers: Vec<String>,
+    arrows: Vec<String>,
+}
+
+#[cfg(feature = "serde")]
+impl Default for VariantData {
+    fn default() -> Self {
+        Variant::classic().into()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Variant> for VariantData {
+    fn from(variant: Variant) -> Self {
+        let regions = (!variant.regions.is_classic())
+            .then(|| variant.regions.region_of.iter().map(|region_id| char::from(b'1' + *region_id as u8)).collect());
+        Self {
+            regions, latin_square: variant.latin_square, diagonals: variant.diagonals, windows: variant.windows,
+            anti_knight: variant.anti_knight, anti_king: variant.anti_king, non_consecutive: variant.non_consecutive,
+            thermometers: variant.thermometers.iter().map(|thermometer| thermometer.to_string()).collect(),
+            arrows: variant.arrows.iter().map(|arrow| arrow.to_string()).collect(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<VariantData> for Variant {
+    type Error = Error;
+
+    fn try_from(data: VariantData) -> Result<Self, Error> {
+        let mut variant = Variant::classic();
+        if let Some(regions) = data.regions {
+            variant.regions = regions.parse()?;
+        }
+        variant.latin_square = data.latin_square;
+        variant.diagonals = data.diagonals;
+        variant.windows = data.windows;
+        variant.anti_knight = data.anti_knight;
+        variant.anti_king = data.anti_king;
+        variant.non_consecutive = data.non_consecutive;
+        variant.thermometers = data.thermometers.iter().map(|thermometer| thermometer.parse()).collect::<Result<_, Error>>()?;
+        variant.arrows = data.arrows.iter().map(|arrow| arrow.parse()).collect::<Result<_, Error>>()?;
+        variant.update_topology();
+        Ok(variant)
+    }
+}
+
+/// Box-drawing character joining the lines leaving a point upwards, downwards, to the left and to the right
+fn joint(up: bool, down: bool, left: bool, right: bool) -> char {
+    match (up, down, left, right) {
+        (false, false, false, false) => ' ',
+        (true, true, false, false) | (true, false, false, false) | (false, true, false, false) => '│',
+        (false, false, true, true) | (false, false, true, false) | (false, false, false, true) => '─',
+        (false, true, false, true) => '┌',
+        (false, true, true, false) => '┐',
+        (true, false, false, true) => '└',
+        (true, false, true, false) => '┘',
+        (true, true, false, true) => '├',
+        (true, true, true, false) => '┤',
+        (false, true, true, true) => '┬',
+        (true, false, true, true) => '┴',
+        (true, true, true, true) => '┼',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The classic blocks with the region of some cells changed
+    fn changed_blocks(changes: &[(usize, usize)]) -> [usize; Board::COUNT_VALUES] {
+        let mut region_of = BLOCK_OF;
+        for (cell_id, region_id) in changes.iter() {
+            region_of[*cell_id] = *region_id;
+        }
+        region_of
+    }
+
+    fn layout_error(region_of: [usize; Board::COUNT_VALUES]) -> String {
+        match Regions::new(region_of) {
+            Err(Error::LayoutError { reason }) => reason,
+            other => panic!("expected a layout error but got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn new_accepts_connected_regions_of_nine_cells() {
+        assert!(Regions::new(BLOCK_OF).unwrap().is_classic());
+        // NOTE: r1c4 moves to the top-left block and r3c3 to the top-middle one
+        let regions = Regions::new(changed_blocks(&[(3, 0), (20, 1)])).unwrap();
+        assert_eq!(regions.region_of(3), 0);
+        assert!(!regions.is_classic());
+    }
+
+    #[test]
+    fn new_rejects_regions_outside_of_the_board() {
+        assert_eq!(layout_error(changed_blocks(&[(0, 9)])), "region 10 of cell r1c1 lies outside of 1..=9");
+    }
+
+    #[test]
+    fn new_rejects_regions_of_other_sizes() {
+        assert_eq!(layout_error(changed_blocks(&[(3, 0)])), "region 1 has 10 instead of 9 cells");
+    }
+
+    #[test]
+    fn new_rejects_disconnected_regions() {
+        // NOTE: r1c1 and r9c9 swap their blocks, so neither is connected to the rest
+        assert_eq!(layout_error(changed_blocks(&[(0, 8), (80, 0)])), "the cells of region 1 are not connected");
+    }
+}